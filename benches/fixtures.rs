@@ -0,0 +1,105 @@
+//! Synthetic fixtures for the Criterion benchmarks.
+//!
+//! Every generator is deterministic so runs on the same machine compare like
+//! with like.
+
+use repo_context::domain::{Chunk, FileInfo};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Source languages the chunking benchmarks cover.
+pub const LANGUAGES: &[(&str, &str)] = &[("rust", ".rs"), ("python", ".py"), ("javascript", ".js")];
+
+/// A source file of `lines` lines, made of small functions with doc comments.
+pub fn source_file(language: &str, lines: usize) -> String {
+    let mut out = String::new();
+    let mut n = 0usize;
+    let mut written = 0usize;
+    while written < lines {
+        let body = match language {
+            "rust" => format!(
+                "/// Returns item {n} scaled by the factor.\npub fn item_{n}(factor: u64) -> u64 {{\n    let base = {n} * factor;\n    base.wrapping_add(factor)\n}}\n\n"
+            ),
+            "python" => format!(
+                "def item_{n}(factor):\n    \"\"\"Return item {n} scaled by the factor.\"\"\"\n    base = {n} * factor\n    return base + factor\n\n"
+            ),
+            _ => format!(
+                "/** Returns item {n} scaled by the factor. */\nexport function item{n}(factor) {{\n  const base = {n} * factor;\n  return base + factor;\n}}\n\n"
+            ),
+        };
+        for line in body.lines().take(lines - written) {
+            let _ = writeln!(out, "{line}");
+            written += 1;
+        }
+        n += 1;
+    }
+    out
+}
+
+/// Writes a repository of `files` source files spread over nested directories.
+pub fn synthetic_repo(root: &Path, files: usize) {
+    fs::write(root.join("README.md"), "# Synthetic\n\nBenchmark fixture.\n").expect("readme");
+    for i in 0..files {
+        let (language, extension) = LANGUAGES[i % LANGUAGES.len()];
+        let dir = root.join(format!("src/module_{}/sub_{}", i % 20, i % 7));
+        fs::create_dir_all(&dir).expect("create fixture dir");
+        fs::write(dir.join(format!("file_{i}{extension}")), source_file(language, 40))
+            .expect("write fixture file");
+    }
+}
+
+/// File metadata for chunking `content` as `language`.
+pub fn file_info(language: &str, extension: &str, content: &str) -> FileInfo {
+    let relative_path = format!("src/bench{extension}");
+    FileInfo {
+        path: PathBuf::from("/tmp/bench").join(&relative_path),
+        relative_path,
+        size_bytes: content.len() as u64,
+        extension: extension.to_string(),
+        language: language.to_string(),
+        id: "bench".to_string(),
+        priority: 0.5,
+        token_estimate: content.len() / 4,
+        tags: BTreeSet::new(),
+        is_readme: false,
+        is_config: false,
+        is_doc: false,
+        is_env_file: false,
+        is_symlink: false,
+        symlink_target: None,
+        complexity_estimate: 0,
+        priority_explanation: None,
+    }
+}
+
+/// `count` small chunks across `count / 100` files, as the chunkers emit them
+/// for files of many short functions.
+pub fn small_chunks(count: usize) -> Vec<Chunk> {
+    (0..count)
+        .map(|i| {
+            let path = format!("src/file_{}.rs", i / 100);
+            let start_line = (i % 100) * 5 + 1;
+            let content = format!("fn item_{i}() -> u64 {{\n    {i}\n}}\n\n");
+            Chunk {
+                id: format!("{path}:{start_line}"),
+                path,
+                language: "rust".to_string(),
+                start_line,
+                end_line: start_line + 4,
+                token_estimate: content.len() / 4,
+                content,
+                priority: 0.5,
+                tags: BTreeSet::new(),
+                file_id: format!("file_{}", i / 100),
+                chunk_index: i % 100,
+                chunks_in_file: 100,
+                byte_start: None,
+                byte_end: None,
+                content_sha256: String::new(),
+                file_sha256: String::new(),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,101 @@
+//! Criterion benchmarks for scanning and chunking.
+//!
+//! Run with `cargo bench --bench scan_chunk`. To catch regressions, save a
+//! baseline on the base branch (`cargo bench --bench scan_chunk --
+//! --save-baseline main`) and compare the change against it (`--baseline
+//! main`). Criterion reports a change as significant at p < 0.05.
+//!
+//! Expected scaling, which is what review should check:
+//! - `scan/1000_files` is dominated by directory walking and the binary and
+//!   minified probes, so it is linear in file count.
+//! - `chunk_content/<language>/<lines>` is linear in line count. Rust,
+//!   Python, and JavaScript go through tree-sitter, so 5000 lines should cost
+//!   about ten times 500 lines. A jump between sizes points at quadratic work
+//!   in boundary detection.
+//! - `coalesce_small_chunks_with_max/10000` sorts once and merges in a single
+//!   pass, so it should stay well below the time to chunk those files.
+//! - `read_file/<reader>/<KiB>` compares `read_file_safe`, which copies the
+//!   file into a `String`, with `read_file_mmap`, which only validates the
+//!   mapped bytes as UTF-8. The gap should widen with file size.
+//!
+//! No absolute numbers are recorded here because they depend on the machine.
+//! See `bench/README.md` for the end-to-end export benchmark.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use repo_context::chunk::{chunk_content, coalesce_small_chunks_with_max, ChunkOptions};
+use repo_context::scan::scanner::FileScanner;
+use repo_context::utils::{read_file_mmap, read_file_safe, Tokenizer};
+use std::hint::black_box;
+use tempfile::TempDir;
+
+mod fixtures;
+
+fn bench_scan(c: &mut Criterion) {
+    let repo = TempDir::new().expect("temp repo");
+    fixtures::synthetic_repo(repo.path(), 1000);
+
+    let mut group = c.benchmark_group("scan");
+    group.throughput(Throughput::Elements(1000));
+    group.sample_size(20);
+    group.bench_function("1000_files", |b| {
+        b.iter(|| {
+            let mut scanner = FileScanner::new(repo.path().to_path_buf());
+            black_box(scanner.scan().expect("scan"))
+        })
+    });
+    group.finish();
+}
+
+fn bench_chunk_content(c: &mut Criterion) {
+    let mut group = c.benchmark_group("chunk_content");
+    for &(language, extension) in fixtures::LANGUAGES {
+        for lines in [500usize, 1000, 5000] {
+            let content = fixtures::source_file(language, lines);
+            let file = fixtures::file_info(language, extension, &content);
+            group.throughput(Throughput::Elements(lines as u64));
+            group.bench_with_input(BenchmarkId::new(language, lines), &content, |b, content| {
+                b.iter(|| {
+                    chunk_content(&file, content, &ChunkOptions::new(800, 100)).expect("chunk")
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+fn bench_coalesce(c: &mut Criterion) {
+    let chunks = fixtures::small_chunks(10_000);
+    let mut group = c.benchmark_group("coalesce_small_chunks_with_max");
+    group.throughput(Throughput::Elements(chunks.len() as u64));
+    group.bench_function("10000", |b| {
+        b.iter_batched(
+            || chunks.clone(),
+            |chunks| coalesce_small_chunks_with_max(chunks, 120, 800, Tokenizer::Heuristic),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+fn bench_read_file(c: &mut Criterion) {
+    let dir = TempDir::new().expect("temp dir");
+    let mut group = c.benchmark_group("read_file");
+    for kib in [128usize, 512, 1024] {
+        let path = dir.path().join(format!("large_{kib}.rs"));
+        let mut content = fixtures::source_file("rust", kib * 64);
+        content.truncate(kib * 1024);
+        std::fs::write(&path, &content).expect("write fixture");
+
+        group.throughput(Throughput::Bytes(content.len() as u64));
+        group.bench_with_input(BenchmarkId::new("read_file_safe", kib), &path, |b, path| {
+            b.iter(|| black_box(read_file_safe(path, None, None).expect("read")))
+        });
+        group.bench_with_input(BenchmarkId::new("read_file_mmap", kib), &path, |b, path| {
+            b.iter(|| black_box(read_file_mmap(path).expect("read")))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_scan, bench_chunk_content, bench_coalesce, bench_read_file);
+criterion_main!(benches);
@@ -0,0 +1,61 @@
+//! Chunks arbitrary content under an arbitrary language name.
+//!
+//! Known languages route to the tree-sitter, Markdown, and line chunkers;
+//! anything else exercises the line fallback.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use repo_context::chunk::{chunk_content, ChunkOptions, ContentTransforms};
+use repo_context::domain::FileInfo;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+const LANGUAGES: &[&str] =
+    &["rust", "python", "javascript", "typescript", "go", "markdown", "text"];
+
+fuzz_target!(|input: (u8, String, String, u16)| {
+    let (selector, language, content, max_tokens) = input;
+    let language = match LANGUAGES.get(selector as usize) {
+        Some(known) => known.to_string(),
+        None => language,
+    };
+    let file = FileInfo {
+        path: PathBuf::from("/fuzz/input"),
+        relative_path: "input".to_string(),
+        size_bytes: content.len() as u64,
+        extension: String::new(),
+        language,
+        id: "fuzz".to_string(),
+        priority: 0.5,
+        token_estimate: 0,
+        tags: BTreeSet::new(),
+        is_readme: false,
+        is_config: false,
+        is_doc: false,
+        is_env_file: false,
+        is_symlink: false,
+        symlink_target: None,
+        complexity_estimate: 0,
+        priority_explanation: None,
+    };
+    let transforms = ContentTransforms {
+        strip_comments: selector & 1 == 1,
+        normalize_whitespace: selector & 2 == 2,
+        extract_docs: selector & 4 == 4,
+    };
+    let max_tokens = usize::from(max_tokens).max(1);
+    let options = ChunkOptions {
+        transforms,
+        priority_decay: 0.1,
+        ..ChunkOptions::new(max_tokens, max_tokens / 8)
+    };
+    let Ok(chunks) = chunk_content(&file, &content, &options) else {
+        return;
+    };
+    let line_count = content.lines().count().max(1);
+    for chunk in &chunks {
+        assert!(chunk.start_line >= 1 && chunk.start_line <= chunk.end_line, "{chunk:?}");
+        assert!(chunk.end_line <= line_count, "{chunk:?}");
+    }
+});
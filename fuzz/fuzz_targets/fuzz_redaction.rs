@@ -0,0 +1,32 @@
+//! Runs the full redaction pipeline on arbitrary input.
+//!
+//! The default and the most aggressive (entropy + paranoid + structure-safe)
+//! redactors both run on every input, along with the dry-run report. Run
+//! with `-timeout=1` so any input that takes over a second counts as a crash.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use once_cell::sync::Lazy;
+use repo_context::domain::RedactionConfig;
+use repo_context::redact::Redactor;
+
+static REDACTORS: Lazy<[Redactor; 2]> = Lazy::new(|| {
+    let cfg = RedactionConfig::default();
+    [
+        Redactor::from_config(false, false, false, &cfg),
+        Redactor::from_config(true, true, true, &cfg),
+    ]
+});
+
+fuzz_target!(|data: &[u8]| {
+    let text = String::from_utf8_lossy(data);
+    for redactor in REDACTORS.iter() {
+        let outcome =
+            redactor.redact_with_language_report(&text, "python", ".py", "app.py", "src/app.py");
+        assert!(outcome.counts.values().all(|&count| count > 0));
+        for found in redactor.redact_dry_run(&text, "text") {
+            assert!(found.start < found.end && text.is_char_boundary(found.end));
+        }
+    }
+});
@@ -0,0 +1,141 @@
+//! Cyclomatic complexity estimation
+//!
+//! Counts decision points (`if`, loops, `match`/`switch` arms, boolean
+//! operators, exception handlers) without building an AST. Keywords inside
+//! strings are counted too, so treat the result as an approximation.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static C_LIKE_KEYWORDS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(?:if|for|while|case|catch)\b").expect("valid regex"));
+static RUST_KEYWORDS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(?:if|for|while)\b").expect("valid regex"));
+static PYTHON_KEYWORDS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(?:if|elif|for|while|except|case|and|or)\b").expect("valid regex"));
+static RUBY_KEYWORDS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:if|elsif|unless|for|while|until|when|rescue|and|or)\b").expect("valid regex")
+});
+
+/// Languages with a complexity estimator.
+const SUPPORTED_LANGUAGES: &[&str] = &[
+    "rust",
+    "python",
+    "javascript",
+    "typescript",
+    "go",
+    "java",
+    "kotlin",
+    "c",
+    "cpp",
+    "csharp",
+    "php",
+    "swift",
+    "scala",
+    "ruby",
+];
+
+/// Whether [`estimate_cyclomatic_complexity`] understands `language`.
+pub fn supports_language(language: &str) -> bool {
+    SUPPORTED_LANGUAGES.contains(&language)
+}
+
+/// Estimate the cyclomatic complexity of a source file or fragment.
+///
+/// Starts at 1 and adds one per decision point: `if` / `else if`, `for`,
+/// `while`, `match` arms (Rust) or `case` labels, `catch` / `except`, and
+/// `&&` / `||` (`and` / `or` in Python and Ruby). Line comments are ignored.
+///
+/// Returns 0 for languages without an estimator (prose, config, data).
+pub fn estimate_cyclomatic_complexity(content: &str, language: &str) -> usize {
+    if !supports_language(language) {
+        return 0;
+    }
+
+    let comment_prefix = match language {
+        "python" | "ruby" => "#",
+        _ => "//",
+    };
+    let keywords: &Regex = match language {
+        "rust" => &RUST_KEYWORDS,
+        "python" => &PYTHON_KEYWORDS,
+        "ruby" => &RUBY_KEYWORDS,
+        _ => &C_LIKE_KEYWORDS,
+    };
+    let counts_symbolic_ops = !matches!(language, "python");
+
+    let mut complexity = 1;
+    for line in content.lines() {
+        let code = line.split(comment_prefix).next().unwrap_or("");
+        complexity += keywords.find_iter(code).count();
+        if counts_symbolic_ops {
+            complexity += code.matches("&&").count() + code.matches("||").count();
+        }
+        if language == "rust" {
+            complexity += code.matches("=>").count();
+        }
+    }
+    complexity
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn straight_line_code_has_complexity_one() {
+        assert_eq!(estimate_cyclomatic_complexity("fn main() {\n    run();\n}\n", "rust"), 1);
+        assert_eq!(estimate_cyclomatic_complexity("def main():\n    run()\n", "python"), 1);
+    }
+
+    #[test]
+    fn counts_rust_decision_points() {
+        let code = r#"
+fn classify(x: i32) -> i32 {
+    if x > 0 && x < 10 {
+        for _ in 0..x {}
+    } else if x < 0 {
+        while false {}
+    }
+    // if this comment counted, the total would be off
+    match x {
+        1 => 1,
+        2 => 2,
+        _ => 0,
+    }
+}
+"#;
+        // 1 + if + && + for + else-if + while + 3 match arms
+        assert_eq!(estimate_cyclomatic_complexity(code, "rust"), 9);
+    }
+
+    #[test]
+    fn counts_python_decision_points() {
+        let code = r#"
+def classify(x):
+    if x > 0 and x < 10:
+        for i in range(x):
+            pass
+    elif x < 0 or x > 100:
+        while x:
+            x += 1
+    try:
+        risky()
+    except ValueError:  # if in a comment is ignored
+        pass
+"#;
+        // 1 + if + and + for + elif + or + while + except
+        assert_eq!(estimate_cyclomatic_complexity(code, "python"), 8);
+    }
+
+    #[test]
+    fn counts_c_like_switch_cases() {
+        let code = "switch (x) {\n  case 1: break;\n  case 2: break;\n}\nif (a || b) {}\n";
+        assert_eq!(estimate_cyclomatic_complexity(code, "javascript"), 5);
+    }
+
+    #[test]
+    fn unsupported_language_is_zero() {
+        assert_eq!(estimate_cyclomatic_complexity("if this then that", "markdown"), 0);
+    }
+}
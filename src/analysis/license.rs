@@ -0,0 +1,142 @@
+//! License file detection
+//!
+//! Classifies root-level license files as SPDX identifiers by matching
+//! distinctive phrases from each license text. Unrecognized texts yield
+//! `None` rather than a guess.
+
+use std::path::Path;
+
+use crate::utils::read_file_safe;
+
+/// File names checked at the repository root, in priority order.
+const LICENSE_FILE_NAMES: &[&str] = &[
+    "LICENSE",
+    "LICENSE.md",
+    "LICENSE.txt",
+    "LICENCE",
+    "LICENCE.md",
+    "COPYING",
+    "COPYING.md",
+    "LICENSE-MIT",
+    "LICENSE-APACHE",
+];
+
+/// A license file and the SPDX identifier it was classified as.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectedLicense {
+    /// License file name relative to the repository root.
+    pub file: String,
+    /// SPDX license identifier (e.g. `MIT`, `Apache-2.0`).
+    pub spdx_id: &'static str,
+}
+
+/// Find and classify the first recognizable license file under `root_path`.
+pub fn detect_license_file(root_path: &Path) -> Option<DetectedLicense> {
+    LICENSE_FILE_NAMES.iter().find_map(|name| {
+        let path = root_path.join(name);
+        if !path.is_file() {
+            return None;
+        }
+        let (content, _) = read_file_safe(&path, Some(16_384), None).ok()?;
+        classify_license_text(&content)
+            .map(|spdx_id| DetectedLicense { file: (*name).to_string(), spdx_id })
+    })
+}
+
+/// Classify license text as an SPDX identifier using keyword matching.
+pub fn classify_license_text(text: &str) -> Option<&'static str> {
+    let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    let has = |needle: &str| normalized.contains(needle);
+
+    // GPL variants share phrasing, so check the more specific ones first.
+    if has("gnu affero general public license") {
+        return Some("AGPL-3.0");
+    }
+    if has("gnu lesser general public license") {
+        return Some(if has("version 2.1") { "LGPL-2.1" } else { "LGPL-3.0" });
+    }
+    if has("gnu general public license") {
+        if has("version 3") {
+            return Some("GPL-3.0");
+        }
+        if has("version 2") {
+            return Some("GPL-2.0");
+        }
+    }
+    if has("apache license") && has("version 2.0") {
+        return Some("Apache-2.0");
+    }
+    if has("mozilla public license") && has("2.0") {
+        return Some("MPL-2.0");
+    }
+    if has("permission is hereby granted, free of charge") {
+        return Some("MIT");
+    }
+    if has("permission to use, copy, modify, and/or distribute this software for any purpose") {
+        return Some("ISC");
+    }
+    if has("redistribution and use in source and binary forms") {
+        return Some(if has("neither the name") { "BSD-3-Clause" } else { "BSD-2-Clause" });
+    }
+    if has("this is free and unencumbered software released into the public domain") {
+        return Some("Unlicense");
+    }
+    None
+}
+
+/// Whether a declared SPDX expression (e.g. `MIT OR Apache-2.0`) omits the
+/// license found on disk. `-only` / `-or-later` suffixes are ignored.
+pub fn license_mismatch(declared: &str, detected: &str) -> bool {
+    let base = |id: &str| {
+        id.trim_end_matches('+')
+            .trim_end_matches("-only")
+            .trim_end_matches("-or-later")
+            .to_ascii_lowercase()
+    };
+    let detected = base(detected);
+    !declared
+        .split(|c: char| c.is_whitespace() || matches!(c, '/' | '(' | ')'))
+        .filter(|token| !matches!(*token, "" | "OR" | "AND" | "WITH"))
+        .any(|token| base(token) == detected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const MIT: &str = "MIT License\n\nCopyright (c) 2024 Example\n\nPermission is hereby granted, free of charge, to any person obtaining a copy\nof this software...";
+    const APACHE: &str = "                                 Apache License\n                           Version 2.0, January 2004\n                        http://www.apache.org/licenses/";
+    const GPL3: &str = "                    GNU GENERAL PUBLIC LICENSE\n                       Version 3, 29 June 2007\n";
+    const BSD3: &str = "Redistribution and use in source and binary forms, with or without\nmodification, are permitted...\n3. Neither the name of the copyright holder nor the names of its contributors";
+    const ISC: &str = "ISC License\n\nPermission to use, copy, modify, and/or distribute this software for any\npurpose with or without fee is hereby granted";
+
+    #[test]
+    fn classifies_common_licenses() {
+        assert_eq!(classify_license_text(MIT), Some("MIT"));
+        assert_eq!(classify_license_text(APACHE), Some("Apache-2.0"));
+        assert_eq!(classify_license_text(GPL3), Some("GPL-3.0"));
+        assert_eq!(classify_license_text(BSD3), Some("BSD-3-Clause"));
+        assert_eq!(classify_license_text(ISC), Some("ISC"));
+        assert_eq!(classify_license_text("All rights reserved."), None);
+    }
+
+    #[test]
+    fn detects_license_from_copying_file() {
+        let tmp = TempDir::new().expect("tmp");
+        fs::write(tmp.path().join("COPYING"), GPL3).expect("write copying");
+
+        let detected = detect_license_file(tmp.path()).expect("license");
+        assert_eq!(detected, DetectedLicense { file: "COPYING".to_string(), spdx_id: "GPL-3.0" });
+    }
+
+    #[test]
+    fn detects_declared_license_mismatch() {
+        assert!(!license_mismatch("MIT", "MIT"));
+        assert!(!license_mismatch("MIT OR Apache-2.0", "Apache-2.0"));
+        assert!(!license_mismatch("MIT/Apache-2.0", "MIT"));
+        assert!(!license_mismatch("GPL-3.0-only", "GPL-3.0"));
+        assert!(license_mismatch("Apache-2.0", "MIT"));
+    }
+}
@@ -0,0 +1,16 @@
+//! Static code analysis helpers
+//!
+//! Lightweight, regex-based metrics used as ranking signals. These are
+//! estimates, not full parsers.
+
+pub mod complexity;
+pub mod license;
+pub mod openapi;
+pub mod python_docs;
+pub mod sbom;
+
+pub use complexity::estimate_cyclomatic_complexity;
+pub use license::{detect_license_file, DetectedLicense};
+pub use openapi::{extract_openapi_routes, RouteSpec};
+pub use python_docs::{extract_python_docstrings, DocstringEntry};
+pub use sbom::{generate_sbom, SbomDocument, SbomPackage};
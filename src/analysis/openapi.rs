@@ -0,0 +1,263 @@
+//! HTTP route extraction
+//!
+//! Finds route declarations in chunk content by pattern: actix-web/Rocket
+//! attributes (`#[get("/path")]`), axum `.route("/path", get(handler))`,
+//! Flask/FastAPI decorators (`@app.route(...)`, `@app.get(...)`), and NestJS
+//! method decorators (`@Get("/path")`). Prefixes added by routers,
+//! blueprints, or controllers are not resolved, so paths are as written.
+
+use crate::domain::Chunk;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashSet;
+
+static RUST_ROUTE_ATTR: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"^\s*#\[\s*(?:[\w:]+::)?(get|post|put|delete|patch|head|options)\s*\(\s*"([^"]*)""#,
+    )
+    .expect("valid regex")
+});
+static AXUM_ROUTE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\.route\(\s*"([^"]*)"\s*,(.*)"#).expect("valid regex"));
+static AXUM_METHOD_ROUTER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(get|post|put|delete|patch|head|options)\(\s*([\w:]+)\s*\)")
+        .expect("valid regex")
+});
+static FLASK_ROUTE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^\s*@\w+(?:\.\w+)*\.route\(\s*["']([^"']*)["'](.*)"#).expect("valid regex")
+});
+static PYTHON_METHOD_DECORATOR: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"^\s*@\w+(?:\.\w+)*\.(get|post|put|delete|patch|head|options)\(\s*["']([^"']*)["']"#,
+    )
+    .expect("valid regex")
+});
+static FLASK_METHODS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"methods\s*=\s*[\[(]([^\])]*)[\])]").expect("valid regex"));
+static NEST_ROUTE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"^\s*@(Get|Post|Put|Delete|Patch|Head|Options|All)\(\s*(?:["'`]([^"'`]*)["'`])?\s*\)"#,
+    )
+    .expect("valid regex")
+});
+static RUST_FN: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bfn\s+(\w+)").expect("valid regex"));
+static PYTHON_DEF: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*(?:async\s+)?def\s+(\w+)").expect("valid regex"));
+static TS_METHOD: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:(?:public|private|protected|static|async)\s+)*(\w+)\s*\(")
+        .expect("valid regex")
+});
+
+/// Maximum number of lines searched below a route annotation for its handler.
+const HANDLER_LOOKAHEAD: usize = 8;
+
+/// One HTTP route found in a chunk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteSpec {
+    /// Upper-case HTTP method (`GET`, `POST`, ...; `ALL` for NestJS `@All`).
+    pub method: String,
+    /// Route path as written in the source.
+    pub path: String,
+    /// Name of the handler function, or empty when it could not be found.
+    pub handler_name: String,
+    /// Chunk the route was declared in.
+    pub chunk_id: String,
+}
+
+/// Extract HTTP routes from `chunks`.
+///
+/// Routes declared in overlapping chunks of the same file are reported once,
+/// for the first chunk they appear in. The result is sorted by path, then
+/// method.
+pub fn extract_openapi_routes(chunks: &[Chunk]) -> Vec<RouteSpec> {
+    let mut seen: HashSet<(String, String, String, String)> = HashSet::new();
+    let mut routes = Vec::new();
+    for chunk in chunks {
+        let found = match chunk.language.as_str() {
+            "rust" => rust_routes(&chunk.content),
+            "python" => python_routes(&chunk.content),
+            "typescript" | "javascript" => nest_routes(&chunk.content),
+            _ => continue,
+        };
+        for (method, path, handler_name) in found {
+            let key = (chunk.path.clone(), method.clone(), path.clone(), handler_name.clone());
+            if seen.insert(key) {
+                routes.push(RouteSpec { method, path, handler_name, chunk_id: chunk.id.clone() });
+            }
+        }
+    }
+    routes.sort_by(|a, b| a.path.cmp(&b.path).then_with(|| a.method.cmp(&b.method)));
+    routes
+}
+
+type FoundRoute = (String, String, String);
+
+fn rust_routes(content: &str) -> Vec<FoundRoute> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut routes = Vec::new();
+    for (idx, line) in lines.iter().enumerate() {
+        if let Some(caps) = RUST_ROUTE_ATTR.captures(line) {
+            let handler = handler_after(&lines, idx, &RUST_FN);
+            routes.push((caps[1].to_ascii_uppercase(), caps[2].to_string(), handler));
+        } else if let Some(caps) = AXUM_ROUTE.captures(line) {
+            for router in AXUM_METHOD_ROUTER.captures_iter(&caps[2]) {
+                let handler = router[2].rsplit("::").next().unwrap_or(&router[2]).to_string();
+                routes.push((router[1].to_ascii_uppercase(), caps[1].to_string(), handler));
+            }
+        }
+    }
+    routes
+}
+
+fn python_routes(content: &str) -> Vec<FoundRoute> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut routes = Vec::new();
+    for (idx, line) in lines.iter().enumerate() {
+        if let Some(caps) = FLASK_ROUTE.captures(line) {
+            let handler = handler_after(&lines, idx, &PYTHON_DEF);
+            let methods: Vec<String> = FLASK_METHODS
+                .captures(&caps[2])
+                .map(|m| {
+                    m[1].split(',')
+                        .map(|method| method.trim().trim_matches(['"', '\'']).to_ascii_uppercase())
+                        .filter(|method| !method.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default();
+            let methods = if methods.is_empty() { vec!["GET".to_string()] } else { methods };
+            for method in methods {
+                routes.push((method, caps[1].to_string(), handler.clone()));
+            }
+        } else if let Some(caps) = PYTHON_METHOD_DECORATOR.captures(line) {
+            let handler = handler_after(&lines, idx, &PYTHON_DEF);
+            routes.push((caps[1].to_ascii_uppercase(), caps[2].to_string(), handler));
+        }
+    }
+    routes
+}
+
+fn nest_routes(content: &str) -> Vec<FoundRoute> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut routes = Vec::new();
+    for (idx, line) in lines.iter().enumerate() {
+        if let Some(caps) = NEST_ROUTE.captures(line) {
+            let path = caps.get(2).map_or("", |m| m.as_str());
+            let path = if path.starts_with('/') { path.to_string() } else { format!("/{path}") };
+            let handler = handler_after(&lines, idx, &TS_METHOD);
+            routes.push((caps[1].to_ascii_uppercase(), path, handler));
+        }
+    }
+    routes
+}
+
+/// Name captured by `pattern` on the first line after `idx` that is not
+/// blank, a comment, or another attribute/decorator.
+fn handler_after(lines: &[&str], idx: usize, pattern: &Regex) -> String {
+    lines
+        .iter()
+        .skip(idx + 1)
+        .take(HANDLER_LOOKAHEAD)
+        .map(|line| line.trim())
+        .find(|line| {
+            !line.is_empty()
+                && !line.starts_with('#')
+                && !line.starts_with('@')
+                && !line.starts_with("//")
+        })
+        .and_then(|line| pattern.captures(line).map(|caps| caps[1].to_string()))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::extract_openapi_routes;
+    use crate::domain::Chunk;
+    use std::collections::BTreeSet;
+
+    fn chunk(id: &str, path: &str, language: &str, content: &str) -> Chunk {
+        Chunk {
+            id: id.to_string(),
+            path: path.to_string(),
+            language: language.to_string(),
+            start_line: 1,
+            end_line: content.lines().count(),
+            content: content.to_string(),
+            priority: 0.5,
+            tags: BTreeSet::new(),
+            token_estimate: 10,
+            file_id: String::new(),
+            chunk_index: 0,
+            chunks_in_file: 1,
+            byte_start: None,
+            byte_end: None,
+            content_sha256: String::new(),
+            file_sha256: String::new(),
+        }
+    }
+
+    fn summary(chunks: &[Chunk]) -> Vec<(String, String, String, String)> {
+        extract_openapi_routes(chunks)
+            .into_iter()
+            .map(|r| (r.method, r.path, r.handler_name, r.chunk_id))
+            .collect()
+    }
+
+    fn route(
+        method: &str,
+        path: &str,
+        handler: &str,
+        chunk_id: &str,
+    ) -> (String, String, String, String) {
+        (method.to_string(), path.to_string(), handler.to_string(), chunk_id.to_string())
+    }
+
+    #[test]
+    fn extracts_actix_and_axum_routes() {
+        let actix = "#[get(\"/users/{id}\")]\nasync fn get_user(path: web::Path<u32>) -> impl Responder {\n    HttpResponse::Ok()\n}\n\n#[actix_web::post(\"/users\")]\n#[tracing::instrument]\npub async fn create_user() -> impl Responder {\n    HttpResponse::Created()\n}\n";
+        let axum =
+            "let app = Router::new()\n    .route(\"/health\", get(handlers::health).post(ping));\n";
+        let chunks =
+            [chunk("c1", "src/api.rs", "rust", actix), chunk("c2", "src/main.rs", "rust", axum)];
+
+        assert_eq!(
+            summary(&chunks),
+            [
+                route("GET", "/health", "health", "c2"),
+                route("POST", "/health", "ping", "c2"),
+                route("POST", "/users", "create_user", "c1"),
+                route("GET", "/users/{id}", "get_user", "c1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_flask_routes_with_methods() {
+        let flask = "@app.route(\"/items\", methods=[\"GET\", \"POST\"])\n@login_required\ndef items():\n    return []\n\n@bp.route('/health')\ndef health():\n    return 'ok'\n\n@api.delete(\"/items/<int:item_id>\")\nasync def delete_item(item_id):\n    pass\n";
+        let chunks = [chunk("py", "app.py", "python", flask)];
+
+        assert_eq!(
+            summary(&chunks),
+            [
+                route("GET", "/health", "health", "py"),
+                route("GET", "/items", "items", "py"),
+                route("POST", "/items", "items", "py"),
+                route("DELETE", "/items/<int:item_id>", "delete_item", "py"),
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_nestjs_routes_and_skips_overlap_duplicates() {
+        let nest = "@Controller('cats')\nexport class CatsController {\n  @Get()\n  findAll() {\n    return [];\n  }\n\n  @Post(':id')\n  @HttpCode(204)\n  async update(@Param('id') id: string) {}\n}\n";
+        let chunks = [
+            chunk("ts1", "src/cats.controller.ts", "typescript", nest),
+            chunk("ts2", "src/cats.controller.ts", "typescript", nest),
+            chunk("md", "README.md", "markdown", "#[get(\"/ignored\")]\nfn nope() {}\n"),
+        ];
+
+        assert_eq!(
+            summary(&chunks),
+            [route("GET", "/", "findAll", "ts1"), route("POST", "/:id", "update", "ts1")]
+        );
+    }
+}
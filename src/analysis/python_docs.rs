@@ -0,0 +1,276 @@
+//! Python docstring extraction
+//!
+//! Collects the docstring of each `def`/`class` together with its documented
+//! parameters and return value. Google-style (`Args:` / `Returns:`) and
+//! NumPy-style (`Parameters` / `Returns` underlined with dashes) sections are
+//! recognised; other docstrings keep their text with no parameters.
+
+use crate::chunk::code_chunker::{extract_symbol_tags_from_line, python_docstring_below};
+
+/// Section headings that list parameters.
+const PARAM_SECTIONS: &[&str] = &["Args", "Arguments", "Parameters", "Params"];
+/// Section headings that describe the return value.
+const RETURN_SECTIONS: &[&str] = &["Returns", "Return", "Yields"];
+
+/// Documentation of one Python function or class.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocstringEntry {
+    /// Name of the function or class.
+    pub symbol: String,
+    /// 1-based line the docstring opens on.
+    pub start_line: usize,
+    /// 1-based line the docstring closes on.
+    pub end_line: usize,
+    /// Docstring text with its quotes and indentation removed.
+    pub docstring: String,
+    /// `(name, description)` of each documented parameter, in order. Types
+    /// are not part of the description.
+    pub params: Vec<(String, String)>,
+    /// Return value description, prefixed with its type when one is given.
+    pub returns: Option<String>,
+}
+
+/// Extracts the docstring of every top-level and nested `def`/`class` in
+/// Python source, in source order.
+pub fn extract_python_docstrings(content: &str) -> Vec<DocstringEntry> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut entries = Vec::new();
+    for (idx, line) in lines.iter().enumerate() {
+        let Some((first, last, docstring)) = python_docstring_below(&lines, idx) else {
+            continue;
+        };
+        let Some(symbol) = extract_symbol_tags_from_line("python", line.trim())
+            .into_iter()
+            .find_map(|tag| tag.split_once(':').map(|(_, name)| name.to_string()))
+        else {
+            continue;
+        };
+
+        let doc_lines: Vec<&str> = docstring.lines().collect();
+        let mut params = Vec::new();
+        let mut returns = None;
+        for section in sections(&doc_lines) {
+            if PARAM_SECTIONS.contains(&section.title) {
+                params.extend(parse_params(&section));
+            } else if RETURN_SECTIONS.contains(&section.title) && returns.is_none() {
+                returns = parse_returns(&section);
+            }
+        }
+        entries.push(DocstringEntry {
+            symbol,
+            start_line: first + 1,
+            end_line: last + 1,
+            docstring: docstring.trim_end().to_string(),
+            params,
+            returns,
+        });
+    }
+    entries
+}
+
+/// A titled docstring section and the lines below its heading.
+struct Section<'a> {
+    title: &'a str,
+    /// NumPy sections list items at the heading's indentation; Google
+    /// sections indent them.
+    numpy: bool,
+    indent: usize,
+    body: Vec<&'a str>,
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+fn is_underline(line: Option<&&str>) -> bool {
+    line.map(|l| l.trim()).is_some_and(|l| l.len() >= 3 && l.chars().all(|c| c == '-'))
+}
+
+/// Splits docstring lines into Google (`Title:`) and NumPy (`Title` over a
+/// dashed line) sections.
+fn sections<'a>(lines: &[&'a str]) -> Vec<Section<'a>> {
+    let mut sections: Vec<Section<'a>> = Vec::new();
+    let mut idx = 0;
+    while idx < lines.len() {
+        let line = lines[idx];
+        let trimmed = line.trim();
+        let indent = indent_of(line);
+        if !trimmed.is_empty() && is_underline(lines.get(idx + 1)) {
+            sections.push(Section { title: trimmed, numpy: true, indent, body: Vec::new() });
+            idx += 2;
+            continue;
+        }
+        let google_title = trimmed.strip_suffix(':').filter(|title| {
+            !title.is_empty() && title.chars().all(|c| c.is_alphanumeric() || c == ' ')
+        });
+        // Indented lines belong to the open section, even when they end in ':'.
+        let inside = sections.last().is_some_and(|s| trimmed.is_empty() || indent > s.indent);
+        if let Some(title) = google_title.filter(|_| !inside) {
+            sections.push(Section { title, numpy: false, indent, body: Vec::new() });
+        } else if let Some(section) = sections.last_mut() {
+            if section.numpy || inside {
+                section.body.push(line);
+            }
+        }
+        idx += 1;
+    }
+    sections
+}
+
+/// Lines of a section grouped into items: the item's first line and its
+/// more deeply indented continuation lines.
+fn items<'a>(section: &Section<'a>) -> Vec<(&'a str, Vec<&'a str>)> {
+    let item_indent = if section.numpy {
+        section.indent
+    } else {
+        match section.body.iter().find(|line| !line.trim().is_empty()) {
+            Some(line) => indent_of(line),
+            None => return Vec::new(),
+        }
+    };
+    let mut items: Vec<(&str, Vec<&str>)> = Vec::new();
+    for line in &section.body {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if indent_of(line) <= item_indent {
+            items.push((trimmed, Vec::new()));
+        } else if let Some((_, rest)) = items.last_mut() {
+            rest.push(trimmed);
+        }
+    }
+    items
+}
+
+fn parse_params(section: &Section<'_>) -> Vec<(String, String)> {
+    items(section)
+        .into_iter()
+        .filter_map(|(head, rest)| {
+            let (name, inline) = if section.numpy {
+                // `name : type`; the description is on the following lines.
+                (head.split(" : ").next().unwrap_or(head).trim_end_matches(':').trim(), "")
+            } else {
+                // `name (type): description` or `name: description`.
+                let (signature, description) = head.split_once(':')?;
+                let name = signature.split_once('(').map_or(signature, |(name, _)| name);
+                (name.trim(), description.trim())
+            };
+            if name.is_empty() || name.contains(' ') {
+                return None;
+            }
+            let description =
+                std::iter::once(inline).chain(rest).filter(|s| !s.is_empty()).collect::<Vec<_>>();
+            Some((name.to_string(), description.join(" ")))
+        })
+        .collect()
+}
+
+fn parse_returns(section: &Section<'_>) -> Option<String> {
+    let items = items(section);
+    let (head, rest) = items.first()?;
+    let text = if section.numpy && !rest.is_empty() {
+        format!("{head}: {}", rest.join(" "))
+    } else {
+        std::iter::once(*head).chain(rest.iter().copied()).collect::<Vec<_>>().join(" ")
+    };
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::extract_python_docstrings;
+
+    const GOOGLE: &str = r#"
+def transfer(source, target, amount=0):
+    """Move funds between accounts.
+
+    Args:
+        source (Account): Account to debit.
+        target (Account): Account to credit.
+        amount: Amount in cents. Must not
+            exceed the source balance.
+
+    Returns:
+        bool: True when the transfer was booked.
+
+    Raises:
+        ValueError: If the amount is negative.
+    """
+    return True
+
+
+class Ledger:
+    """Append-only record of transfers."""
+"#;
+
+    const NUMPY: &str = r#"
+def scale(values, factor, *, clip=None):
+    """
+    Scale values by a factor.
+
+    Parameters
+    ----------
+    values : list of float
+        Values to scale.
+    factor : float
+        Multiplier applied to
+        every value.
+    clip : float, optional
+
+    Returns
+    -------
+    list of float
+        The scaled values.
+    """
+    return [v * factor for v in values]
+"#;
+
+    fn pairs(params: &[(String, String)]) -> Vec<(&str, &str)> {
+        params.iter().map(|(name, desc)| (name.as_str(), desc.as_str())).collect()
+    }
+
+    #[test]
+    fn extracts_google_style_params_and_returns() {
+        let entries = extract_python_docstrings(GOOGLE);
+        assert_eq!(entries.len(), 2);
+
+        let transfer = &entries[0];
+        assert_eq!(transfer.symbol, "transfer");
+        assert_eq!((transfer.start_line, transfer.end_line), (3, 16));
+        assert!(transfer.docstring.starts_with("Move funds between accounts.\n\nArgs:"));
+        assert_eq!(
+            pairs(&transfer.params),
+            [
+                ("source", "Account to debit."),
+                ("target", "Account to credit."),
+                ("amount", "Amount in cents. Must not exceed the source balance."),
+            ]
+        );
+        assert_eq!(transfer.returns.as_deref(), Some("bool: True when the transfer was booked."));
+
+        let ledger = &entries[1];
+        assert_eq!(ledger.symbol, "Ledger");
+        assert_eq!(ledger.docstring, "Append-only record of transfers.");
+        assert!(ledger.params.is_empty());
+        assert_eq!(ledger.returns, None);
+    }
+
+    #[test]
+    fn extracts_numpy_style_params_and_returns() {
+        let entries = extract_python_docstrings(NUMPY);
+        assert_eq!(entries.len(), 1);
+
+        let scale = &entries[0];
+        assert_eq!(scale.symbol, "scale");
+        assert_eq!(
+            pairs(&scale.params),
+            [
+                ("values", "Values to scale."),
+                ("factor", "Multiplier applied to every value."),
+                ("clip", ""),
+            ]
+        );
+        assert_eq!(scale.returns.as_deref(), Some("list of float: The scaled values."));
+    }
+}
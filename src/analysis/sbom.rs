@@ -0,0 +1,499 @@
+//! Software Bill of Materials (SPDX 2.3) generation
+//!
+//! Reads root-level dependency manifests and lock files and lists the
+//! packages they pin. Lock files win over manifests when both exist, since
+//! only they record exact versions.
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use crate::utils::read_file_safe;
+
+const NOASSERTION: &str = "NOASSERTION";
+
+/// Package registry a dependency comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Ecosystem {
+    /// crates.io (Cargo)
+    Cargo,
+    /// npm registry
+    Npm,
+    /// Python Package Index
+    PyPI,
+}
+
+impl Ecosystem {
+    fn purl_type(self) -> &'static str {
+        match self {
+            Self::Cargo => "cargo",
+            Self::Npm => "npm",
+            Self::PyPI => "pypi",
+        }
+    }
+}
+
+/// One dependency discovered in a manifest or lock file.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SbomPackage {
+    /// Registry the package is published to.
+    pub ecosystem: Ecosystem,
+    /// Package name as written in the manifest.
+    pub name: String,
+    /// Exact version from a lock file, or the requirement from a manifest.
+    pub version: Option<String>,
+    /// SPDX license expression, when the lock file records one.
+    pub license: Option<String>,
+    /// Whether the package comes from the public registry (vs. path/git).
+    pub from_registry: bool,
+}
+
+impl SbomPackage {
+    fn new(ecosystem: Ecosystem, name: &str, version: Option<&str>) -> Self {
+        Self {
+            ecosystem,
+            name: name.to_string(),
+            version: version.map(str::trim).filter(|v| !v.is_empty()).map(str::to_string),
+            license: None,
+            from_registry: true,
+        }
+    }
+
+    /// Registry download URL, if the package has a pinned registry version.
+    pub fn download_location(&self) -> Option<String> {
+        let version = self.version.as_deref().filter(|v| is_exact_version(v))?;
+        if !self.from_registry {
+            return None;
+        }
+        Some(match self.ecosystem {
+            Ecosystem::Cargo => {
+                format!("https://crates.io/api/v1/crates/{}/{version}/download", self.name)
+            }
+            Ecosystem::Npm => {
+                let basename = self.name.rsplit('/').next().unwrap_or(&self.name);
+                format!("https://registry.npmjs.org/{}/-/{basename}-{version}.tgz", self.name)
+            }
+            Ecosystem::PyPI => format!("https://pypi.org/project/{}/{version}/", self.name),
+        })
+    }
+
+    /// Package URL (purl) identifying this package.
+    pub fn purl(&self) -> String {
+        let name = match self.ecosystem {
+            Ecosystem::Npm => self.name.replacen('@', "%40", 1),
+            _ => self.name.clone(),
+        };
+        match self.version.as_deref().filter(|v| is_exact_version(v)) {
+            Some(version) => format!("pkg:{}/{name}@{version}", self.ecosystem.purl_type()),
+            None => format!("pkg:{}/{name}", self.ecosystem.purl_type()),
+        }
+    }
+
+    fn spdx_id(&self) -> String {
+        let raw = format!(
+            "{}-{}-{}",
+            self.ecosystem.purl_type(),
+            self.name,
+            self.version.as_deref().unwrap_or("any")
+        );
+        let sanitized: String = raw
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '.' { c } else { '-' })
+            .collect();
+        format!("SPDXRef-Package-{sanitized}")
+    }
+}
+
+/// Dependencies of one repository.
+#[derive(Debug, Clone, Default)]
+pub struct SbomDocument {
+    /// Document name (usually the repository name).
+    pub name: String,
+    /// Sorted, de-duplicated package list.
+    pub packages: Vec<SbomPackage>,
+    /// Manifest and lock files that contributed packages.
+    pub sources: Vec<String>,
+}
+
+impl SbomDocument {
+    /// Render the document as SPDX 2.3 JSON.
+    ///
+    /// Without `created`, a fixed epoch timestamp keeps output reproducible.
+    pub fn to_spdx_json(&self, created: Option<&str>) -> Value {
+        let mut hasher = Sha256::new();
+        for package in &self.packages {
+            hasher.update(package.purl().as_bytes());
+            hasher.update(b"\n");
+        }
+        let digest = format!("{:x}", hasher.finalize());
+
+        let packages: Vec<Value> = self
+            .packages
+            .iter()
+            .map(|p| {
+                json!({
+                    "SPDXID": p.spdx_id(),
+                    "name": p.name,
+                    "versionInfo": p.version.as_deref().unwrap_or(NOASSERTION),
+                    "downloadLocation": p.download_location().unwrap_or_else(|| NOASSERTION.to_string()),
+                    "filesAnalyzed": false,
+                    "licenseConcluded": NOASSERTION,
+                    "licenseDeclared": p.license.as_deref().unwrap_or(NOASSERTION),
+                    "copyrightText": NOASSERTION,
+                    "externalRefs": [{
+                        "referenceCategory": "PACKAGE-MANAGER",
+                        "referenceType": "purl",
+                        "referenceLocator": p.purl(),
+                    }],
+                })
+            })
+            .collect();
+        let relationships: Vec<Value> = self
+            .packages
+            .iter()
+            .map(|p| {
+                json!({
+                    "spdxElementId": "SPDXRef-DOCUMENT",
+                    "relationshipType": "DESCRIBES",
+                    "relatedSpdxElement": p.spdx_id(),
+                })
+            })
+            .collect();
+
+        json!({
+            "spdxVersion": "SPDX-2.3",
+            "dataLicense": "CC0-1.0",
+            "SPDXID": "SPDXRef-DOCUMENT",
+            "name": self.name,
+            "documentNamespace": format!("https://spdx.org/spdxdocs/{}-{}", self.name, &digest[..16]),
+            "creationInfo": {
+                "created": created.unwrap_or("1970-01-01T00:00:00Z"),
+                "creators": [format!("Tool: repo-context-{}", env!("CARGO_PKG_VERSION"))],
+            },
+            "packages": packages,
+            "relationships": relationships,
+        })
+    }
+}
+
+/// Parses one manifest's contents into its packages.
+type SbomParser = fn(&str) -> Result<Vec<SbomPackage>>;
+
+/// Collect dependencies from the manifests at the root of `root_path`.
+///
+/// Supports `Cargo.lock` (else `Cargo.toml`), `package-lock.json` (else
+/// `package.json`), `requirements.txt`, and `pyproject.toml`. Unparseable
+/// files are skipped with a warning rather than failing the export.
+pub fn generate_sbom(root_path: &Path) -> Result<SbomDocument> {
+    let name = root_path.file_name().and_then(|n| n.to_str()).unwrap_or("repository").to_string();
+    let mut packages = BTreeSet::new();
+    let mut sources = Vec::new();
+
+    let parsers: [(&str, &str, SbomParser); 6] = [
+        ("Cargo.lock", "", parse_cargo_lock),
+        ("Cargo.toml", "Cargo.lock", parse_cargo_toml),
+        ("package-lock.json", "", parse_package_lock),
+        ("package.json", "package-lock.json", parse_package_json),
+        ("requirements.txt", "", parse_requirements_txt),
+        ("pyproject.toml", "", parse_pyproject_toml),
+    ];
+    for (file_name, superseded_by, parse) in parsers {
+        let path = root_path.join(file_name);
+        if !path.is_file() || (!superseded_by.is_empty() && root_path.join(superseded_by).is_file())
+        {
+            continue;
+        }
+        let (content, _) = read_file_safe(&path, None, None)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        match parse(&content) {
+            Ok(found) => {
+                packages.extend(found);
+                sources.push(file_name.to_string());
+            }
+            Err(e) => tracing::warn!("Skipping {file_name} in SBOM: {e:#}"),
+        }
+    }
+
+    Ok(SbomDocument { name, packages: packages.into_iter().collect(), sources })
+}
+
+/// Write `doc` as SPDX JSON to `path`.
+pub fn write_sbom(path: &Path, doc: &SbomDocument, created: Option<&str>) -> Result<()> {
+    let json = serde_json::to_string_pretty(&doc.to_spdx_json(created))?;
+    fs::write(path, json + "\n").with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn parse_cargo_lock(content: &str) -> Result<Vec<SbomPackage>> {
+    let lock: toml::Value = toml::from_str(content)?;
+    let entries = lock.get("package").and_then(toml::Value::as_array).cloned().unwrap_or_default();
+    Ok(entries
+        .iter()
+        .filter_map(|entry| {
+            let name = entry.get("name")?.as_str()?;
+            let mut package =
+                SbomPackage::new(Ecosystem::Cargo, name, entry.get("version")?.as_str());
+            // Workspace members have no source; git/path sources are not on crates.io.
+            let source = entry.get("source").and_then(toml::Value::as_str);
+            if !source.is_some_and(|s| s.starts_with("registry+")) {
+                package.from_registry = false;
+            }
+            Some(package)
+        })
+        .collect())
+}
+
+fn parse_cargo_toml(content: &str) -> Result<Vec<SbomPackage>> {
+    let manifest: toml::Value = toml::from_str(content)?;
+    let mut packages = Vec::new();
+    for table in ["dependencies", "dev-dependencies", "build-dependencies"] {
+        let Some(deps) = manifest.get(table).and_then(toml::Value::as_table) else {
+            continue;
+        };
+        for (name, spec) in deps {
+            let (version, registry) = match spec {
+                toml::Value::String(v) => (Some(v.as_str()), true),
+                toml::Value::Table(t) => (
+                    t.get("version").and_then(toml::Value::as_str),
+                    !t.contains_key("path") && !t.contains_key("git"),
+                ),
+                _ => (None, true),
+            };
+            let mut package = SbomPackage::new(Ecosystem::Cargo, name, version);
+            package.from_registry = registry;
+            packages.push(package);
+        }
+    }
+    Ok(packages)
+}
+
+fn parse_package_lock(content: &str) -> Result<Vec<SbomPackage>> {
+    let lock: Value = serde_json::from_str(content)?;
+    let mut packages = Vec::new();
+
+    // lockfileVersion 2/3: flat "packages" map keyed by node_modules path.
+    if let Some(entries) = lock.get("packages").and_then(Value::as_object) {
+        for (key, entry) in entries {
+            let Some(idx) = key.rfind("node_modules/") else {
+                continue; // "" is the root project itself
+            };
+            let name = &key[idx + "node_modules/".len()..];
+            let mut package = SbomPackage::new(
+                Ecosystem::Npm,
+                name,
+                entry.get("version").and_then(Value::as_str),
+            );
+            package.license = entry.get("license").and_then(Value::as_str).map(str::to_string);
+            package.from_registry = entry.get("link").and_then(Value::as_bool) != Some(true);
+            packages.push(package);
+        }
+        return Ok(packages);
+    }
+
+    // lockfileVersion 1: nested "dependencies" tree.
+    fn walk(deps: &serde_json::Map<String, Value>, out: &mut Vec<SbomPackage>) {
+        for (name, entry) in deps {
+            out.push(SbomPackage::new(
+                Ecosystem::Npm,
+                name,
+                entry.get("version").and_then(Value::as_str),
+            ));
+            if let Some(nested) = entry.get("dependencies").and_then(Value::as_object) {
+                walk(nested, out);
+            }
+        }
+    }
+    if let Some(deps) = lock.get("dependencies").and_then(Value::as_object) {
+        walk(deps, &mut packages);
+    }
+    Ok(packages)
+}
+
+fn parse_package_json(content: &str) -> Result<Vec<SbomPackage>> {
+    let manifest: Value = serde_json::from_str(content)?;
+    let mut packages = Vec::new();
+    for field in ["dependencies", "devDependencies", "optionalDependencies"] {
+        let Some(deps) = manifest.get(field).and_then(Value::as_object) else {
+            continue;
+        };
+        for (name, version) in deps {
+            packages.push(SbomPackage::new(Ecosystem::Npm, name, version.as_str()));
+        }
+    }
+    Ok(packages)
+}
+
+fn parse_requirements_txt(content: &str) -> Result<Vec<SbomPackage>> {
+    Ok(content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('-'))
+        .filter_map(parse_pep508)
+        .collect())
+}
+
+fn parse_pyproject_toml(content: &str) -> Result<Vec<SbomPackage>> {
+    let manifest: toml::Value = toml::from_str(content)?;
+    let mut packages = Vec::new();
+
+    if let Some(deps) =
+        manifest.get("project").and_then(|p| p.get("dependencies")).and_then(toml::Value::as_array)
+    {
+        packages.extend(deps.iter().filter_map(toml::Value::as_str).filter_map(parse_pep508));
+    }
+
+    if let Some(deps) = manifest
+        .get("tool")
+        .and_then(|t| t.get("poetry"))
+        .and_then(|p| p.get("dependencies"))
+        .and_then(toml::Value::as_table)
+    {
+        for (name, spec) in deps.iter().filter(|(name, _)| name.as_str() != "python") {
+            let version = match spec {
+                toml::Value::String(v) => Some(v.as_str()),
+                toml::Value::Table(t) => t.get("version").and_then(toml::Value::as_str),
+                _ => None,
+            };
+            packages.push(SbomPackage::new(Ecosystem::PyPI, name, version));
+        }
+    }
+    Ok(packages)
+}
+
+/// Parse a PEP 508 requirement such as `requests[socks]==2.31.0; python_version>"3"`.
+fn parse_pep508(requirement: &str) -> Option<SbomPackage> {
+    let requirement = requirement.split(';').next()?.trim();
+    let name_end = requirement
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .unwrap_or(requirement.len());
+    let name = &requirement[..name_end];
+    if name.is_empty() {
+        return None;
+    }
+    let rest = requirement[name_end..].trim_start();
+    let rest = match rest.strip_prefix('[') {
+        Some(extras) => extras.split_once(']').map(|(_, r)| r.trim_start()).unwrap_or(""),
+        None => rest,
+    };
+    let version = match rest.strip_prefix("==") {
+        Some(pinned) => Some(pinned.trim()),
+        None if rest.is_empty() => None,
+        None => Some(rest),
+    };
+    Some(SbomPackage::new(Ecosystem::PyPI, name, version))
+}
+
+/// Whether `version` is a concrete version rather than a range like `^1.2`.
+fn is_exact_version(version: &str) -> bool {
+    version.chars().next().is_some_and(|c| c.is_ascii_digit())
+        && !version.contains(['<', '>', '=', '*', ',', ' ', '|'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const CARGO_LOCK: &str = r#"
+version = 3
+
+[[package]]
+name = "demo"
+version = "0.1.0"
+dependencies = ["serde"]
+
+[[package]]
+name = "serde"
+version = "1.0.200"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "local-helper"
+version = "0.2.0"
+source = "git+https://example.com/helper.git#abc123"
+"#;
+
+    const PACKAGE_LOCK: &str = r#"{
+  "name": "web",
+  "lockfileVersion": 3,
+  "packages": {
+    "": { "name": "web", "version": "1.0.0" },
+    "node_modules/left-pad": { "version": "1.3.0", "license": "WTFPL" },
+    "node_modules/@scope/util": { "version": "2.0.1", "license": "MIT" }
+  }
+}"#;
+
+    #[test]
+    fn counts_packages_from_lock_files() {
+        let tmp = TempDir::new().expect("tmp");
+        fs::write(tmp.path().join("Cargo.lock"), CARGO_LOCK).expect("write lock");
+        fs::write(tmp.path().join("Cargo.toml"), "[dependencies]\nignored = \"1\"\n")
+            .expect("write manifest");
+        fs::write(tmp.path().join("package-lock.json"), PACKAGE_LOCK).expect("write npm lock");
+        fs::write(
+            tmp.path().join("requirements.txt"),
+            "# pinned\nrequests[socks]==2.31.0\nflask>=2.0 ; python_version > \"3.8\"\n-r dev.txt\n",
+        )
+        .expect("write requirements");
+
+        let doc = generate_sbom(tmp.path()).expect("sbom");
+        let count = |eco| doc.packages.iter().filter(|p| p.ecosystem == eco).count();
+
+        assert_eq!(count(Ecosystem::Cargo), 3, "Cargo.toml is superseded by Cargo.lock");
+        assert_eq!(count(Ecosystem::Npm), 2, "root project entry is skipped");
+        assert_eq!(count(Ecosystem::PyPI), 2);
+        assert_eq!(doc.sources, vec!["Cargo.lock", "package-lock.json", "requirements.txt"]);
+    }
+
+    #[test]
+    fn records_licenses_and_download_locations() {
+        let npm = parse_package_lock(PACKAGE_LOCK).expect("parse");
+        let scoped = npm.iter().find(|p| p.name == "@scope/util").expect("scoped package");
+        assert_eq!(scoped.license.as_deref(), Some("MIT"));
+        assert_eq!(
+            scoped.download_location().as_deref(),
+            Some("https://registry.npmjs.org/@scope/util/-/util-2.0.1.tgz")
+        );
+        assert_eq!(scoped.purl(), "pkg:npm/%40scope/util@2.0.1");
+
+        let cargo = parse_cargo_lock(CARGO_LOCK).expect("parse");
+        let serde = cargo.iter().find(|p| p.name == "serde").expect("serde");
+        assert_eq!(
+            serde.download_location().as_deref(),
+            Some("https://crates.io/api/v1/crates/serde/1.0.200/download")
+        );
+        let git_dep = cargo.iter().find(|p| p.name == "local-helper").expect("git dep");
+        assert_eq!(git_dep.download_location(), None);
+    }
+
+    #[test]
+    fn parses_pyproject_dependencies() {
+        let packages = parse_pyproject_toml(
+            "[project]\ndependencies = [\"httpx==0.27.0\", \"rich\"]\n\n[tool.poetry.dependencies]\npython = \"^3.10\"\nclick = \"^8.1\"\n",
+        )
+        .expect("parse");
+        let names: Vec<&str> = packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["httpx", "rich", "click"]);
+        assert_eq!(packages[0].version.as_deref(), Some("0.27.0"));
+        assert_eq!(packages[2].download_location(), None, "ranges have no exact download");
+    }
+
+    #[test]
+    fn spdx_document_has_required_fields() {
+        let doc = SbomDocument {
+            name: "demo".to_string(),
+            packages: parse_cargo_lock(CARGO_LOCK).expect("parse"),
+            sources: vec!["Cargo.lock".to_string()],
+        };
+        let json = doc.to_spdx_json(None);
+        assert_eq!(json["spdxVersion"], "SPDX-2.3");
+        assert_eq!(json["dataLicense"], "CC0-1.0");
+        assert_eq!(json["creationInfo"]["created"], "1970-01-01T00:00:00Z");
+        assert_eq!(json["packages"].as_array().map(Vec::len), Some(3));
+        assert_eq!(json["relationships"].as_array().map(Vec::len), Some(3));
+        assert_eq!(json["packages"][1]["SPDXID"], "SPDXRef-Package-cargo-serde-1.0.200");
+        assert_eq!(doc.to_spdx_json(None), json, "output is deterministic");
+    }
+}
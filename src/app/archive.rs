@@ -0,0 +1,106 @@
+//! `export --archive` bundle.
+//!
+//! Packs the written artifacts into one `.tar.gz` alongside a
+//! `manifest.json` of SHA-256 checksums. Entries are sorted and carry fixed
+//! ownership and permissions, so without a timestamp the same artifacts
+//! always produce a byte-identical archive under the same name.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the checksum manifest stored inside the archive.
+pub const MANIFEST_NAME: &str = "manifest.json";
+
+/// Bundle `files` into `<output_dir>/<prefix>_context_<suffix>.tar.gz`.
+///
+/// The suffix is `timestamp` formatted as `YYYYMMDD_HHMMSS`, or the first 16
+/// hex digits of the manifest's SHA-256 when `timestamp` is `None`.
+pub fn write_archive(
+    output_dir: &Path,
+    prefix: &str,
+    files: &[PathBuf],
+    timestamp: Option<DateTime<Utc>>,
+) -> Result<PathBuf> {
+    let mut entries = files
+        .iter()
+        .map(|path| {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .with_context(|| format!("Archive input has no file name: {}", path.display()))?;
+            let bytes = fs::read(path)
+                .with_context(|| format!("Failed to read {} for archive", path.display()))?;
+            Ok((name, bytes))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let manifest = json!({
+        "files": entries
+            .iter()
+            .map(|(name, bytes)| json!({
+                "name": name,
+                "bytes": bytes.len(),
+                "sha256": format!("{:x}", Sha256::digest(bytes)),
+            }))
+            .collect::<Vec<_>>(),
+    });
+    let manifest_bytes = serde_json::to_vec_pretty(&manifest)?;
+
+    let suffix = match timestamp {
+        Some(ts) => ts.format("%Y%m%d_%H%M%S").to_string(),
+        None => format!("{:x}", Sha256::digest(&manifest_bytes))[..16].to_string(),
+    };
+    let mtime = timestamp.map(|ts| ts.timestamp().max(0) as u64).unwrap_or(0);
+    let archive_path = output_dir.join(format!("{prefix}_context_{suffix}.tar.gz"));
+
+    let file = fs::File::create(&archive_path)
+        .with_context(|| format!("Failed to create {}", archive_path.display()))?;
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    let members = std::iter::once((MANIFEST_NAME, manifest_bytes.as_slice()))
+        .chain(entries.iter().map(|(name, bytes)| (name.as_str(), bytes.as_slice())));
+    for (name, bytes) in members {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(bytes.len() as u64);
+        header.set_mode(0o644);
+        header.set_uid(0);
+        header.set_gid(0);
+        header.set_mtime(mtime);
+        builder.append_data(&mut header, name, bytes)?;
+    }
+    builder.into_inner()?.finish()?;
+
+    Ok(archive_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn untimestamped_archive_is_reproducible() {
+        let tmp = TempDir::new().expect("tmp");
+        let files = ["b_report.json", "a_chunks.jsonl"].map(|name| {
+            let path = tmp.path().join(name);
+            fs::write(&path, name).expect("write artifact");
+            path
+        });
+
+        let first = write_archive(tmp.path(), "repo", &files, None).expect("archive");
+        let first_bytes = fs::read(&first).expect("read archive");
+        fs::remove_file(&first).expect("remove archive");
+        let second = write_archive(tmp.path(), "repo", &files, None).expect("archive");
+
+        assert_eq!(first, second);
+        assert_eq!(first_bytes, fs::read(&second).expect("read archive"));
+        let name = second.file_name().unwrap().to_string_lossy().to_string();
+        assert!(name.starts_with("repo_context_") && name.ends_with(".tar.gz"));
+    }
+}
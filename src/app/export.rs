@@ -1,28 +1,51 @@
 #![allow(missing_docs)]
 
 use anyhow::{Context, Result};
+use chrono::Utc;
+use globset::{Glob, GlobMatcher};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde_json::{json, Value};
 use sha2::{Digest, Sha256};
+use std::borrow::Cow;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
-use std::time::Instant;
-
-use crate::chunk::{chunk_content, coalesce_small_chunks_with_max, enrich_chunks};
+use std::time::{Duration, Instant};
+
+use super::archive::write_archive;
+use super::progress::Progress;
+use crate::analysis::sbom::{generate_sbom, write_sbom};
+use crate::chunk::code_chunker::validate_custom_definition_kinds;
+use crate::chunk::{
+    chunk_content, coalesce_small_chunks_with_max, dedup_similar_chunks, enrich_chunks,
+    ChunkOptions,
+};
 use crate::domain::{
-    Chunk, Config, FileDisposition, FileDispositionReason, FileInfo, OutputMode, RedactionMode,
-    ScanStats,
+    compute_chunk_percentiles, Chunk, ChunkSortKey, Config, FileDisposition, FileDispositionReason,
+    FileInfo, OutputMode, PackingStrategy, RedactionMode, ScanStats,
 };
-use crate::fetch::fetch_repository;
+use crate::fetch::github::resolve_ssh_key;
+use crate::fetch::{fetch_repository, ProxySettings};
 use crate::module::focus_picker::ScanMode;
 use crate::module::FocusResult;
-use crate::rank::rank_files_with_manifest;
-use crate::redact::Redactor;
-use crate::render::{render_jsonl, write_report, ContextPackCtx, ReportOptions};
+use crate::rank::{
+    debug_rank_file, pack_chunks_to_budget, rank_files_with_manifest, RankingSignal,
+};
+use crate::redact::{RedactionMatch, Redactor};
+use crate::render::openai::{DEFAULT_BATCH_MODEL, DEFAULT_BATCH_SYSTEM_PROMPT};
+use crate::render::{
+    merge_context_packs, render_editor_snippets, render_html, render_jsonl, render_langchain_docs,
+    render_llamaindex_docs, render_openai_batch, render_template, write_manifest, write_report,
+    ContextPackCtx, ReportOptions, TemplateContext,
+};
 use crate::scan::scanner::FileScanner;
 use crate::scan::tree::generate_tree;
-use crate::utils::{estimate_tokens, read_file_safe, redact_url_credentials};
+use crate::utils::{
+    normalize_path, read_file_mmap, read_file_safe, redact_url_credentials, write_compressed,
+    FileText, Tokenizer, MMAP_THRESHOLD_BYTES,
+};
 
 /// Options controlling export runtime behavior.
 #[derive(Debug, Clone)]
@@ -35,6 +58,32 @@ pub struct ExportExecutionOptions {
     pub scan_mode: Option<ScanMode>,
     /// For focused mode: pre-select this file or module entry (non-interactive).
     pub focus_path: Option<PathBuf>,
+    /// Optional Tera template replacing the built-in context pack layout.
+    pub template_path: Option<PathBuf>,
+    /// System prompt for OpenAI Batch output (defaults to a summarization prompt).
+    pub system_prompt: Option<String>,
+    /// Model name for OpenAI Batch output.
+    pub model: Option<String>,
+    /// Also write an SPDX 2.3 SBOM of the repository's dependencies.
+    pub sbom: bool,
+    /// Run the pipeline through budget enforcement but write no files.
+    pub dry_run: bool,
+    /// Bundle the written artifacts into a `.tar.gz` with a checksum manifest.
+    pub archive: bool,
+    /// Write the context pack and chunks JSONL gzip-compressed (`.gz`).
+    pub compress: bool,
+    /// Record each file's `priority_explanation` (also on with debug logging).
+    pub explain: bool,
+    /// File (relative to the repository root) whose ranking signals to report.
+    pub debug_ranking: Option<PathBuf>,
+    /// Rendered context packs of other repositories to merge with this one.
+    pub merge_with: Vec<PathBuf>,
+    /// Report what redaction would match in each selected file, then stop
+    /// without chunking or writing files.
+    pub dry_run_redaction: bool,
+    /// Reuse the scan cache (see [`FileScanner::try_load_scan_cache`]) when
+    /// it was written less than this long ago (None = always rescan).
+    pub scan_cache_ttl: Option<Duration>,
 }
 
 /// Result summary from an export execution.
@@ -43,6 +92,16 @@ pub struct ExportOutcome {
     pub root_path: PathBuf,
     pub stats: ScanStats,
     pub output_files: Vec<String>,
+    /// Final per-file dispositions (also written to the report).
+    pub dispositions: Vec<FileDisposition>,
+    /// Tokens across all chunks before the token budget was applied.
+    pub candidate_tokens: usize,
+    /// Redaction rule match counts per file (relative path → rule → matches).
+    pub redactions: BTreeMap<String, BTreeMap<String, usize>>,
+    /// Ranking breakdown for `--debug-ranking` (relative path, final priority, signals).
+    pub ranking_debug: Option<(String, f64, Vec<RankingSignal>)>,
+    /// Redaction matches per file for `--dry-run-redaction` (relative path → matches).
+    pub redaction_matches: BTreeMap<String, Vec<RedactionMatch>>,
 }
 
 pub fn execute(mut config: Config, options: ExportExecutionOptions) -> Result<ExportOutcome> {
@@ -52,6 +111,9 @@ pub fn execute(mut config: Config, options: ExportExecutionOptions) -> Result<Ex
         config.path.as_deref(),
         config.repo_url.as_deref(),
         config.ref_.as_deref(),
+        &ProxySettings::resolve(config.proxy_url.as_deref(), config.no_proxy.as_deref()),
+        &config.gitea_hosts,
+        resolve_ssh_key(config.ssh_key.as_ref()).as_ref(),
     )?;
     let root_path = repo_ctx.root_path.clone();
 
@@ -66,9 +128,30 @@ pub fn execute(mut config: Config, options: ExportExecutionOptions) -> Result<Ex
         );
     }
 
+    validate_custom_definition_kinds(&config.custom_tree_sitter_queries)?;
+
     let mut scanner = FileScanner::from_config(root_path.clone(), &config);
 
-    let scanned_files = scanner.scan()?;
+    let show_progress = Progress::enabled();
+    // A fresh clone of a remote repository never has a cache worth reading.
+    let scan_cache_ttl = options.scan_cache_ttl.filter(|_| !was_remote);
+    let cached_files = scan_cache_ttl.and_then(|ttl| scanner.try_load_scan_cache(ttl));
+    let scanned_files = match cached_files {
+        Some(files) => files,
+        None => {
+            let scan_progress = Progress::spinner("Scanning files", show_progress);
+            let scanned_files = scanner.scan();
+            scan_progress.finish();
+            let scanned_files = scanned_files?;
+            // Dry runs read a fresh cache but promise to write no files.
+            if scan_cache_ttl.is_some() && !options.dry_run && !options.dry_run_redaction {
+                if let Err(e) = scanner.write_scan_cache(&scanned_files) {
+                    tracing::warn!("Failed to write scan cache: {}", e);
+                }
+            }
+            scanned_files
+        }
+    };
     let mut stats = scanner.stats().clone();
     let mut dispositions = scanner.dispositions().to_vec();
 
@@ -83,8 +166,27 @@ pub fn execute(mut config: Config, options: ExportExecutionOptions) -> Result<Ex
         }
     };
 
-    let (ranked_files, manifest_info) =
-        rank_files_with_manifest(&root_path, scanned_files, config.ranking_weights.clone())?;
+    let explain = options.explain || tracing::enabled!(tracing::Level::DEBUG);
+    let (mut ranked_files, manifest_info) = rank_files_with_manifest(
+        &root_path,
+        scanned_files,
+        config.ranking_weights.clone(),
+        explain,
+    )?;
+    let ranking_debug = options.debug_ranking.as_deref().and_then(|path| {
+        let relative =
+            normalize_path(&path.strip_prefix(&root_path).unwrap_or(path).to_string_lossy());
+        let signals =
+            debug_rank_file(&root_path, &ranked_files, config.ranking_weights.clone(), &relative);
+        if signals.is_none() {
+            tracing::warn!("--debug-ranking: {relative} is not among the scanned files");
+        }
+        let priority = ranked_files.iter().find(|f| f.relative_path == relative)?.priority;
+        Some((relative, priority, signals?))
+    });
+    if config.dedup_files {
+        ranked_files = dedup_files_by_content(ranked_files, &mut stats, &mut dispositions);
+    }
     let module_run = if matches!(scan_mode, ScanMode::Focused) {
         if let Some(ref focus_path) = options.focus_path {
             // Non-interactive: use the provided focus path.
@@ -118,6 +220,24 @@ pub fn execute(mut config: Config, options: ExportExecutionOptions) -> Result<Ex
         &mut dispositions,
     );
 
+    let content_overrides = module_run.as_ref().map(|module| &module.content_overrides);
+    if options.dry_run_redaction {
+        // Report with the configured rules even under --no-redact, so the
+        // dry run can be used to decide whether redaction is needed.
+        let redactor = build_redactor(config.redaction_mode, &config.redaction);
+        let redaction_matches = redaction_dry_run(&selected_files, &redactor, content_overrides)?;
+        return Ok(ExportOutcome {
+            root_path,
+            stats,
+            output_files: Vec::new(),
+            dispositions,
+            candidate_tokens: 0,
+            redactions: BTreeMap::new(),
+            ranking_debug,
+            redaction_matches,
+        });
+    }
+
     let redactor = if config.redact_secrets {
         Some(build_redactor(config.redaction_mode, &config.redaction))
     } else {
@@ -134,21 +254,49 @@ pub fn execute(mut config: Config, options: ExportExecutionOptions) -> Result<Ex
 
     let mut all_chunks = Vec::new();
     let mut redaction_counts: BTreeMap<String, usize> = BTreeMap::new();
-    let content_overrides = module_run.as_ref().map(|module| &module.content_overrides);
+    let mut redactions: BTreeMap<String, BTreeMap<String, usize>> = BTreeMap::new();
+    let file_progress = Progress::files(selected_files.len(), "processed", show_progress);
     for file in &selected_files {
         let processed = process_file(file, redactor.as_ref(), &config, content_overrides)?;
+        file_progress.inc();
         if processed.redacted {
             stats.redacted_files += 1;
             stats.redacted_chunks += processed.chunks.len();
         }
-        for (rule, count) in processed.counts {
-            *redaction_counts.entry(rule).or_insert(0) += count;
+        if file.is_env_file {
+            stats.env_files_redacted += 1;
+        }
+        for (rule, count) in &processed.counts {
+            *redaction_counts.entry(rule.clone()).or_insert(0) += count;
+        }
+        if processed.redacted {
+            redactions.insert(file.relative_path.clone(), processed.counts);
         }
         all_chunks.extend(processed.chunks);
     }
+    file_progress.finish();
     stats.redaction_counts = redaction_counts;
 
-    let chunks = apply_chunk_token_budget(all_chunks, config.max_tokens, &mut stats);
+    if config.dedup_chunks {
+        let before = all_chunks.len();
+        all_chunks = dedup_similar_chunks(all_chunks, config.dedup_threshold);
+        stats.chunks_deduplicated = before - all_chunks.len();
+    }
+    if !config.require_tags.is_empty() || !config.exclude_tags.is_empty() {
+        all_chunks = filter_chunks_by_tags(all_chunks, &config.require_tags, &config.exclude_tags)?;
+    }
+
+    stats.chunk_distribution = compute_chunk_percentiles(&all_chunks);
+
+    let candidate_file_tokens = file_token_totals(&all_chunks);
+    let candidate_tokens = candidate_file_tokens.values().sum();
+    let mut chunks = apply_chunk_token_budget(
+        all_chunks,
+        config.max_tokens,
+        config.packing_strategy,
+        &mut stats,
+    );
+    sort_chunks_by_key(&mut chunks, config.sort_by, &root_path);
 
     let file_tokens = file_token_totals(&chunks);
     let included_files = selected_files_with_tokens(selected_files.clone(), &file_tokens);
@@ -157,28 +305,39 @@ pub fn execute(mut config: Config, options: ExportExecutionOptions) -> Result<Ex
     stats.chunks_created = chunks.len();
     stats.total_tokens_estimated = chunks.iter().map(|c| c.token_estimate).sum();
     stats.total_tokens_estimated_rag = stats.total_tokens_estimated;
-    stats.rag_chunks_rendered =
-        if matches!(config.mode, OutputMode::Rag | OutputMode::Both) { chunks.len() } else { 0 };
-    stats.files_selected_rag = if matches!(config.mode, OutputMode::Rag | OutputMode::Both) {
-        included_files.len()
-    } else {
-        0
-    };
-    stats.files_selected_prompt = if matches!(config.mode, OutputMode::Prompt | OutputMode::Both) {
-        included_files.len()
-    } else {
-        0
-    };
+    stats.rag_chunks_rendered = if config.mode.includes_rag() { chunks.len() } else { 0 };
+    stats.files_selected_rag = if config.mode.includes_rag() { included_files.len() } else { 0 };
+    stats.files_selected_prompt =
+        if config.mode.includes_prompt() { included_files.len() } else { 0 };
     update_dispositions_for_outputs(&mut dispositions, &included_files, &chunks, config.mode);
-    mark_token_dropped(&mut dispositions, &selected_files, &included_files);
+    mark_token_dropped(&mut dispositions, &selected_files, &included_files, &candidate_file_tokens);
+
+    if options.dry_run {
+        return Ok(ExportOutcome {
+            root_path,
+            stats,
+            output_files: Vec::new(),
+            dispositions,
+            candidate_tokens,
+            redactions,
+            ranking_debug,
+            redaction_matches: BTreeMap::new(),
+        });
+    }
 
     let highlights: HashSet<String> =
         included_files.iter().take(10).map(|f| f.relative_path.clone()).collect();
-    let tree = generate_tree(&root_path, config.tree_depth, true, &highlights)?;
+    let tree = generate_tree(
+        &root_path,
+        config.tree_depth,
+        config.tree_collapsed_depth,
+        true,
+        &highlights,
+    )?;
 
     let repo_name = repo_name_for_output(&root_path, config.repo_url.as_deref());
     let module_basename = module_run.as_ref().map(|module| module.entry_basename.as_str());
-    let output_dir = resolve_output_dir(&config.output_dir, &repo_name, module_basename);
+    let output_dir = resolve_output_dir(&config, &root_path, &repo_name, module_basename);
     fs::create_dir_all(&output_dir)?;
 
     let output_prefix = module_basename
@@ -186,9 +345,17 @@ pub fn execute(mut config: Config, options: ExportExecutionOptions) -> Result<Ex
         .unwrap_or_else(|| repo_name.clone());
     let context_path = output_dir.join(format!("{}_context_pack.md", output_prefix));
     let jsonl_path = output_dir.join(format!("{}_chunks.jsonl", output_prefix));
+    let html_path = output_dir.join(format!("{}_context_pack.html", output_prefix));
+    let langchain_path = output_dir.join(format!("{}_langchain.jsonl", output_prefix));
+    let llamaindex_path = output_dir.join(format!("{}_llamaindex.jsonl", output_prefix));
+    let openai_batch_path = output_dir.join(format!("{}_openai_batch.jsonl", output_prefix));
+    let editor_snippets_path = output_dir.join(format!("{}_editor_snippets.jsonl", output_prefix));
     let report_path = output_dir.join(format!("{}_report.json", output_prefix));
+    let manifest_path = output_dir.join(format!("{}_manifest.json", output_prefix));
 
     let mut output_files = Vec::new();
+    let mut compressed_outputs = Vec::new();
+    let mut prompt_pack: Option<String> = None;
 
     match config.mode {
         OutputMode::Prompt => {
@@ -204,18 +371,31 @@ pub fn execute(mut config: Config, options: ExportExecutionOptions) -> Result<Ex
                 full_inventory: config.full_inventory,
                 include_timestamp: options.include_timestamp,
             };
-            let mut content = ctx.render();
-            if let Some(module) = &module_run {
-                content = format!("{}{}", module.header, content);
-            }
-            stats.total_tokens_estimated_prompt = estimate_tokens(&content);
-            fs::write(&context_path, content)?;
-            output_files.push(context_path.display().to_string());
+            let pack = render_prompt_pack(&ctx, options.template_path.as_deref())?;
+            let content = match &module_run {
+                Some(module) => format!("{}{}", module.header, pack),
+                None => pack.clone(),
+            };
+            stats.total_tokens_estimated_prompt =
+                Tokenizer::for_model(config.semantic_model.as_deref()).count(&content);
+            write_text_artifact(
+                &context_path,
+                &content,
+                options.compress,
+                &mut output_files,
+                &mut compressed_outputs,
+            )?;
+            prompt_pack = Some(pack);
         }
         OutputMode::Rag => {
             let jsonl = render_jsonl(&chunks);
-            fs::write(&jsonl_path, jsonl)?;
-            output_files.push(jsonl_path.display().to_string());
+            write_text_artifact(
+                &jsonl_path,
+                &jsonl,
+                options.compress,
+                &mut output_files,
+                &mut compressed_outputs,
+            )?;
         }
         OutputMode::Both => {
             stats.prompt_chunks_rendered = chunks.len();
@@ -230,20 +410,97 @@ pub fn execute(mut config: Config, options: ExportExecutionOptions) -> Result<Ex
                 full_inventory: config.full_inventory,
                 include_timestamp: options.include_timestamp,
             };
-            let mut content = ctx.render();
-            if let Some(module) = &module_run {
-                content = format!("{}{}", module.header, content);
-            }
-            stats.total_tokens_estimated_prompt = estimate_tokens(&content);
-            fs::write(&context_path, content)?;
-            output_files.push(context_path.display().to_string());
+            let pack = render_prompt_pack(&ctx, options.template_path.as_deref())?;
+            let content = match &module_run {
+                Some(module) => format!("{}{}", module.header, pack),
+                None => pack.clone(),
+            };
+            stats.total_tokens_estimated_prompt =
+                Tokenizer::for_model(config.semantic_model.as_deref()).count(&content);
+            write_text_artifact(
+                &context_path,
+                &content,
+                options.compress,
+                &mut output_files,
+                &mut compressed_outputs,
+            )?;
+            prompt_pack = Some(pack);
 
             let jsonl = render_jsonl(&chunks);
-            fs::write(&jsonl_path, jsonl)?;
-            output_files.push(jsonl_path.display().to_string());
+            write_text_artifact(
+                &jsonl_path,
+                &jsonl,
+                options.compress,
+                &mut output_files,
+                &mut compressed_outputs,
+            )?;
+        }
+        OutputMode::Html => {
+            let html = render_html(&root_path, &chunks, config.max_tokens);
+            fs::write(&html_path, html)?;
+            output_files.push(html_path.display().to_string());
+        }
+        OutputMode::Langchain => {
+            let docs = render_langchain_docs(&chunks);
+            fs::write(&langchain_path, docs)?;
+            output_files.push(langchain_path.display().to_string());
+        }
+        OutputMode::Llamaindex => {
+            let nodes = render_llamaindex_docs(&chunks);
+            fs::write(&llamaindex_path, nodes)?;
+            output_files.push(llamaindex_path.display().to_string());
+        }
+        OutputMode::OpenaiBatch => {
+            let requests = render_openai_batch(
+                &chunks,
+                options.system_prompt.as_deref().unwrap_or(DEFAULT_BATCH_SYSTEM_PROMPT),
+                options.model.as_deref().unwrap_or(DEFAULT_BATCH_MODEL),
+            );
+            fs::write(&openai_batch_path, requests)?;
+            output_files.push(openai_batch_path.display().to_string());
+        }
+        OutputMode::EditorSnippets => {
+            let snippets = render_editor_snippets(&root_path, &chunks);
+            fs::write(&editor_snippets_path, snippets)?;
+            output_files.push(editor_snippets_path.display().to_string());
         }
     }
 
+    if let Some(pack) = prompt_pack.as_deref().filter(|_| !options.merge_with.is_empty()) {
+        let others = options
+            .merge_with
+            .iter()
+            .map(|path| {
+                fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read context pack: {}", path.display()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut packs = vec![pack];
+        packs.extend(others.iter().map(String::as_str));
+        let merged = merge_context_packs(
+            packs,
+            config.max_tokens.unwrap_or(usize::MAX),
+            Tokenizer::for_model(config.semantic_model.as_deref()),
+        )?;
+        write_text_artifact(
+            &output_dir.join(format!("{}_merged_context_pack.md", output_prefix)),
+            &merged,
+            options.compress,
+            &mut output_files,
+            &mut compressed_outputs,
+        )?;
+    }
+
+    if options.sbom {
+        let sbom_path = output_dir.join(format!("{}_sbom.spdx.json", repo_name));
+        let mut doc = generate_sbom(&root_path)?;
+        doc.name = repo_name.clone();
+        let created =
+            options.include_timestamp.then(|| Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string());
+        write_sbom(&sbom_path, &doc, created.as_deref())?;
+        output_files.push(sbom_path.display().to_string());
+    }
+
     stats.processing_time_seconds =
         if options.include_timestamp { started.elapsed().as_secs_f64() } else { 0.0 };
     let config_json = build_config_json(&config);
@@ -252,6 +509,7 @@ pub fn execute(mut config: Config, options: ExportExecutionOptions) -> Result<Ex
         "repo": config.repo_url.as_ref().map(|u| redact_url_credentials(u)),
         "ref": config.ref_,
         "tool_version": env!("CARGO_PKG_VERSION"),
+        "license": manifest_info.get("license"),
         "note": "Report includes deterministic stats and explicit supported fields only.",
     });
 
@@ -302,42 +560,156 @@ pub fn execute(mut config: Config, options: ExportExecutionOptions) -> Result<Ex
             include_timestamp: options.include_timestamp,
             provenance: Some(&provenance),
             focus: focus_json.as_ref(),
+            compressed_outputs: &compressed_outputs,
         },
     )?;
     output_files.push(report_path.display().to_string());
 
-    Ok(ExportOutcome { root_path, stats, output_files })
+    if options.archive {
+        let artifacts: Vec<PathBuf> = output_files.iter().map(PathBuf::from).collect();
+        let timestamp = options.include_timestamp.then(Utc::now);
+        let archive_path = write_archive(&output_dir, &output_prefix, &artifacts, timestamp)?;
+        output_files.push(archive_path.display().to_string());
+    }
+
+    let artifacts: Vec<PathBuf> = output_files.iter().map(PathBuf::from).collect();
+    let fingerprint =
+        provenance_fingerprint(&provenance, &config_json, head_commit(&root_path).as_deref());
+    write_manifest(&manifest_path, &artifacts, &fingerprint)?;
+    output_files.push(manifest_path.display().to_string());
+
+    Ok(ExportOutcome {
+        root_path,
+        stats,
+        output_files,
+        dispositions,
+        candidate_tokens,
+        redactions,
+        ranking_debug,
+        redaction_matches: BTreeMap::new(),
+    })
 }
 
-struct ProcessedFile {
-    chunks: Vec<Chunk>,
-    redacted: bool,
-    counts: BTreeMap<String, usize>,
+/// Write a text artifact, or its gzip-compressed form at `<path>.gz` when
+/// `compress` is set; compressed writes also record `path`, `original_bytes`,
+/// and `compressed_bytes` for the report.
+fn write_text_artifact(
+    path: &Path,
+    content: &str,
+    compress: bool,
+    output_files: &mut Vec<String>,
+    compressed_outputs: &mut Vec<Value>,
+) -> Result<()> {
+    if !compress {
+        fs::write(path, content)?;
+        output_files.push(path.display().to_string());
+        return Ok(());
+    }
+    let gz_path = PathBuf::from(format!("{}.gz", path.display()));
+    write_compressed(&gz_path, content)?;
+    compressed_outputs.push(json!({
+        "path": gz_path.display().to_string(),
+        "original_bytes": content.len(),
+        "compressed_bytes": fs::metadata(&gz_path)?.len(),
+    }));
+    output_files.push(gz_path.display().to_string());
+    Ok(())
 }
 
-fn process_file(
+fn render_prompt_pack(ctx: &ContextPackCtx<'_>, template_path: Option<&Path>) -> Result<String> {
+    match template_path {
+        Some(path) => render_template(path, &TemplateContext::from_context_pack(ctx)),
+        None => Ok(ctx.render()),
+    }
+}
+
+/// Replacement content for `.env` files when redaction is enabled.
+pub(crate) const ENV_FILE_PLACEHOLDER: &str = "# [.env file redacted]\n";
+
+pub(crate) struct ProcessedFile {
+    pub(crate) chunks: Vec<Chunk>,
+    pub(crate) redacted: bool,
+    pub(crate) counts: BTreeMap<String, usize>,
+}
+
+/// File content, preferring a module-focus override over the file on disk.
+/// Files above [`MMAP_THRESHOLD_BYTES`] are memory-mapped.
+fn read_file_content(
     file: &FileInfo,
-    redactor: Option<&Redactor>,
-    config: &Config,
     content_overrides: Option<&HashMap<PathBuf, String>>,
-) -> Result<ProcessedFile> {
+) -> Result<FileText> {
     let canonical_path = file.path.canonicalize().unwrap_or_else(|_| file.path.clone());
-    let raw_content = if let Some(content) =
+    if let Some(content) =
         content_overrides.and_then(|m| m.get(&file.path).or_else(|| m.get(&canonical_path)))
     {
-        content.clone()
+        return Ok(FileText::Owned(content.clone()));
+    }
+    let read = if file.size_bytes > MMAP_THRESHOLD_BYTES {
+        read_file_mmap(&file.path).map(|(content, _)| content)
     } else {
-        read_file_safe(&file.path, None, None)
-            .with_context(|| format!("Failed to read {}", file.relative_path))?
-            .0
+        read_file_safe(&file.path, None, None).map(|(content, _)| FileText::Owned(content))
     };
+    read.with_context(|| format!("Failed to read {}", file.relative_path))
+}
 
-    let file_name =
-        Path::new(&file.relative_path).file_name().and_then(|name| name.to_str()).unwrap_or("");
+fn file_basename(file: &FileInfo) -> &str {
+    Path::new(&file.relative_path).file_name().and_then(|name| name.to_str()).unwrap_or("")
+}
+
+/// Redaction matches for each selected file that has any, following the
+/// same env-file and allowlist handling as [`process_file`]. An env file is
+/// reported as a single `env_file` match covering the whole file.
+fn redaction_dry_run(
+    files: &[FileInfo],
+    redactor: &Redactor,
+    content_overrides: Option<&HashMap<PathBuf, String>>,
+) -> Result<BTreeMap<String, Vec<RedactionMatch>>> {
+    let mut report = BTreeMap::new();
+    for file in files {
+        let file_name = file_basename(file);
+        if !file.is_env_file && redactor.is_file_allowlisted(file_name, &file.relative_path) {
+            continue;
+        }
+        let content = read_file_content(file, content_overrides)?;
+        let matches = if file.is_env_file {
+            vec![RedactionMatch {
+                rule_name: "env_file".to_string(),
+                start: 0,
+                end: content.len(),
+                matched_text_preview: "***".to_string(),
+            }]
+        } else {
+            redactor.redact_dry_run_for_file(
+                &content,
+                &file.language,
+                &file.extension,
+                file_name,
+                &file.relative_path,
+            )
+        };
+        if !matches.is_empty() {
+            report.insert(file.relative_path.clone(), matches);
+        }
+    }
+    Ok(report)
+}
 
-    let (content, counts) = if let Some(redactor) = redactor {
+pub(crate) fn process_file(
+    file: &FileInfo,
+    redactor: Option<&Redactor>,
+    config: &Config,
+    content_overrides: Option<&HashMap<PathBuf, String>>,
+) -> Result<ProcessedFile> {
+    let raw_content = read_file_content(file, content_overrides)?;
+    let file_name = file_basename(file);
+
+    let (content, counts): (Cow<'_, str>, _) = if file.is_env_file {
+        // Dotenv files are secrets by definition; drop the whole body, even
+        // with redaction off, rather than trusting pattern matches.
+        (ENV_FILE_PLACEHOLDER.into(), BTreeMap::from([("env_file".to_string(), 1)]))
+    } else if let Some(redactor) = redactor {
         if redactor.is_file_allowlisted(file_name, &file.relative_path) {
-            (raw_content, BTreeMap::new())
+            (Cow::Borrowed(&*raw_content), BTreeMap::new())
         } else {
             let outcome = redactor.redact_with_language_report(
                 &raw_content,
@@ -346,21 +718,26 @@ fn process_file(
                 file_name,
                 &file.relative_path,
             );
-            (outcome.content, outcome.counts)
+            (outcome.content.into(), outcome.counts)
         }
     } else {
-        (raw_content, BTreeMap::new())
+        (Cow::Borrowed(&*raw_content), BTreeMap::new())
     };
 
     let redacted = !counts.is_empty();
 
+    let options = ChunkOptions::from_config(config);
     let raw_chunks = if should_prompt_summary_only(file) {
-        vec![summary_chunk(file, &content)]
+        vec![summary_chunk(file, &content, options.tokenizer)]
     } else {
-        chunk_content(file, &content, config.chunk_tokens, config.chunk_overlap)?
+        chunk_content(file, &content, &options)?
     };
-    let mut chunks =
-        coalesce_small_chunks_with_max(raw_chunks, config.min_chunk_tokens, config.chunk_tokens);
+    let mut chunks = coalesce_small_chunks_with_max(
+        raw_chunks,
+        config.min_chunk_tokens,
+        config.chunk_tokens,
+        options.tokenizer,
+    );
 
     // Re-enrich after coalescing to correct chunk_index, chunks_in_file,
     // byte offsets, content_sha256, file_sha256, and file_id.
@@ -368,13 +745,37 @@ fn process_file(
         enrich_chunks(&mut chunks, file, &content);
     }
 
-    for chunk in &mut chunks {
-        chunk.token_estimate = estimate_tokens(&chunk.content);
-    }
-
     Ok(ProcessedFile { chunks, redacted, counts })
 }
 
+/// Keep chunks that have a tag matching every `require` glob and no tag
+/// matching any `exclude` glob (`def:*` matches any definition tag).
+fn filter_chunks_by_tags(
+    chunks: Vec<Chunk>,
+    require: &[String],
+    exclude: &[String],
+) -> Result<Vec<Chunk>> {
+    let compile = |patterns: &[String]| -> Result<Vec<GlobMatcher>> {
+        patterns
+            .iter()
+            .map(|p| {
+                Ok(Glob::new(p)
+                    .with_context(|| format!("Invalid tag glob '{p}'"))?
+                    .compile_matcher())
+            })
+            .collect()
+    };
+    let require = compile(require)?;
+    let exclude = compile(exclude)?;
+    Ok(chunks
+        .into_iter()
+        .filter(|chunk| {
+            let has = |m: &GlobMatcher| chunk.tags.iter().any(|tag| m.is_match(tag));
+            require.iter().all(has) && !exclude.iter().any(has)
+        })
+        .collect())
+}
+
 fn apply_file_byte_budget(
     ranked_files: Vec<FileInfo>,
     max_total_bytes: u64,
@@ -415,9 +816,96 @@ fn apply_file_byte_budget(
     selected
 }
 
+/// Keep one file per group of byte-identical files: the highest priority,
+/// then the shortest path, then the lexicographically first. Order is
+/// otherwise preserved; unreadable files are always kept.
+fn dedup_files_by_content(
+    files: Vec<FileInfo>,
+    stats: &mut ScanStats,
+    dispositions: &mut [FileDisposition],
+) -> Vec<FileInfo> {
+    let hashes: Vec<Option<[u8; 32]>> = files
+        .iter()
+        .map(|file| fs::read(&file.path).ok().map(|b| Sha256::digest(b).into()))
+        .collect();
+
+    let mut keeper: HashMap<[u8; 32], usize> = HashMap::new();
+    for (idx, hash) in hashes.iter().enumerate() {
+        let Some(hash) = hash else { continue };
+        let best = keeper.entry(*hash).or_insert(idx);
+        let (current, candidate) = (&files[*best], &files[idx]);
+        let better = candidate
+            .priority
+            .partial_cmp(&current.priority)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| current.relative_path.len().cmp(&candidate.relative_path.len()))
+            .then_with(|| current.relative_path.cmp(&candidate.relative_path))
+            .is_gt();
+        if better {
+            *best = idx;
+        }
+    }
+
+    let mut kept = Vec::with_capacity(files.len());
+    for (idx, file) in files.iter().enumerate() {
+        let Some(best) = hashes[idx].and_then(|hash| keeper.get(&hash).copied()) else {
+            kept.push(file.clone());
+            continue;
+        };
+        if best == idx {
+            kept.push(file.clone());
+            continue;
+        }
+        stats.files_deduplicated += 1;
+        set_disposition_reason(
+            dispositions,
+            &file.relative_path,
+            FileDispositionReason::DroppedDuplicateContent,
+        );
+        stats.dropped_files.push(HashMap::from([
+            ("path".to_string(), json!(file.relative_path)),
+            ("reason".to_string(), json!("duplicate_content")),
+            ("duplicate_of".to_string(), json!(files[best].relative_path)),
+            ("priority".to_string(), json!(file.priority)),
+        ]));
+    }
+    kept
+}
+
+/// Reorder budgeted chunks (which arrive in priority order) for output.
+///
+/// The sort is stable, so ties keep priority order; `Modified` reads each
+/// file's mtime under `root_path`, treating unreadable files as oldest.
+pub fn sort_chunks_by_key(chunks: &mut [Chunk], key: ChunkSortKey, root_path: &Path) {
+    let by_location =
+        |a: &Chunk, b: &Chunk| a.path.cmp(&b.path).then_with(|| a.start_line.cmp(&b.start_line));
+    match key {
+        ChunkSortKey::Priority => {}
+        ChunkSortKey::Path => chunks.sort_by(by_location),
+        ChunkSortKey::Language => chunks.sort_by(|a, b| a.language.cmp(&b.language)),
+        ChunkSortKey::StartLine => {
+            chunks.sort_by(|a, b| a.start_line.cmp(&b.start_line).then_with(|| a.path.cmp(&b.path)))
+        }
+        ChunkSortKey::Modified => {
+            let mut mtimes: HashMap<String, std::time::SystemTime> = HashMap::new();
+            for chunk in chunks.iter() {
+                mtimes.entry(chunk.path.clone()).or_insert_with(|| {
+                    fs::metadata(root_path.join(&chunk.path))
+                        .and_then(|meta| meta.modified())
+                        .unwrap_or(std::time::UNIX_EPOCH)
+                });
+            }
+            chunks.sort_by(|a, b| {
+                mtimes[&b.path].cmp(&mtimes[&a.path]).then_with(|| by_location(a, b))
+            });
+        }
+    }
+}
+
 fn apply_chunk_token_budget(
     mut chunks: Vec<Chunk>,
     max_tokens: Option<usize>,
+    strategy: PackingStrategy,
     stats: &mut ScanStats,
 ) -> Vec<Chunk> {
     chunks.sort_by(|a, b| {
@@ -458,16 +946,32 @@ fn apply_chunk_token_budget(
             deferred.push(chunk.clone());
         }
     }
-    for chunk in deferred {
-        if used.saturating_add(chunk.token_estimate) > limit {
-            dropped_paths.insert(chunk.path.clone());
-            continue;
+    match strategy {
+        PackingStrategy::Ranked => {
+            for chunk in deferred {
+                if used.saturating_add(chunk.token_estimate) > limit {
+                    dropped_paths.insert(chunk.path.clone());
+                    continue;
+                }
+                used += chunk.token_estimate;
+                seen_paths.insert(chunk.path.clone());
+                kept.push(chunk);
+            }
+        }
+        PackingStrategy::Greedy => {
+            let packed = pack_chunks_to_budget(deferred.clone(), limit.saturating_sub(used));
+            let packed_ids: HashSet<&str> = packed.iter().map(|c| c.id.as_str()).collect();
+            for chunk in deferred.iter().filter(|c| !packed_ids.contains(c.id.as_str())) {
+                dropped_paths.insert(chunk.path.clone());
+            }
+            kept.extend(packed);
         }
-        used += chunk.token_estimate;
-        seen_paths.insert(chunk.path.clone());
-        kept.push(chunk);
     }
 
+    // A file is partial when the budget kept some of its chunks but not all.
+    let kept_paths: HashSet<&str> = kept.iter().map(|c| c.path.as_str()).collect();
+    stats.partial_files = kept_paths.iter().filter(|p| dropped_paths.contains(**p)).count();
+
     for path in dropped_paths {
         stats.dropped_files.push(HashMap::from([
             ("path".to_string(), json!(path)),
@@ -486,15 +990,16 @@ fn should_prompt_summary_only(file: &FileInfo) -> bool {
     file.tags.contains("lock-file")
 }
 
-fn summary_chunk(file: &FileInfo, content: &str) -> Chunk {
+fn summary_chunk(file: &FileInfo, content: &str, tokenizer: Tokenizer) -> Chunk {
     let summary = format!(
         "Summary only: {}\nlanguage: {}\nbytes: {}\ntokens_estimate: {}\nrole/tags: {}\n",
         file.relative_path,
         file.language,
         file.size_bytes,
-        estimate_tokens(content),
+        tokenizer.count(content),
         file.tags.iter().cloned().collect::<Vec<_>>().join(",")
     );
+    let token_estimate = tokenizer.count(&summary);
     let id = crate::utils::stable_hash(&summary, &file.relative_path, 1, 1);
     let content_sha256 = format!("{:x}", Sha256::digest(summary.as_bytes()));
     let file_sha256 = format!("{:x}", Sha256::digest(content.as_bytes()));
@@ -507,7 +1012,7 @@ fn summary_chunk(file: &FileInfo, content: &str) -> Chunk {
         content: summary,
         priority: file.priority,
         tags: file.tags.clone(),
-        token_estimate: 64,
+        token_estimate,
         file_id: file.id.clone(),
         chunk_index: 0,
         chunks_in_file: 1,
@@ -539,10 +1044,10 @@ fn update_dispositions_for_outputs(
         if let Some(d) = dispositions.iter_mut().find(|d| d.path == file.relative_path) {
             d.priority = Some(file.priority);
             d.token_estimate = Some(file.token_estimate);
-            d.included_in_prompt = matches!(mode, OutputMode::Prompt | OutputMode::Both)
-                && chunk_paths.contains(file.relative_path.as_str());
-            d.included_in_rag = matches!(mode, OutputMode::Rag | OutputMode::Both)
-                && chunk_paths.contains(file.relative_path.as_str());
+            d.included_in_prompt =
+                mode.includes_prompt() && chunk_paths.contains(file.relative_path.as_str());
+            d.included_in_rag =
+                mode.includes_rag() && chunk_paths.contains(file.relative_path.as_str());
             d.reason = if should_prompt_summary_only(file) {
                 FileDispositionReason::IncludedSummaryOnly
             } else if chunks.iter().filter(|c| c.path == file.relative_path).count() > 1 {
@@ -570,6 +1075,7 @@ fn mark_token_dropped(
     dispositions: &mut [FileDisposition],
     selected_files: &[FileInfo],
     included_files: &[FileInfo],
+    candidate_tokens: &HashMap<String, usize>,
 ) {
     let included: HashSet<&str> = included_files.iter().map(|f| f.relative_path.as_str()).collect();
     for file in selected_files {
@@ -579,6 +1085,12 @@ fn mark_token_dropped(
                 &file.relative_path,
                 FileDispositionReason::DroppedTokenBudget,
             );
+            // Record what the file would have cost so dropped entries are comparable.
+            if let Some(tokens) = candidate_tokens.get(&file.relative_path) {
+                if let Some(d) = dispositions.iter_mut().find(|d| d.path == file.relative_path) {
+                    d.token_estimate = Some(*tokens);
+                }
+            }
         }
     }
 }
@@ -611,7 +1123,10 @@ fn selected_files_with_tokens(
     selected
 }
 
-fn build_redactor(mode: RedactionMode, cfg: &crate::domain::RedactionConfig) -> Redactor {
+pub(crate) fn build_redactor(
+    mode: RedactionMode,
+    cfg: &crate::domain::RedactionConfig,
+) -> Redactor {
     match mode {
         RedactionMode::Fast => Redactor::from_config(false, false, false, cfg),
         RedactionMode::Standard => Redactor::from_config(true, false, false, cfg),
@@ -620,11 +1135,82 @@ fn build_redactor(mode: RedactionMode, cfg: &crate::domain::RedactionConfig) ->
     }
 }
 
-fn resolve_output_dir(base_dir: &Path, repo_name: &str, module_basename: Option<&str>) -> PathBuf {
-    let repo_dir = base_dir.join(repo_name);
+/// Resolve the directory artifacts are written to.
+///
+/// A plain `output_dir` gets a `<repo_name>/` subdirectory. When it contains
+/// `{...}` template variables it is used as-is after substitution, so
+/// `exports/{date}/{repo_name}` yields `exports/2025-01-15/myrepo/`.
+fn resolve_output_dir(
+    config: &Config,
+    root_path: &Path,
+    repo_name: &str,
+    module_basename: Option<&str>,
+) -> PathBuf {
+    let base = config.output_dir.to_string_lossy();
+    let repo_dir = if OUTPUT_DIR_VAR.is_match(&base) {
+        let mut vars = HashMap::from([
+            ("repo_name".to_string(), repo_name.to_string()),
+            ("date".to_string(), Utc::now().format("%Y-%m-%d").to_string()),
+            ("mode".to_string(), output_mode_name(config.mode).to_string()),
+        ]);
+        if let Some(commit) = head_commit_short(root_path) {
+            vars.insert("git_commit".to_string(), commit);
+        }
+        resolve_output_dir_with_template(&base, &vars)
+    } else {
+        config.output_dir.join(repo_name)
+    };
     module_basename.map(|entry| repo_dir.join(format!("focus_{entry}"))).unwrap_or(repo_dir)
 }
 
+static OUTPUT_DIR_VAR: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{([A-Za-z_][A-Za-z0-9_]*)\}").expect("valid regex"));
+
+/// Substitute `{name}` variables in an output directory template.
+///
+/// Unknown variables are left in place literally and logged as a warning.
+pub fn resolve_output_dir_with_template(template: &str, vars: &HashMap<String, String>) -> PathBuf {
+    let resolved = OUTPUT_DIR_VAR.replace_all(template, |caps: &regex::Captures<'_>| {
+        let name = &caps[1];
+        match vars.get(name) {
+            Some(value) => value.clone(),
+            None => {
+                tracing::warn!("Unknown output directory variable {{{name}}}; leaving it as-is");
+                caps[0].to_string()
+            }
+        }
+    });
+    PathBuf::from(resolved.as_ref())
+}
+
+/// First 8 characters of the HEAD commit, when `root_path` is in a git repository.
+fn head_commit_short(root_path: &Path) -> Option<String> {
+    head_commit(root_path).map(|id| id.chars().take(8).collect())
+}
+
+/// Full id of the HEAD commit, when `root_path` is in a git repository.
+fn head_commit(root_path: &Path) -> Option<String> {
+    let repo = git2::Repository::discover(root_path).ok()?;
+    let commit = repo.head().ok()?.peel_to_commit().ok()?;
+    Some(commit.id().to_string())
+}
+
+/// SHA-256 over the run's provenance, effective config, and HEAD commit.
+///
+/// Remote exports clone into a fresh temp directory each time, so the clone
+/// path is left out for them; re-running the same repository, ref, and config
+/// yields the same fingerprint.
+fn provenance_fingerprint(provenance: &Value, config: &Value, commit: Option<&str>) -> String {
+    let mut provenance = provenance.clone();
+    if !provenance["repo"].is_null() {
+        if let Some(fields) = provenance.as_object_mut() {
+            fields.remove("path");
+        }
+    }
+    let input = json!({ "provenance": provenance, "config": config, "commit": commit });
+    format!("{:x}", Sha256::digest(input.to_string().as_bytes()))
+}
+
 fn repo_name_for_output(root_path: &Path, repo_url: Option<&str>) -> String {
     if let Some(url) = repo_url {
         if let Some(name) = repo_name_from_remote_url(url) {
@@ -645,17 +1231,26 @@ fn repo_name_from_remote_url(url: &str) -> Option<String> {
     }
 }
 
+fn output_mode_name(mode: OutputMode) -> &'static str {
+    match mode {
+        OutputMode::Prompt => "prompt",
+        OutputMode::Rag => "rag",
+        OutputMode::Both => "both",
+        OutputMode::Html => "html",
+        OutputMode::Langchain => "langchain",
+        OutputMode::Llamaindex => "llamaindex",
+        OutputMode::OpenaiBatch => "openai-batch",
+        OutputMode::EditorSnippets => "editor-snippets",
+    }
+}
+
 fn build_config_json(config: &Config) -> Value {
     let mut include_extensions: Vec<String> = config.include_extensions.iter().cloned().collect();
     include_extensions.sort();
     let mut exclude_globs: Vec<String> = config.exclude_globs.iter().cloned().collect();
     exclude_globs.sort();
 
-    let mode = match config.mode {
-        OutputMode::Prompt => "prompt",
-        OutputMode::Rag => "rag",
-        OutputMode::Both => "both",
-    };
+    let mode = output_mode_name(config.mode);
 
     let redaction_mode = match config.redaction_mode {
         RedactionMode::Fast => "fast",
@@ -666,10 +1261,14 @@ fn build_config_json(config: &Config) -> Value {
 
     let coverage_strategy = if config.max_tokens.is_some() { "budget" } else { "full" };
 
-    json!({
+    // Two literals: a single `json!` this large exceeds the macro recursion limit.
+    let mut object = json!({
         "path": config.path,
         "repo": config.repo_url.as_ref().map(|u| redact_url_credentials(u)),
         "ref": config.ref_,
+        "proxy_url": config.proxy_url.as_ref().map(|u| redact_url_credentials(u)),
+        "no_proxy": config.no_proxy,
+        "gitea_hosts": config.gitea_hosts,
         "include_extensions": include_extensions,
         "exclude_globs": exclude_globs,
         "max_file_bytes": config.max_file_bytes,
@@ -677,21 +1276,48 @@ fn build_config_json(config: &Config) -> Value {
         "respect_gitignore": config.respect_gitignore,
         "follow_symlinks": config.follow_symlinks,
         "skip_minified": config.skip_minified,
+        "file_scan_timeout_ms": config.file_scan_timeout_ms,
+        "max_depth": config.max_depth,
+        "sniff_content": config.sniff_content,
+        "include_env_files": config.include_env_files,
+    });
+    let output = json!({
         "max_tokens": config.max_tokens,
         "chunk_tokens": config.chunk_tokens,
         "chunk_overlap": config.chunk_overlap,
         "min_chunk_tokens": config.min_chunk_tokens,
+        "semantic_model": config.semantic_model,
+        "strip_comments": config.strip_comments,
+        "normalize_whitespace": config.normalize_whitespace,
+        "extract_docs": config.extract_docs,
+        "dedup_files": config.dedup_files,
+        "dedup_chunks": config.dedup_chunks,
+        "dedup_threshold": config.dedup_threshold,
+        "priority_decay": config.priority_decay,
+        "custom_tree_sitter_queries": config
+            .custom_tree_sitter_queries
+            .iter()
+            .collect::<BTreeMap<_, _>>(),
+        "require_tags": &config.require_tags,
+        "exclude_tags": &config.exclude_tags,
         "coverage_strategy": coverage_strategy,
+        "packing_strategy": config.packing_strategy,
+        "sort_by": config.sort_by,
         "mode": mode,
         "output_dir": config.output_dir,
         "tree_depth": config.tree_depth,
+        "tree_collapsed_depth": config.tree_collapsed_depth,
         "redact_secrets": config.redact_secrets,
         "redaction_mode": redaction_mode,
         "module": {
             "module_roots": &config.module.module_roots,
             "css_files": &config.module.css_files,
         },
-    })
+    });
+    if let (Value::Object(object), Value::Object(output)) = (&mut object, output) {
+        object.extend(output);
+    }
+    object
 }
 
 /// Redact secrets from manifest info (package.json scripts, etc.) that bypass
@@ -726,3 +1352,276 @@ fn redact_manifest_info(
     }
     info
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> HashMap<String, String> {
+        HashMap::from([
+            ("repo_name".to_string(), "myrepo".to_string()),
+            ("date".to_string(), "2025-01-15".to_string()),
+            ("git_commit".to_string(), "1a2b3c4d".to_string()),
+            ("mode".to_string(), "rag".to_string()),
+        ])
+    }
+
+    fn mk_chunk(path: &str, start_line: usize, tokens: usize) -> Chunk {
+        Chunk {
+            id: format!("{path}:{start_line}"),
+            path: path.to_string(),
+            language: "rust".to_string(),
+            start_line,
+            end_line: start_line,
+            content: String::new(),
+            priority: 0.5,
+            tags: Default::default(),
+            token_estimate: tokens,
+            file_id: String::new(),
+            chunk_index: 0,
+            chunks_in_file: 3,
+            byte_start: None,
+            byte_end: None,
+            content_sha256: String::new(),
+            file_sha256: String::new(),
+        }
+    }
+
+    #[test]
+    fn token_budget_keeps_chunks_that_fit_and_counts_partial_file() {
+        let chunks = vec![
+            mk_chunk("src/a.rs", 1, 40),
+            mk_chunk("src/a.rs", 2, 40),
+            mk_chunk("src/a.rs", 3, 40),
+        ];
+        let mut stats = ScanStats::default();
+
+        let kept = apply_chunk_token_budget(chunks, Some(100), PackingStrategy::Ranked, &mut stats);
+        assert_eq!(kept.iter().map(|c| c.start_line).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(stats.partial_files, 1);
+        // Partially kept files are still listed as having lost chunks to the budget.
+        assert_eq!(stats.dropped_files.len(), 1);
+    }
+
+    #[test]
+    fn sort_chunks_by_key_orders_deterministically_per_key() {
+        let tmp = tempfile::tempdir().expect("tmp");
+        let base = std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000);
+        for (rel, age) in [("src/b.rs", 0), ("src/a.py", 10), ("docs/c.md", 20)] {
+            let path = tmp.path().join(rel);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            let file = fs::File::create(&path).unwrap();
+            file.set_modified(base - std::time::Duration::from_secs(age)).unwrap();
+        }
+        let chunk = |path: &str, start: usize, language: &str, priority: f64| {
+            let mut c = mk_chunk(path, start, 10);
+            c.language = language.to_string();
+            c.priority = priority;
+            c
+        };
+        // Priority order, as produced by the token budget step.
+        let ranked = vec![
+            chunk("src/b.rs", 40, "rust", 0.9),
+            chunk("src/a.py", 1, "python", 0.8),
+            chunk("docs/c.md", 5, "markdown", 0.7),
+            chunk("src/b.rs", 2, "rust", 0.6),
+            chunk("src/a.py", 30, "python", 0.5),
+        ];
+        let order = |key: ChunkSortKey| {
+            let mut chunks = ranked.clone();
+            sort_chunks_by_key(&mut chunks, key, tmp.path());
+            chunks.iter().map(|c| c.id.clone()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            order(ChunkSortKey::Priority),
+            ["src/b.rs:40", "src/a.py:1", "docs/c.md:5", "src/b.rs:2", "src/a.py:30"]
+        );
+        assert_eq!(
+            order(ChunkSortKey::Path),
+            ["docs/c.md:5", "src/a.py:1", "src/a.py:30", "src/b.rs:2", "src/b.rs:40"]
+        );
+        assert_eq!(
+            order(ChunkSortKey::Language),
+            ["docs/c.md:5", "src/a.py:1", "src/a.py:30", "src/b.rs:40", "src/b.rs:2"]
+        );
+        assert_eq!(
+            order(ChunkSortKey::StartLine),
+            ["src/a.py:1", "src/b.rs:2", "docs/c.md:5", "src/a.py:30", "src/b.rs:40"]
+        );
+        assert_eq!(
+            order(ChunkSortKey::Modified),
+            ["src/b.rs:2", "src/b.rs:40", "src/a.py:1", "src/a.py:30", "docs/c.md:5"]
+        );
+    }
+
+    fn file_at(root: &Path, rel: &str, content: &str, priority: f64) -> FileInfo {
+        let path = root.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        FileInfo {
+            path,
+            relative_path: rel.to_string(),
+            size_bytes: content.len() as u64,
+            extension: ".rs".to_string(),
+            language: "rust".to_string(),
+            id: rel.to_string(),
+            priority,
+            token_estimate: 0,
+            tags: Default::default(),
+            is_readme: false,
+            is_config: false,
+            is_doc: false,
+            is_env_file: false,
+            is_symlink: false,
+            symlink_target: None,
+            complexity_estimate: 0,
+            priority_explanation: None,
+        }
+    }
+
+    #[test]
+    fn semantic_model_selects_the_tokenizer_budgets_count_with() {
+        let dir = tempfile::tempdir().unwrap();
+        let code = "fn main() {\n    println!(\"hello, world\");\n}\n";
+        let file = file_at(dir.path(), "src/main.rs", code, 0.5);
+        let chunks = |semantic_model: Option<&str>| {
+            let config =
+                Config { semantic_model: semantic_model.map(str::to_string), ..Config::default() };
+            process_file(&file, None, &config, None).unwrap().chunks
+        };
+        let tokens = |chunks: &[Chunk]| chunks.iter().map(|c| c.token_estimate).sum::<usize>();
+
+        let heuristic = tokens(&chunks(None));
+        let bpe = tokens(&chunks(Some("gpt-4")));
+        assert_eq!(heuristic, crate::utils::estimate_tokens(code));
+        assert_eq!(tokens(&chunks(Some("nomic-embed-text"))), heuristic);
+        if cfg!(feature = "tiktoken") {
+            assert_eq!(bpe, crate::utils::count_tokens_tiktoken(code, "gpt-4"));
+            assert_ne!(bpe, heuristic);
+        } else {
+            assert_eq!(bpe, heuristic);
+        }
+
+        // A budget between the two counts fits the file under only one tokenizer.
+        let limit = heuristic.min(bpe);
+        let kept = |semantic_model| {
+            let mut stats = ScanStats::default();
+            let budgeted = apply_chunk_token_budget(
+                chunks(semantic_model),
+                Some(limit),
+                PackingStrategy::Ranked,
+                &mut stats,
+            );
+            budgeted.len()
+        };
+        assert_eq!(kept(None) != kept(Some("gpt-4")), cfg!(feature = "tiktoken"));
+    }
+
+    #[test]
+    fn dedup_files_keeps_one_copy_per_identical_group() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = vec![
+            file_at(dir.path(), "vendor/copy/util.rs", "fn util() {}\n", 0.5),
+            file_at(dir.path(), "src/util.rs", "fn util() {}\n", 0.5),
+            file_at(dir.path(), "src/lib.rs", "pub mod util;\n", 0.5),
+            file_at(dir.path(), "gen/util_copy.rs", "fn util() {}\n", 0.4),
+        ];
+        let mut stats = ScanStats::default();
+
+        let kept = dedup_files_by_content(files, &mut stats, &mut []);
+        let paths: Vec<&str> = kept.iter().map(|f| f.relative_path.as_str()).collect();
+        assert_eq!(paths, ["src/util.rs", "src/lib.rs"]);
+        assert_eq!(stats.files_deduplicated, 2);
+        assert!(stats.dropped_files.iter().all(|d| d["duplicate_of"] == "src/util.rs"));
+    }
+
+    fn tagged(path: &str, tags: &[&str]) -> Chunk {
+        let mut chunk = mk_chunk(path, 1, 10);
+        chunk.tags = tags.iter().map(|t| t.to_string()).collect();
+        chunk
+    }
+
+    fn tag_filter_paths(require: &[&str], exclude: &[&str]) -> Vec<String> {
+        let chunks = vec![
+            tagged("src/auth.rs", &["def:verify", "type:Token"]),
+            tagged("tests/auth.rs", &["def:test_verify", "reason:test"]),
+            tagged("README.md", &["section:Usage"]),
+        ];
+        let owned = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        filter_chunks_by_tags(chunks, &owned(require), &owned(exclude))
+            .expect("valid globs")
+            .into_iter()
+            .map(|c| c.path)
+            .collect()
+    }
+
+    #[test]
+    fn require_tag_keeps_only_matching_chunks() {
+        assert_eq!(tag_filter_paths(&["reason:test"], &[]), ["tests/auth.rs"]);
+        assert_eq!(tag_filter_paths(&["def:verify", "type:Token"], &[]), ["src/auth.rs"]);
+    }
+
+    #[test]
+    fn tag_globs_match_prefixes() {
+        assert_eq!(tag_filter_paths(&["def:*"], &[]), ["src/auth.rs", "tests/auth.rs"]);
+        assert_eq!(tag_filter_paths(&[], &["section:*"]), ["src/auth.rs", "tests/auth.rs"]);
+    }
+
+    #[test]
+    fn require_and_exclude_tags_combine() {
+        assert_eq!(tag_filter_paths(&["def:*"], &["reason:test"]), ["src/auth.rs"]);
+        assert!(tag_filter_paths(&["def:*"], &["def:*"]).is_empty());
+        assert!(filter_chunks_by_tags(Vec::new(), &["[".to_string()], &[]).is_err());
+    }
+
+    #[test]
+    fn output_dir_template_substitutes_each_variable() {
+        let vars = vars();
+        let resolve = |t: &str| resolve_output_dir_with_template(t, &vars);
+        assert_eq!(resolve("out/{repo_name}"), PathBuf::from("out/myrepo"));
+        assert_eq!(resolve("out/{date}"), PathBuf::from("out/2025-01-15"));
+        assert_eq!(resolve("out/{git_commit}"), PathBuf::from("out/1a2b3c4d"));
+        assert_eq!(resolve("out/{mode}"), PathBuf::from("out/rag"));
+        assert_eq!(
+            resolve("exports/{date}/{repo_name}-{mode}"),
+            PathBuf::from("exports/2025-01-15/myrepo-rag")
+        );
+    }
+
+    #[test]
+    fn output_dir_template_keeps_unknown_variables_literal() {
+        assert_eq!(
+            resolve_output_dir_with_template("out/{unknown}/{repo_name}", &vars()),
+            PathBuf::from("out/{unknown}/myrepo")
+        );
+    }
+
+    #[test]
+    fn plain_output_dir_gets_repo_subdirectory() {
+        let config = Config { output_dir: PathBuf::from("out"), ..Config::default() };
+        let dir = resolve_output_dir(&config, Path::new("."), "myrepo", Some("main"));
+        assert_eq!(dir, PathBuf::from("out/myrepo/focus_main"));
+
+        let config = Config { output_dir: PathBuf::from("out/{repo_name}"), ..Config::default() };
+        let dir = resolve_output_dir(&config, Path::new("."), "myrepo", None);
+        assert_eq!(dir, PathBuf::from("out/myrepo"));
+    }
+
+    #[test]
+    fn provenance_fingerprint_ignores_remote_clone_path() {
+        let config = json!({"mode": "rag"});
+        let remote =
+            |path: &str| json!({"path": path, "repo": "https://github.com/o/r", "ref": null});
+        let first = provenance_fingerprint(&remote("/tmp/repo-context-1"), &config, Some("abc"));
+        let second = provenance_fingerprint(&remote("/tmp/repo-context-2"), &config, Some("abc"));
+        assert_eq!(first, second);
+        assert_ne!(first, provenance_fingerprint(&remote("/tmp/x"), &config, Some("def")));
+
+        let local = |path: &str| json!({"path": path, "repo": null, "ref": null});
+        assert_ne!(
+            provenance_fingerprint(&local("/src/a"), &config, None),
+            provenance_fingerprint(&local("/src/b"), &config, None)
+        );
+    }
+}
@@ -2,4 +2,7 @@
 
 //! Application-level use cases.
 
+pub mod archive;
 pub mod export;
+pub mod progress;
+pub mod snapshot;
@@ -0,0 +1,78 @@
+//! Terminal progress indicators for long-running phases.
+//!
+//! Bars draw to stderr and are disabled entirely when stdout is not a
+//! terminal, so piped and CI output stays clean.
+
+use indicatif::{ProgressBar, ProgressStyle};
+use std::io::IsTerminal;
+use std::time::Duration;
+
+const FILES_TEMPLATE: &str = "{spinner} [{pos}/{len} files {msg}] {wide_bar} ETA {eta}";
+
+/// A progress bar that is a no-op when disabled.
+pub struct Progress {
+    bar: Option<ProgressBar>,
+}
+
+impl Progress {
+    /// Whether progress should be drawn for this process.
+    pub fn enabled() -> bool {
+        std::io::stdout().is_terminal()
+    }
+
+    /// An indeterminate spinner for phases without a known total.
+    pub fn spinner(message: &str, enabled: bool) -> Self {
+        let bar = enabled.then(|| {
+            let bar = ProgressBar::new_spinner();
+            bar.set_message(message.to_string());
+            bar.enable_steady_tick(Duration::from_millis(100));
+            bar
+        });
+        Self { bar }
+    }
+
+    /// A bar counting `total` files, labelled `[N/M files <verb>]`.
+    pub fn files(total: usize, verb: &str, enabled: bool) -> Self {
+        let bar = enabled.then(|| {
+            let bar = ProgressBar::new(total as u64);
+            if let Ok(style) = ProgressStyle::with_template(FILES_TEMPLATE) {
+                bar.set_style(style);
+            }
+            bar.set_message(verb.to_string());
+            bar
+        });
+        Self { bar }
+    }
+
+    /// Advance by one unit.
+    pub fn inc(&self) {
+        if let Some(bar) = &self.bar {
+            bar.inc(1);
+        }
+    }
+
+    /// Remove the bar from the terminal.
+    pub fn finish(self) {
+        if let Some(bar) = self.bar {
+            bar.finish_and_clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_file_progress_does_not_panic() {
+        for enabled in [true, false] {
+            let files = Progress::files(0, "processed", enabled);
+            files.inc();
+            files.finish();
+
+            let spinner = Progress::spinner("Scanning", enabled);
+            spinner.inc();
+            spinner.finish();
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! In-memory repository snapshot for interactive use (e.g. `serve`).
+//!
+//! Runs the scan → rank → redact → chunk part of the export pipeline once and
+//! keeps the results around so they can be searched and read repeatedly.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+use super::export::{build_redactor, process_file, ENV_FILE_PLACEHOLDER};
+use crate::domain::{Chunk, Config, FileInfo};
+use crate::rank::bm25::{expand_query_terms, score_query_against_chunks, tokenize};
+use crate::rank::rank_files_with_weights;
+use crate::redact::Redactor;
+use crate::scan::scanner::FileScanner;
+use crate::utils::read_file_safe;
+
+/// Ranked files and redacted chunks for one local repository.
+pub struct RepoSnapshot {
+    pub root_path: PathBuf,
+    /// Files in ranked order (highest priority first).
+    pub files: Vec<FileInfo>,
+    /// Chunks for every ranked file, in file rank order.
+    pub chunks: Vec<Chunk>,
+    /// Add prefix completions of query terms when a search finds few hits.
+    pub query_expansion: bool,
+    redactor: Option<Redactor>,
+}
+
+/// Prefix completions added per query term by query expansion.
+const QUERY_EXPANSION_TERMS: usize = 3;
+
+/// A chunk matched by [`RepoSnapshot::search`].
+#[derive(Debug, Clone)]
+pub struct SearchHit<'a> {
+    pub chunk: &'a Chunk,
+    pub score: f64,
+}
+
+impl RepoSnapshot {
+    /// Scan, rank, and chunk the local repository at `config.path`.
+    pub fn build(config: &Config) -> Result<Self> {
+        let root = config.path.as_deref().context("Snapshot requires a local path")?;
+        let root_path = root
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve path: {}", root.display()))?;
+
+        let mut scanner = FileScanner::from_config(root_path.clone(), config);
+        let scanned = scanner.scan()?;
+        let files = rank_files_with_weights(&root_path, scanned, config.ranking_weights.clone())?;
+
+        let redactor = if config.redact_secrets {
+            Some(build_redactor(config.redaction_mode, &config.redaction))
+        } else {
+            None
+        };
+
+        let mut chunks = Vec::new();
+        for file in &files {
+            match process_file(file, redactor.as_ref(), config, None) {
+                Ok(processed) => chunks.extend(processed.chunks),
+                Err(e) => tracing::warn!("Skipping {}: {:#}", file.relative_path, e),
+            }
+        }
+
+        Ok(Self { root_path, files, chunks, query_expansion: true, redactor })
+    }
+
+    /// BM25 search over all chunks, best match first.
+    ///
+    /// Chunks that share no terms with the query are omitted; ties are broken
+    /// by chunk priority, then path and start line for determinism. With
+    /// `query_expansion` on, a query that finds fewer than `limit / 2` chunks
+    /// is retried with corpus terms that extend its words (`auth` →
+    /// `authentication`).
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit<'_>> {
+        let hits = self.search_terms(query, limit);
+        if !self.query_expansion || hits.len() >= limit / 2 {
+            return hits;
+        }
+        let expansions = expand_query_terms(&tokenize(query), &self.chunks, QUERY_EXPANSION_TERMS);
+        if expansions.is_empty() {
+            return hits;
+        }
+        tracing::debug!("Expanding query {query:?} with {}", expansions.join(", "));
+        self.search_terms(&format!("{query} {}", expansions.join(" ")), limit)
+    }
+
+    fn search_terms(&self, query: &str, limit: usize) -> Vec<SearchHit<'_>> {
+        let scores = score_query_against_chunks(&self.chunks, query);
+        let mut hits: Vec<SearchHit<'_>> = self
+            .chunks
+            .iter()
+            .zip(scores)
+            .filter(|(_, score)| *score > 0.0)
+            .map(|(chunk, score)| SearchHit { chunk, score })
+            .collect();
+        hits.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| {
+                    b.chunk
+                        .priority
+                        .partial_cmp(&a.chunk.priority)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .then_with(|| a.chunk.path.cmp(&b.chunk.path))
+                .then_with(|| a.chunk.start_line.cmp(&b.chunk.start_line))
+        });
+        hits.truncate(limit);
+        hits
+    }
+
+    /// Look up a scanned file by its repository-relative path.
+    pub fn file(&self, relative_path: &str) -> Option<&FileInfo> {
+        let normalized = relative_path.trim_start_matches("./").replace('\\', "/");
+        self.files.iter().find(|f| f.relative_path == normalized)
+    }
+
+    /// Read a scanned file's content, redacted with the snapshot's redactor.
+    ///
+    /// Only files that were part of the scan can be read, so paths outside the
+    /// repository (or excluded by config) are rejected.
+    pub fn read_file(&self, relative_path: &str) -> Result<String> {
+        let file = self
+            .file(relative_path)
+            .with_context(|| format!("File not found in snapshot: {relative_path}"))?;
+        if file.is_env_file {
+            // Same as exports: never hand out dotenv values, redaction or not.
+            return Ok(ENV_FILE_PLACEHOLDER.to_string());
+        }
+        let (content, _) = read_file_safe(&file.path, None, None)
+            .with_context(|| format!("Failed to read {}", file.relative_path))?;
+
+        let Some(redactor) = &self.redactor else {
+            return Ok(content);
+        };
+        let file_name =
+            Path::new(&file.relative_path).file_name().and_then(|name| name.to_str()).unwrap_or("");
+        if redactor.is_file_allowlisted(file_name, &file.relative_path) {
+            return Ok(content);
+        }
+        Ok(redactor
+            .redact_with_language_report(
+                &content,
+                &file.language,
+                &file.extension,
+                file_name,
+                &file.relative_path,
+            )
+            .content)
+    }
+}
@@ -1,9 +1,12 @@
 //! Code-aware chunking using tree-sitter for structure-aware boundaries.
 //! Falls back to line-based chunking for unsupported languages.
 
+use crate::analysis::extract_python_docstrings;
 use crate::chunk::line_chunker::chunk_lines;
+use crate::chunk::ChunkOptions;
 use crate::domain::{Chunk, FileInfo};
 use crate::utils::{estimate_tokens, stable_hash};
+use anyhow::{bail, Result};
 use std::collections::{BTreeSet, HashMap};
 use tree_sitter::{Language, Parser};
 
@@ -23,7 +26,32 @@ pub fn chunk_code(
     max_tokens: usize,
     overlap_tokens: usize,
 ) -> Vec<Chunk> {
-    if let Some(chunks) = chunk_with_tree_sitter(file_info, content, max_tokens, overlap_tokens) {
+    chunk_code_with_options(file_info, content, &ChunkOptions::new(max_tokens, overlap_tokens))
+}
+
+/// [`chunk_code`] honouring `options.custom_kinds`, per-language tree-sitter
+/// node kinds that replace the built-in definition kinds
+/// (`custom_tree_sitter_queries` in the config).
+///
+/// With `options.transforms.extract_docs`, a Rust item's chunk starts at its
+/// `///` docs and attributes, so [`chunk_content`](super::chunk_content) can
+/// move the docs into their own chunk; otherwise they end the previous chunk.
+pub fn chunk_code_with_options(
+    file_info: &FileInfo,
+    content: &str,
+    options: &ChunkOptions,
+) -> Vec<Chunk> {
+    let ChunkOptions { max_tokens, overlap_tokens, .. } = *options;
+    let custom = options.custom_kinds.get(&file_info.language).map(Vec::as_slice);
+    let docs_lead_items = options.transforms.extract_docs;
+    if let Some(chunks) = chunk_with_tree_sitter(
+        file_info,
+        content,
+        max_tokens,
+        overlap_tokens,
+        custom,
+        docs_lead_items,
+    ) {
         if !chunks.is_empty() {
             return chunks;
         }
@@ -106,15 +134,107 @@ fn chunk_with_tree_sitter(
     content: &str,
     max_tokens: usize,
     overlap_tokens: usize,
+    custom_kinds: Option<&[String]>,
+    docs_lead_items: bool,
 ) -> Option<Vec<Chunk>> {
-    let (language, definition_kinds): (Language, &[&str]) = match file_info.language.as_str() {
+    let (language, builtin_kinds) = tree_sitter_grammar(&file_info.language)?;
+    let definition_kinds: Vec<&str> = match custom_kinds {
+        Some(kinds) => kinds.iter().map(String::as_str).collect(),
+        None => builtin_kinds.to_vec(),
+    };
+
+    let mut parser = Parser::new();
+    parser.set_language(&language).ok()?;
+
+    let tree = parser.parse(content, None)?;
+    let root = tree.root_node();
+
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    if lines.is_empty() {
+        return Some(Vec::new());
+    }
+
+    let mut boundaries = vec![0usize];
+    let mut symbol_tags: SymbolTagsByBoundary = HashMap::new();
+    // Start row of the doc comments / attributes directly above the next item,
+    // so a Rust item's chunk begins with its `///` docs rather than the
+    // previous item's chunk ending with them.
+    let mut leading_start: Option<usize> = None;
+    let mut prev_end_row = 0usize;
+    for i in 0..root.named_child_count() {
+        if let Some(child) = root.named_child(i) {
+            // JS/TS `export ...` statements wrap the declaration they export.
+            let definition = child
+                .child_by_field_name("declaration")
+                .filter(|_| child.kind() == "export_statement")
+                .unwrap_or(child);
+            let kind = definition.kind();
+            let row = child.start_position().row;
+            let adjacent = row <= prev_end_row + 1;
+            prev_end_row = child.end_position().row;
+            if docs_lead_items
+                && file_info.language == "rust"
+                && is_rust_item_prelude(content, child)
+            {
+                leading_start = leading_start.filter(|_| adjacent).or(Some(row));
+                continue;
+            }
+            let boundary = leading_start.take().filter(|_| adjacent).unwrap_or(row);
+            let anonymous_default = is_anonymous_default_export(child);
+            if definition_kinds.contains(&kind) || anonymous_default {
+                if boundary > 0 {
+                    boundaries.push(boundary);
+                }
+                let mut tags = extract_symbol_tags_from_tree_node(
+                    content,
+                    file_info.language.as_str(),
+                    definition,
+                );
+                if anonymous_default {
+                    tags.insert("def:default".to_string());
+                }
+                if !tags.is_empty() {
+                    symbol_tags.entry(boundary).or_default().extend(tags);
+                }
+            }
+        }
+    }
+    boundaries.push(lines.len());
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    if boundaries.len() <= 2 {
+        return Some(Vec::new());
+    }
+
+    Some(chunk_by_boundaries(
+        file_info,
+        &lines,
+        &boundaries,
+        &symbol_tags,
+        max_tokens,
+        overlap_tokens,
+    ))
+}
+
+/// The bundled grammar for `language` and its built-in definition node kinds.
+fn tree_sitter_grammar(language: &str) -> Option<(Language, &'static [&'static str])> {
+    let grammar: (Language, &'static [&'static str]) = match language {
         "python" => (
             tree_sitter_python::LANGUAGE.into(),
             &["function_definition", "class_definition", "decorated_definition"],
         ),
         "rust" => (
             tree_sitter_rust::LANGUAGE.into(),
-            &["function_item", "impl_item", "struct_item", "enum_item", "trait_item", "mod_item"],
+            &[
+                "function_item",
+                "impl_item",
+                "struct_item",
+                "enum_item",
+                "trait_item",
+                "mod_item",
+                "macro_definition",
+            ],
         ),
         "javascript" => (
             tree_sitter_javascript::LANGUAGE.into(),
@@ -148,52 +268,56 @@ fn chunk_with_tree_sitter(
         ),
         _ => return None,
     };
+    Some(grammar)
+}
 
-    let mut parser = Parser::new();
-    parser.set_language(&language).ok()?;
-
-    let tree = parser.parse(content, None)?;
-    let root = tree.root_node();
-
-    let lines: Vec<&str> = content.split_inclusive('\n').collect();
-    if lines.is_empty() {
-        return Some(Vec::new());
-    }
-
-    let mut boundaries = vec![0usize];
-    let mut symbol_tags: SymbolTagsByBoundary = HashMap::new();
-    for i in 0..root.named_child_count() {
-        if let Some(child) = root.named_child(i) {
-            let kind = child.kind();
-            if definition_kinds.contains(&kind) {
-                let row = child.start_position().row;
-                if row > 0 {
-                    boundaries.push(row);
-                }
-                let tags =
-                    extract_symbol_tags_from_tree_node(content, file_info.language.as_str(), child);
-                if !tags.is_empty() {
-                    symbol_tags.entry(row).or_default().extend(tags);
-                }
+/// Checks that every language in `custom_tree_sitter_queries` has a bundled
+/// grammar and that each node kind is a named node of that grammar.
+///
+/// # Errors
+/// Returns an error naming the first unknown language or node kind
+pub fn validate_custom_definition_kinds(custom_kinds: &HashMap<String, Vec<String>>) -> Result<()> {
+    let mut languages: Vec<&String> = custom_kinds.keys().collect();
+    languages.sort();
+    for language in languages {
+        let Some((grammar, _)) = tree_sitter_grammar(language) else {
+            bail!(
+                "custom_tree_sitter_queries: no tree-sitter grammar for '{}' (supported: {})",
+                language,
+                supported_tree_sitter_languages().join(", ")
+            );
+        };
+        for kind in &custom_kinds[language] {
+            if grammar.id_for_node_kind(kind, true) == 0 {
+                bail!(
+                    "custom_tree_sitter_queries: '{}' is not a node kind of the {} grammar",
+                    kind,
+                    language
+                );
             }
         }
     }
-    boundaries.push(lines.len());
-    boundaries.sort_unstable();
-    boundaries.dedup();
+    Ok(())
+}
 
-    if boundaries.len() <= 2 {
-        return Some(Vec::new());
-    }
+/// `export default function () {}`, `export default class {}`, and
+/// `export default () => ...`, which have no declaration name.
+fn is_anonymous_default_export(node: tree_sitter::Node<'_>) -> bool {
+    node.kind() == "export_statement"
+        && node.child_by_field_name("value").is_some_and(|value| {
+            matches!(value.kind(), "function_expression" | "function" | "arrow_function" | "class")
+        })
+}
 
-    Some(chunk_by_boundaries(
-        file_info,
-        &lines,
-        &boundaries,
-        &symbol_tags,
-        max_tokens,
-        overlap_tokens,
-    ))
+/// Outer doc comments and attributes, which belong to the item below them.
+fn is_rust_item_prelude(content: &str, node: tree_sitter::Node<'_>) -> bool {
+    let text = || node.utf8_text(content.as_bytes()).unwrap_or("");
+    match node.kind() {
+        "attribute_item" => true,
+        "line_comment" => text().starts_with("///") && !text().starts_with("////"),
+        "block_comment" => text().starts_with("/**") && !text().starts_with("/***"),
+        _ => false,
+    }
 }
 
 fn chunk_by_boundaries(
@@ -283,6 +407,7 @@ fn find_definition_boundaries(lines: &[&str], language: &str) -> Vec<usize> {
                     || trimmed.starts_with("struct ")
                     || trimmed.starts_with("enum ")
                     || trimmed.starts_with("trait ")
+                    || trimmed.starts_with("macro_rules! ")
             }
             "javascript" | "typescript" => {
                 trimmed.starts_with("function ")
@@ -308,7 +433,15 @@ fn find_definition_boundaries(lines: &[&str], language: &str) -> Vec<usize> {
         };
 
         if is_boundary {
-            boundaries.push(idx);
+            // A Python definition's chunk starts at its decorators, so kind
+            // tags such as `prop:` see them.
+            let mut start = idx;
+            if language == "python" {
+                while start > 0 && lines[start - 1].trim_start().starts_with('@') {
+                    start -= 1;
+                }
+            }
+            boundaries.push(start);
         }
     }
 
@@ -343,6 +476,9 @@ fn extract_symbol_tags_from_tree_node(
 ) -> BTreeSet<String> {
     let mut tags = BTreeSet::new();
     let kind = node.kind();
+    if language == "go" {
+        tags.extend(go_method_set_tags(content, node));
+    }
 
     let prefix = match (language, kind) {
         (
@@ -365,6 +501,7 @@ fn extract_symbol_tags_from_tree_node(
             | "type_declaration",
         ) => Some("type"),
         ("rust", "impl_item") => Some("impl"),
+        ("rust", "macro_definition") => Some("def"),
         _ => None,
     };
 
@@ -381,6 +518,63 @@ fn extract_symbol_tags_from_tree_node(
     tags
 }
 
+/// `method:<Receiver>.<Name>` for a Go method, and `interface:<Name>` plus
+/// `method:<Interface>.<Name>` per method for each Go interface type.
+fn go_method_set_tags(content: &str, node: tree_sitter::Node<'_>) -> BTreeSet<String> {
+    let mut tags = BTreeSet::new();
+    match node.kind() {
+        "method_declaration" => {
+            let receiver = node
+                .child_by_field_name("receiver")
+                .and_then(|list| list.named_child(0))
+                .and_then(|param| param.child_by_field_name("type"))
+                .and_then(|ty| go_base_type_name(content, ty));
+            let name = node.child_by_field_name("name").and_then(|n| node_text(content, n));
+            if let (Some(receiver), Some(name)) = (receiver, name) {
+                tags.insert(format!("method:{receiver}.{name}"));
+            }
+        }
+        "type_declaration" => {
+            for i in 0..node.named_child_count() {
+                let Some(spec) = node.named_child(i) else { continue };
+                let name = spec.child_by_field_name("name").and_then(|n| node_text(content, n));
+                let (Some(name), Some(ty)) = (name, spec.child_by_field_name("type")) else {
+                    continue;
+                };
+                if ty.kind() != "interface_type" {
+                    continue;
+                }
+                tags.insert(format!("interface:{name}"));
+                for j in 0..ty.named_child_count() {
+                    let method = ty
+                        .named_child(j)
+                        .filter(|elem| elem.kind() == "method_elem")
+                        .and_then(|elem| elem.child_by_field_name("name"))
+                        .and_then(|n| node_text(content, n));
+                    if let Some(method) = method {
+                        tags.insert(format!("method:{name}.{method}"));
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+    tags
+}
+
+/// The named type under Go pointer and generic wrappers (`*Server[T]` → `Server`).
+fn go_base_type_name<'a>(content: &'a str, ty: tree_sitter::Node<'_>) -> Option<&'a str> {
+    match ty.kind() {
+        "pointer_type" => go_base_type_name(content, ty.named_child(0)?),
+        "generic_type" => go_base_type_name(content, ty.child_by_field_name("type")?),
+        _ => node_text(content, ty),
+    }
+}
+
+fn node_text<'a>(content: &'a str, node: tree_sitter::Node<'_>) -> Option<&'a str> {
+    node.utf8_text(content.as_bytes()).ok()
+}
+
 fn extract_node_name(content: &str, node: tree_sitter::Node<'_>) -> Option<String> {
     if let Some(name_node) = node.child_by_field_name("name") {
         if let Ok(text) = name_node.utf8_text(content.as_bytes()) {
@@ -408,11 +602,17 @@ fn extract_node_name(content: &str, node: tree_sitter::Node<'_>) -> Option<Strin
 
 fn extract_symbol_tags_from_section(language: &str, section: &str) -> BTreeSet<String> {
     let mut tags = BTreeSet::new();
-    let Some(first_code_line) = section
-        .lines()
-        .map(str::trim)
-        .find(|line| !line.is_empty() && !line.starts_with('@') && !line.starts_with("//"))
-    else {
+    match language {
+        "rust" => tags.extend(extract_rust_macro_tags(section)),
+        "python" => tags.extend(extract_python_member_tags(section)),
+        _ => {}
+    }
+    let Some(first_code_line) = section.lines().map(str::trim).find(|line| {
+        !(line.is_empty()
+            || line.starts_with('@')
+            || line.starts_with("//")
+            || (language == "rust" && line.starts_with("#[")))
+    }) else {
         return tags;
     };
 
@@ -420,7 +620,78 @@ fn extract_symbol_tags_from_section(language: &str, section: &str) -> BTreeSet<S
     tags
 }
 
-fn extract_symbol_tags_from_line(language: &str, line: &str) -> BTreeSet<String> {
+/// Tags for Python definitions whose kind matters beyond `def:`/`type:`:
+/// `class:<Name>` for classes, `prop:`, `classmethod:`, and `staticmethod:`
+/// for methods under those decorators, and `async:<name>` for `async def`.
+fn extract_python_member_tags(section: &str) -> BTreeSet<String> {
+    let mut tags = BTreeSet::new();
+    let mut decorators: Vec<&str> = Vec::new();
+    for line in section.lines().map(str::trim) {
+        if let Some(decorator) = line.strip_prefix('@') {
+            decorators.push(decorator.split(['(', ' ']).next().unwrap_or(decorator));
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix("class ") {
+            if let Some(name) = clean_symbol_name(rest) {
+                tags.insert(format!("class:{name}"));
+            }
+        } else if let Some(rest) = line.strip_prefix("async def ") {
+            if let Some(name) = clean_symbol_name(rest) {
+                tags.insert(format!("async:{name}"));
+                tags.extend(python_decorator_tags(&decorators, &name));
+            }
+        } else if let Some(rest) = line.strip_prefix("def ") {
+            if let Some(name) = clean_symbol_name(rest) {
+                tags.extend(python_decorator_tags(&decorators, &name));
+            }
+        } else if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        decorators.clear();
+    }
+    tags
+}
+
+fn python_decorator_tags(decorators: &[&str], name: &str) -> Vec<String> {
+    decorators
+        .iter()
+        .filter_map(|decorator| match *decorator {
+            "property" | "functools.cached_property" | "cached_property" => Some("prop"),
+            "classmethod" => Some("classmethod"),
+            "staticmethod" => Some("staticmethod"),
+            _ => None,
+        })
+        .map(|kind| format!("{kind}:{name}"))
+        .collect()
+}
+
+/// `def:` tags for every macro a Rust section defines: `macro_rules! name`,
+/// the derive named by `#[proc_macro_derive(Name)]`, and the function under
+/// `#[proc_macro]` / `#[proc_macro_attribute]`.
+fn extract_rust_macro_tags(section: &str) -> BTreeSet<String> {
+    let mut tags = BTreeSet::new();
+    let mut pending_proc_macro = false;
+    for line in section.lines().map(str::trim) {
+        if let Some(rest) = line.strip_prefix("macro_rules!") {
+            if let Some(name) = clean_symbol_name(rest.trim_start()) {
+                tags.insert(format!("def:{name}"));
+            }
+        } else if let Some(rest) = line.strip_prefix("#[proc_macro_derive(") {
+            if let Some(name) = clean_symbol_name(rest.trim_start()) {
+                tags.insert(format!("def:{name}"));
+            }
+        } else if line.starts_with("#[proc_macro]") || line.starts_with("#[proc_macro_attribute]") {
+            pending_proc_macro = true;
+        } else if pending_proc_macro && !line.is_empty() && !line.starts_with("#[") {
+            let tag = extract_symbol_tags_from_line("rust", strip_rust_qualifiers(line));
+            tags.extend(tag.into_iter().filter(|t| t.starts_with("def:")));
+            pending_proc_macro = false;
+        }
+    }
+    tags
+}
+
+pub(crate) fn extract_symbol_tags_from_line(language: &str, line: &str) -> BTreeSet<String> {
     let mut tags = BTreeSet::new();
     let trimmed = line.trim();
 
@@ -460,6 +731,149 @@ fn extract_symbol_tags_from_line(language: &str, line: &str) -> BTreeSet<String>
     tags
 }
 
+/// Extract the doc comment of each definition as its own Markdown chunk.
+///
+/// Covers Rust `///` comments above an item and Python docstrings opening a
+/// `def`/`class` body. Each chunk holds the comment text with its markers
+/// removed, spans the comment's lines, is tagged `doc:<symbol>`, and gets the
+/// file's priority plus 0.1 so documentation outranks its implementation.
+/// Python docstrings come from [`extract_python_docstrings`], and each
+/// documented parameter adds a `param:<name>` tag. Other languages yield no
+/// doc chunks.
+pub fn extract_doc_chunks(file_info: &FileInfo, content: &str) -> Vec<Chunk> {
+    match file_info.language.as_str() {
+        "rust" => {
+            let lines: Vec<&str> = content.lines().collect();
+            (0..lines.len())
+                .filter_map(|idx| {
+                    let (start, end, text) = rust_doc_above(&lines, idx)?;
+                    let symbol = extract_symbol_tags_from_line(
+                        "rust",
+                        strip_rust_qualifiers(lines[idx].trim()),
+                    )
+                    .into_iter()
+                    .find_map(|tag| tag.split_once(':').map(|(_, name)| name.to_string()))?;
+                    Some(doc_chunk(file_info, &symbol, start, end, text))
+                })
+                .collect()
+        }
+        "python" => extract_python_docstrings(content)
+            .into_iter()
+            .map(|entry| {
+                let text = format!("{}\n", entry.docstring);
+                let mut chunk = doc_chunk(
+                    file_info,
+                    &entry.symbol,
+                    entry.start_line - 1,
+                    entry.end_line - 1,
+                    text,
+                );
+                chunk.tags.extend(entry.params.iter().map(|(name, _)| format!("param:{name}")));
+                chunk
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// A Markdown doc chunk for `symbol` spanning 0-based lines `start..=end`.
+fn doc_chunk(file_info: &FileInfo, symbol: &str, start: usize, end: usize, text: String) -> Chunk {
+    let mut tags = file_info.tags.clone();
+    tags.insert(format!("doc:{symbol}"));
+    Chunk {
+        id: stable_hash(&text, &file_info.relative_path, start + 1, end + 1),
+        path: file_info.relative_path.clone(),
+        language: "markdown".to_string(),
+        start_line: start + 1,
+        end_line: end + 1,
+        token_estimate: estimate_tokens(&text),
+        content: text,
+        priority: (file_info.priority + 0.1).min(1.0),
+        tags,
+        file_id: String::new(),
+        chunk_index: 0,
+        chunks_in_file: 0,
+        byte_start: None,
+        byte_end: None,
+        content_sha256: String::new(),
+        file_sha256: String::new(),
+    }
+}
+
+fn strip_rust_qualifiers(mut line: &str) -> &str {
+    if let Some(rest) = line.strip_prefix("pub(") {
+        line = rest.split_once(')').map_or(rest, |(_, after)| after.trim_start());
+    }
+    for qualifier in ["pub ", "const ", "async ", "unsafe ", "extern \"C\" "] {
+        line = line.strip_prefix(qualifier).unwrap_or(line);
+    }
+    line
+}
+
+/// `///` lines directly above line `idx` (attributes in between are skipped),
+/// as 0-based `(first, last, text)`.
+fn rust_doc_above(lines: &[&str], idx: usize) -> Option<(usize, usize, String)> {
+    let mut end = idx;
+    while end > 0 && lines[end - 1].trim_start().starts_with("#[") {
+        end -= 1;
+    }
+    let is_doc = |line: &str| {
+        let trimmed = line.trim_start();
+        trimmed.starts_with("///") && !trimmed.starts_with("////")
+    };
+    let mut start = end;
+    while start > 0 && is_doc(lines[start - 1]) {
+        start -= 1;
+    }
+    if start == end {
+        return None;
+    }
+    let text: String = lines[start..end]
+        .iter()
+        .map(|line| {
+            let body = &line.trim_start()[3..];
+            format!("{}\n", body.strip_prefix(' ').unwrap_or(body))
+        })
+        .collect();
+    Some((start, end - 1, text))
+}
+
+/// The docstring opening the body of the `def`/`class` on line `idx`, as
+/// 0-based `(first, last, text)`.
+pub(crate) fn python_docstring_below(lines: &[&str], idx: usize) -> Option<(usize, usize, String)> {
+    let trimmed = lines[idx].trim_start();
+    if !["def ", "async def ", "class "].iter().any(|p| trimmed.starts_with(p)) {
+        return None;
+    }
+    // The signature may wrap; the body starts after the line ending in ':'.
+    let header_end = (idx..lines.len().min(idx + 20))
+        .find(|&i| lines[i].split('#').next().unwrap_or("").trim_end().ends_with(':'))?;
+    let first = (header_end + 1..lines.len()).find(|&i| !lines[i].trim().is_empty())?;
+    let opening = lines[first].trim_start();
+    let quote = ["\"\"\"", "'''"].into_iter().find(|q| opening.starts_with(q))?;
+    let indent = &lines[first][..lines[first].len() - opening.len()];
+
+    let after_open = &opening[3..];
+    let (last, body) = if let Some(close) = after_open.find(quote) {
+        (first, after_open[..close].to_string())
+    } else {
+        let last = (first + 1..lines.len()).find(|&i| lines[i].contains(quote))?;
+        let mut body = vec![after_open];
+        for line in &lines[first + 1..last] {
+            body.push(line.strip_prefix(indent).unwrap_or(line.trim_start()));
+        }
+        let closing = lines[last];
+        let closing = closing.strip_prefix(indent).unwrap_or(closing.trim_start());
+        body.push(&closing[..closing.find(quote).unwrap_or(closing.len())]);
+        (last, body.join("\n"))
+    };
+    let text = body.trim();
+    if text.is_empty() {
+        return None;
+    }
+    Some((first, last, format!("{text}\n")))
+}
+
 fn clean_symbol_name(raw: &str) -> Option<String> {
     let candidate: String =
         raw.chars().take_while(|c| c.is_alphanumeric() || matches!(c, '_' | ':' | '.')).collect();
@@ -473,9 +887,12 @@ fn clean_symbol_name(raw: &str) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::chunk_code;
+    use super::{
+        chunk_code, chunk_code_with_options, extract_doc_chunks, validate_custom_definition_kinds,
+    };
+    use crate::chunk::ChunkOptions;
     use crate::domain::FileInfo;
-    use std::collections::BTreeSet;
+    use std::collections::{BTreeSet, HashMap};
     use std::path::PathBuf;
 
     #[test]
@@ -493,6 +910,11 @@ mod tests {
             is_readme: false,
             is_config: false,
             is_doc: false,
+            is_env_file: false,
+            is_symlink: false,
+            symlink_target: None,
+            complexity_estimate: 0,
+            priority_explanation: None,
         };
 
         let content = "def a():\n    pass\n\ndef b():\n    pass\n\ndef c():\n    pass\n";
@@ -518,6 +940,11 @@ mod tests {
             is_readme: false,
             is_config: false,
             is_doc: false,
+            is_env_file: false,
+            is_symlink: false,
+            symlink_target: None,
+            complexity_estimate: 0,
+            priority_explanation: None,
         };
 
         let content = "struct S;\nfn a() {}\nimpl S { fn b(&self) {} }\nfn c() {}\n";
@@ -543,6 +970,11 @@ mod tests {
             is_readme: false,
             is_config: false,
             is_doc: false,
+            is_env_file: false,
+            is_symlink: false,
+            symlink_target: None,
+            complexity_estimate: 0,
+            priority_explanation: None,
         };
 
         let content = "package main\n\nfunc a() {}\n\nfunc b() {}\n\nfunc main() {}\n";
@@ -551,4 +983,180 @@ mod tests {
         assert!(chunks.len() >= 2);
         assert!(chunks.iter().any(|c| c.tags.contains("def:a")));
     }
+
+    fn doc_test_info(relative_path: &str, language: &str) -> FileInfo {
+        FileInfo {
+            path: PathBuf::from(format!("/tmp/{relative_path}")),
+            relative_path: relative_path.to_string(),
+            size_bytes: 0,
+            extension: String::new(),
+            language: language.to_string(),
+            id: "x".to_string(),
+            priority: 0.5,
+            token_estimate: 0,
+            tags: BTreeSet::new(),
+            is_readme: false,
+            is_config: false,
+            is_doc: false,
+            is_env_file: false,
+            is_symlink: false,
+            symlink_target: None,
+            complexity_estimate: 0,
+            priority_explanation: None,
+        }
+    }
+
+    #[test]
+    fn extracts_rust_doc_comments_above_items() {
+        let info = doc_test_info("lib.rs", "rust");
+        let content = "/// Adds two numbers.\n/// Wrapping on overflow.\n#[inline]\npub fn add() {}\n\n// plain comment\nfn helper() {}\n\n/// A point.\npub(crate) struct Point;\n";
+
+        let docs = extract_doc_chunks(&info, content);
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[0].content, "Adds two numbers.\nWrapping on overflow.\n");
+        assert_eq!((docs[0].start_line, docs[0].end_line), (1, 2));
+        assert!(docs[0].tags.contains("doc:add"));
+        assert_eq!(docs[0].language, "markdown");
+        assert!((docs[0].priority - 0.6).abs() < 1e-9);
+        assert_eq!((docs[1].start_line, docs[1].end_line), (9, 9));
+        assert!(docs[1].tags.contains("doc:Point"));
+    }
+
+    #[test]
+    fn rust_item_chunks_start_at_their_doc_comments_when_extracting_docs() {
+        let info = doc_test_info("lib.rs", "rust");
+        let content = "fn first() {}\n\n/// Second.\n#[inline]\nfn second() {}\n";
+        let second_start = |chunks: Vec<crate::domain::Chunk>| {
+            chunks.iter().find(|c| c.tags.contains("def:second")).map(|c| c.start_line)
+        };
+
+        assert_eq!(second_start(chunk_code(&info, content, 200, 0)), Some(5));
+        let mut options = ChunkOptions::new(200, 0);
+        options.transforms.extract_docs = true;
+        let chunks = chunk_code_with_options(&info, content, &options);
+        assert!(chunks[1].content.starts_with("/// Second."));
+        assert_eq!(second_start(chunks), Some(3));
+    }
+
+    #[test]
+    fn extracts_python_docstrings() {
+        let info = doc_test_info("mod.py", "python");
+        let content = "def one():\n    \"\"\"Single line.\"\"\"\n    return 1\n\nclass Two:\n    \"\"\"\n    Spans\n      lines.\n    \"\"\"\n\ndef three():\n    return 3\n";
+
+        let docs = extract_doc_chunks(&info, content);
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[0].content, "Single line.\n");
+        assert!(docs[0].tags.contains("doc:one"));
+        assert_eq!((docs[1].start_line, docs[1].end_line), (6, 9));
+        assert_eq!(docs[1].content, "Spans\n  lines.\n");
+        assert!(docs[1].tags.contains("doc:Two"));
+    }
+
+    #[test]
+    fn rust_macros_are_tagged_as_definitions() {
+        let info = doc_test_info("macros.rs", "rust");
+        let content = "#[macro_export]\nmacro_rules! my_vec {\n    ($($x:expr),*) => { vec![$($x),*] };\n}\n\n#[proc_macro_derive(Builder, attributes(builder))]\npub fn derive_builder(input: TokenStream) -> TokenStream {\n    input\n}\n\n#[proc_macro_attribute]\npub fn traced(attr: TokenStream, item: TokenStream) -> TokenStream {\n    item\n}\n";
+
+        let chunks = chunk_code(&info, content, 200, 0);
+        let macro_chunk = chunks.iter().find(|c| c.content.contains("macro_rules!")).unwrap();
+        assert!(macro_chunk.tags.contains("def:my_vec"));
+        assert!(chunks.iter().any(|c| c.tags.contains("def:Builder")));
+        assert!(chunks.iter().any(|c| c.tags.contains("def:traced")));
+
+        // Attributes only lead their item's chunk when docs are extracted.
+        let mut options = ChunkOptions::new(200, 0);
+        options.transforms.extract_docs = true;
+        let chunks = chunk_code_with_options(&info, content, &options);
+        let macro_chunk = chunks.iter().find(|c| c.content.contains("macro_rules!")).unwrap();
+        assert!(!macro_chunk.content.contains("proc_macro_derive"));
+        let derive_chunk = chunks.iter().find(|c| c.content.contains("derive_builder")).unwrap();
+        assert!(derive_chunk.tags.contains("def:Builder"));
+    }
+
+    #[test]
+    fn python_decorated_members_get_kind_tags() {
+        let info = doc_test_info("models.py", "python");
+        let content = "class Account:\n    @property\n    def balance(self):\n        return self._balance\n\n    @classmethod\n    def open(cls):\n        return cls()\n\n    @staticmethod\n    def currency():\n        return \"EUR\"\n\n    async def refresh(self):\n        pass\n\n    def plain(self):\n        pass\n";
+
+        let chunks = chunk_code(&info, content, 500, 0);
+        let tags: BTreeSet<String> = chunks.iter().flat_map(|c| c.tags.iter().cloned()).collect();
+        for expected in [
+            "type:Account",
+            "class:Account",
+            "prop:balance",
+            "classmethod:open",
+            "staticmethod:currency",
+            "async:refresh",
+        ] {
+            assert!(tags.contains(expected), "missing {expected} in {tags:?}");
+        }
+        assert!(!tags.contains("prop:plain"));
+    }
+
+    #[test]
+    fn go_interfaces_and_methods_are_tagged() {
+        let info = doc_test_info("server.go", "go");
+        let content = "package server\n\ntype Handler interface {\n\tServe(req string) error\n\tClose() error\n}\n\ntype Server struct {\n\tname string\n}\n\nfunc (s *Server) Serve(req string) error {\n\treturn nil\n}\n\nfunc (s Server) Close() error {\n\treturn nil\n}\n";
+
+        let chunks = chunk_code(&info, content, 200, 0);
+        let tags: BTreeSet<String> = chunks.iter().flat_map(|c| c.tags.iter().cloned()).collect();
+        for expected in [
+            "interface:Handler",
+            "method:Handler.Serve",
+            "method:Handler.Close",
+            "method:Server.Serve",
+            "method:Server.Close",
+            "type:Server",
+        ] {
+            assert!(tags.contains(expected), "missing {expected} in {tags:?}");
+        }
+        assert!(!tags.contains("interface:Server"));
+    }
+
+    #[test]
+    fn typescript_interfaces_aliases_and_default_exports_are_tagged() {
+        let info = doc_test_info("api.ts", "typescript");
+        let content = "export interface User {\n  id: string;\n}\n\ntype UserId = string;\n\nexport type Role = \"admin\" | \"viewer\";\n\nexport default function handler(user: User): Role {\n  return \"viewer\";\n}\n";
+
+        let chunks = chunk_code(&info, content, 200, 0);
+        assert_eq!(chunks.len(), 4);
+        assert!(chunks[0].tags.contains("type:User"));
+        assert!(chunks[1].tags.contains("type:UserId"));
+        assert!(chunks[2].tags.contains("type:Role"));
+        assert!(chunks[3].tags.contains("def:handler"));
+
+        let anonymous = "export const a = 1;\n\nexport default function () {\n  return a;\n}\n";
+        let chunks = chunk_code(&info, anonymous, 200, 0);
+        assert!(chunks.iter().any(|c| c.start_line == 3 && c.tags.contains("def:default")));
+    }
+
+    #[test]
+    fn custom_node_kinds_replace_builtin_boundaries() {
+        let info = doc_test_info("shapes.py", "python");
+        let content = "import os\n\ndef helper():\n    return 1\n\nclass Alpha:\n    def run(self):\n        return helper()\n\ndef other():\n    return 2\n\nclass Beta:\n    pass\n";
+        let starts = |chunks: Vec<crate::domain::Chunk>| -> Vec<usize> {
+            chunks.iter().map(|c| c.start_line).collect()
+        };
+
+        assert_eq!(starts(chunk_code(&info, content, 200, 0)), [1, 3, 6, 10, 13]);
+        let custom = HashMap::from([("python".to_string(), vec!["class_definition".to_string()])]);
+        let options = ChunkOptions { custom_kinds: custom, ..ChunkOptions::new(200, 0) };
+        let chunks = chunk_code_with_options(&info, content, &options);
+        assert!(chunks[1].tags.contains("type:Alpha"));
+        assert_eq!(starts(chunks), [1, 6, 13]);
+    }
+
+    #[test]
+    fn custom_node_kinds_are_validated_against_the_grammar() {
+        let kinds = |language: &str, kind: &str| {
+            HashMap::from([(language.to_string(), vec![kind.to_string()])])
+        };
+
+        assert!(validate_custom_definition_kinds(&kinds("python", "class_definition")).is_ok());
+        let unknown_kind = validate_custom_definition_kinds(&kinds("python", "rule_definition"));
+        assert!(unknown_kind.unwrap_err().to_string().contains("'rule_definition'"));
+        let unknown_language =
+            validate_custom_definition_kinds(&kinds("prolog", "rule_definition"));
+        assert!(unknown_language.unwrap_err().to_string().contains("'prolog'"));
+    }
 }
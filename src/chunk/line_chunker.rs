@@ -22,6 +22,7 @@ pub fn chunk_lines(
 
     let mut chunks = Vec::new();
     let mut start = 0usize;
+    let mut prev_end = 0usize;
 
     while start < lines.len() {
         let mut end = (start + target_lines).min(lines.len());
@@ -35,8 +36,10 @@ pub fn chunk_lines(
             }
         }
 
-        if end <= start {
-            end = (start + 1).min(lines.len());
+        // End past the previous chunk so an overlapping chunk always adds lines.
+        let min_end = start.max(prev_end) + 1;
+        if end < min_end {
+            end = min_end.min(lines.len());
         }
 
         let chunk_content = lines[start..end].join("");
@@ -64,6 +67,12 @@ pub fn chunk_lines(
             file_sha256: String::new(),
         };
         chunks.push(chunk);
+        prev_end = end;
+        // Stepping back by the overlap from the last line would only repeat
+        // the tail of this chunk.
+        if end == lines.len() {
+            break;
+        }
 
         let next_start = end.saturating_sub(overlap_lines);
         if next_start <= start {
@@ -3,6 +3,7 @@
 use crate::chunk::line_chunker::chunk_lines;
 use crate::domain::{Chunk, FileInfo};
 use crate::utils::{estimate_tokens, stable_hash};
+use std::collections::BTreeSet;
 
 /// Chunk Markdown content at heading boundaries, falling back to line-based
 /// chunking for oversize sections.
@@ -17,13 +18,22 @@ pub fn chunk_markdown(
         return Vec::new();
     }
 
-    let mut sections: Vec<(usize, usize, Option<String>)> = Vec::new();
+    let mut sections: Vec<Section> = Vec::new();
     let mut section_start = 0usize;
     let mut current_heading: Option<String> = None;
+    let mut in_fence = false;
 
     for (i, line) in lines.iter().enumerate() {
-        // Heading detection: must be 1-6 '#' followed by whitespace (Python line 196)
+        // `#` lines inside fenced code (shell comments, examples) are not headings.
         let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        // Heading detection: must be 1-6 '#' followed by whitespace (Python line 196)
         let hash_count = trimmed.chars().take_while(|&c| c == '#').count();
         let is_heading = if (1..=6).contains(&hash_count) {
             let rest = &trimmed[hash_count..];
@@ -36,27 +46,39 @@ pub fn chunk_markdown(
             // Track the heading even when it is line 1, so the first section
             // below gets tagged correctly.
             if i > 0 {
-                sections.push((section_start, i, current_heading.take()));
+                sections.push(Section::new(section_start, i, current_heading.take()));
                 section_start = i;
             }
             // Extract heading text: strip leading '#' characters and whitespace (Python line 238)
-            current_heading =
-                Some(trimmed.trim_start_matches('#').trim().chars().take(30).collect::<String>());
+            current_heading = Some(trimmed.trim_start_matches('#').trim().to_string());
+        }
+    }
+    sections.push(Section::new(section_start, lines.len(), current_heading.take()));
+
+    // A heading with no body of its own (`## API` directly followed by
+    // `### Auth`) is merged into the section after it.
+    let mut merged: Vec<Section> = Vec::new();
+    let mut pending: Option<Section> = None;
+    for mut section in sections {
+        let body_is_empty = !section.headings.is_empty()
+            && lines[section.start + 1..section.end].iter().all(|line| line.trim().is_empty());
+        if let Some(empty) = pending.take() {
+            section.start = empty.start;
+            section.headings.splice(0..0, empty.headings);
+        }
+        if body_is_empty && section.end < lines.len() {
+            pending = Some(section);
+        } else {
+            merged.push(section);
         }
     }
-    sections.push((section_start, lines.len(), current_heading.take()));
 
     let mut result = Vec::new();
 
-    for (start, end, heading) in sections {
+    for Section { start, end, headings } in merged {
+        let tags = section_tags(file_info, &headings);
         let section_content = lines[start..end].join("");
         if estimate_tokens(&section_content) <= max_tokens {
-            let mut tags = file_info.tags.clone();
-            if let Some(ref h) = heading {
-                if !h.is_empty() {
-                    tags.insert(format!("section:{h}"));
-                }
-            }
             result.push(Chunk {
                 id: stable_hash(&section_content, &file_info.relative_path, start + 1, end),
                 path: file_info.relative_path.clone(),
@@ -82,6 +104,7 @@ pub fn chunk_markdown(
                 chunk.end_line += start;
                 chunk.id =
                     stable_hash(&chunk.content, &chunk.path, chunk.start_line, chunk.end_line);
+                chunk.tags.extend(tags.iter().cloned());
                 result.push(chunk);
             }
         }
@@ -91,6 +114,32 @@ pub fn chunk_markdown(
     result
 }
 
+/// Lines `start..end` (0-based, exclusive) and the headings they open with.
+struct Section {
+    start: usize,
+    end: usize,
+    headings: Vec<String>,
+}
+
+impl Section {
+    fn new(start: usize, end: usize, heading: Option<String>) -> Self {
+        Self { start, end, headings: heading.into_iter().collect() }
+    }
+}
+
+/// File tags plus `section:<first heading>` (truncated to 30 characters) and
+/// one `heading:<text>` per heading the chunk starts with.
+fn section_tags(file_info: &FileInfo, headings: &[String]) -> BTreeSet<String> {
+    let mut tags = file_info.tags.clone();
+    if let Some(first) = headings.first().filter(|h| !h.is_empty()) {
+        tags.insert(format!("section:{}", first.chars().take(30).collect::<String>()));
+    }
+    for heading in headings.iter().filter(|h| !h.is_empty()) {
+        tags.insert(format!("heading:{heading}"));
+    }
+    tags
+}
+
 #[cfg(test)]
 mod tests {
     use super::chunk_markdown;
@@ -98,9 +147,8 @@ mod tests {
     use std::collections::BTreeSet;
     use std::path::PathBuf;
 
-    #[test]
-    fn nested_markdown_chunks_keep_absolute_line_numbers() {
-        let info = FileInfo {
+    fn readme() -> FileInfo {
+        FileInfo {
             path: PathBuf::from("/tmp/readme.md"),
             relative_path: "README.md".to_string(),
             size_bytes: 0,
@@ -113,7 +161,17 @@ mod tests {
             is_readme: true,
             is_config: false,
             is_doc: true,
-        };
+            is_env_file: false,
+            is_symlink: false,
+            symlink_target: None,
+            complexity_estimate: 0,
+            priority_explanation: None,
+        }
+    }
+
+    #[test]
+    fn nested_markdown_chunks_keep_absolute_line_numbers() {
+        let info = readme();
         let content = "# A\n\nIntro\n\n# B\n".to_string() + &"line\n".repeat(200);
         let chunks = chunk_markdown(&info, &content, 80, 10);
         assert!(!chunks.is_empty());
@@ -122,4 +180,34 @@ mod tests {
             assert!(chunk.end_line >= chunk.start_line);
         }
     }
+
+    #[test]
+    fn sections_split_at_headings_outside_code_fences() {
+        let content = "# Demo\n\nIntro.\n\n## API Reference\n\n### Auth\n\nCall login.\n\n```sh\n# not a heading\nlogin --user me\n```\n\n## Install\n\nRun cargo.\n";
+        let chunks = chunk_markdown(&readme(), content, 200, 0);
+
+        let ranges: Vec<(usize, usize)> =
+            chunks.iter().map(|c| (c.start_line, c.end_line)).collect();
+        assert_eq!(ranges, [(1, 4), (5, 15), (16, 18)]);
+        assert!(chunks[0].tags.contains("heading:Demo"));
+        // The empty `## API Reference` section is merged into `### Auth`.
+        assert!(chunks[1].content.starts_with("## API Reference\n"));
+        assert!(chunks[1].content.contains("# not a heading\nlogin --user me\n```"));
+        assert!(chunks[1].tags.contains("section:API Reference"));
+        assert!(chunks[1].tags.contains("heading:API Reference"));
+        assert!(chunks[1].tags.contains("heading:Auth"));
+        assert!(chunks[2].tags.contains("heading:Install"));
+    }
+
+    #[test]
+    fn oversized_sections_fall_back_to_line_chunks_with_heading_tags() {
+        let content = "# Intro\n\nShort.\n\n## Changelog\n\n".to_string()
+            + &"- fixed another long-standing bug\n".repeat(60);
+        let chunks = chunk_markdown(&readme(), &content, 80, 0);
+
+        let changelog: Vec<_> = chunks.iter().filter(|c| c.start_line >= 5).collect();
+        assert!(changelog.len() > 1);
+        assert!(changelog.iter().all(|c| c.tags.contains("heading:Changelog")));
+        assert_eq!(chunks.last().map(|c| c.end_line), Some(content.lines().count()));
+    }
 }
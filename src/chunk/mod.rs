@@ -1,60 +1,223 @@
 //! Content chunking strategies
 
-use crate::domain::{Chunk, FileInfo};
-use crate::utils::{estimate_tokens, stable_hash};
+use crate::domain::{Chunk, Config, FileInfo};
+use crate::utils::{
+    estimate_tokens, normalize_chunk_content, stable_hash, strip_comments_from_content, Tokenizer,
+};
 use anyhow::Result;
 use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 
-use code_chunker::chunk_code;
+use code_chunker::{chunk_code_with_options, extract_doc_chunks};
 use line_chunker::chunk_lines;
 use markdown_chunker::chunk_markdown;
+use template_chunker::chunk_template;
 
 pub mod code_chunker;
 pub mod line_chunker;
 pub mod markdown_chunker;
+pub mod template_chunker;
+
+/// Optional rewrites of each chunk's stored content.
+///
+/// Chunk boundaries, line ranges, and byte offsets are always computed from
+/// the original file; only `content` (and its token estimate and hash) change.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ContentTransforms {
+    /// Remove comments via [`strip_comments_from_content`].
+    pub strip_comments: bool,
+    /// Normalize whitespace via [`normalize_chunk_content`]; the chunk ID is
+    /// then derived from the normalized content.
+    pub normalize_whitespace: bool,
+    /// Also emit each definition's doc comment as a separate `doc:` chunk
+    /// (see [`extract_doc_chunks`]), taken from the unmodified file.
+    pub extract_docs: bool,
+}
+
+impl ContentTransforms {
+    /// Transforms enabled by `config`.
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            strip_comments: config.strip_comments,
+            normalize_whitespace: config.normalize_whitespace,
+            extract_docs: config.extract_docs,
+        }
+    }
+
+    fn apply(&self, chunk: &mut Chunk) {
+        if self.strip_comments {
+            chunk.content = strip_comments_from_content(&chunk.content, &chunk.language);
+        }
+        if self.normalize_whitespace {
+            let normalized = normalize_chunk_content(&chunk.content);
+            if !normalized.is_empty() {
+                // Keep the line range pointing at the lines that remain.
+                let blank = |line: &&str| line.trim().is_empty();
+                let leading = chunk.content.lines().take_while(blank).count();
+                let trailing = chunk.content.lines().rev().take_while(blank).count();
+                chunk.start_line += leading;
+                chunk.end_line = chunk.end_line.saturating_sub(trailing).max(chunk.start_line);
+            }
+            chunk.content = normalized;
+            chunk.id = stable_hash(&chunk.content, &chunk.path, chunk.start_line, chunk.end_line);
+        }
+        chunk.token_estimate = estimate_tokens(&chunk.content);
+    }
+}
+
+/// How [`chunk_content`] splits a file and post-processes its chunks.
+#[derive(Debug, Clone)]
+pub struct ChunkOptions {
+    /// Maximum tokens per chunk.
+    pub max_tokens: usize,
+    /// Tokens of each chunk repeated at the start of the next.
+    pub overlap_tokens: usize,
+    /// Rewrites of each chunk's stored content.
+    pub transforms: ContentTransforms,
+    /// Lowers the priority of later chunks in the file (see
+    /// [`apply_priority_decay`]); `0.0` keeps every chunk at the file's priority.
+    pub priority_decay: f64,
+    /// Language to tree-sitter node kinds that replace its built-in
+    /// definition boundaries (see [`chunk_code_with_options`]).
+    pub custom_kinds: HashMap<String, Vec<String>>,
+    /// Counter for each chunk's `token_estimate`.
+    pub tokenizer: Tokenizer,
+}
+
+impl ChunkOptions {
+    /// Chunks of at most `max_tokens` with no transforms, decay, or custom
+    /// kinds, counted with the heuristic tokenizer.
+    pub fn new(max_tokens: usize, overlap_tokens: usize) -> Self {
+        Self {
+            max_tokens,
+            overlap_tokens,
+            transforms: ContentTransforms::default(),
+            priority_decay: 0.0,
+            custom_kinds: HashMap::new(),
+            tokenizer: Tokenizer::default(),
+        }
+    }
+
+    /// Options set by `config`.
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            max_tokens: config.chunk_tokens,
+            overlap_tokens: config.chunk_overlap,
+            transforms: ContentTransforms::from_config(config),
+            priority_decay: config.priority_decay,
+            custom_kinds: config.custom_tree_sitter_queries.clone(),
+            tokenizer: Tokenizer::for_model(config.semantic_model.as_deref()),
+        }
+    }
+}
 
 /// Chunk pre-loaded content, choosing strategy based on file language.
 pub fn chunk_content(
     file_info: &FileInfo,
     content: &str,
-    max_tokens: usize,
-    overlap_tokens: usize,
+    options: &ChunkOptions,
 ) -> Result<Vec<Chunk>> {
+    let ChunkOptions { max_tokens, overlap_tokens, transforms, priority_decay, tokenizer, .. } =
+        *options;
     let chunker_kind = chunker_for_language(&file_info.language);
     let mut chunks = match chunker_kind {
         ChunkerKind::Markdown => chunk_markdown(file_info, content, max_tokens, overlap_tokens),
-        ChunkerKind::Code => chunk_code(file_info, content, max_tokens, overlap_tokens),
+        ChunkerKind::Code => chunk_code_with_options(file_info, content, options),
+        ChunkerKind::Template => chunk_template(file_info, content, max_tokens, overlap_tokens),
         ChunkerKind::Line => chunk_lines(file_info, content, max_tokens, overlap_tokens),
     };
 
-    if !chunks.is_empty() {
-        enrich_chunks(&mut chunks, file_info, content);
-        return Ok(chunks);
-    }
-
-    let line_count = content.lines().count().max(1);
-    let token_estimate = estimate_tokens(content);
-    let id = stable_hash(content, &file_info.relative_path, 1, line_count);
-
-    let content_sha256 = format!("{:x}", Sha256::digest(content.as_bytes()));
-    Ok(vec![Chunk {
-        id,
-        path: file_info.relative_path.clone(),
-        language: file_info.language.clone(),
-        start_line: 1,
-        end_line: line_count,
-        content: content.to_string(),
-        priority: file_info.priority,
-        tags: file_info.tags.clone(),
-        token_estimate,
-        file_id: file_info.id.clone(),
-        chunk_index: 0,
-        chunks_in_file: 1,
-        byte_start: Some(0),
-        byte_end: Some(content.len()),
-        content_sha256: content_sha256.clone(),
-        file_sha256: content_sha256,
-    }])
+    if chunks.is_empty() {
+        let line_count = content.lines().count().max(1);
+        let token_estimate = estimate_tokens(content);
+        let id = stable_hash(content, &file_info.relative_path, 1, line_count);
+
+        let content_sha256 = format!("{:x}", Sha256::digest(content.as_bytes()));
+        chunks.push(Chunk {
+            id,
+            path: file_info.relative_path.clone(),
+            language: file_info.language.clone(),
+            start_line: 1,
+            end_line: line_count,
+            content: content.to_string(),
+            priority: file_info.priority,
+            tags: file_info.tags.clone(),
+            token_estimate,
+            file_id: file_info.id.clone(),
+            chunk_index: 0,
+            chunks_in_file: 1,
+            byte_start: Some(0),
+            byte_end: Some(content.len()),
+            content_sha256: content_sha256.clone(),
+            file_sha256: content_sha256,
+        });
+    }
+
+    let docs = if transforms.extract_docs {
+        let docs = extract_doc_chunks(file_info, content);
+        remove_doc_lines(&mut chunks, &docs);
+        docs
+    } else {
+        Vec::new()
+    };
+    for chunk in &mut chunks {
+        transforms.apply(chunk);
+    }
+    if !docs.is_empty() {
+        chunks.extend(docs);
+        chunks.sort_by_key(|chunk| chunk.start_line);
+    }
+    if tokenizer != Tokenizer::Heuristic {
+        for chunk in &mut chunks {
+            chunk.token_estimate = tokenizer.count(&chunk.content);
+        }
+    }
+    enrich_chunks(&mut chunks, file_info, content);
+    apply_priority_decay(&mut chunks, priority_decay);
+    Ok(chunks)
+}
+
+/// Drop the lines covered by `docs` from `chunks`, so extracted docs are not
+/// also counted inside their code chunk. Ranges shrink past doc lines removed
+/// from either end; chunks left with only whitespace are dropped.
+fn remove_doc_lines(chunks: &mut Vec<Chunk>, docs: &[Chunk]) {
+    let is_doc_line =
+        |line: usize| docs.iter().any(|doc| (doc.start_line..=doc.end_line).contains(&line));
+    for chunk in chunks.iter_mut() {
+        let lines: Vec<&str> = chunk.content.split_inclusive('\n').collect();
+        let keep: Vec<bool> =
+            (0..lines.len()).map(|i| !is_doc_line(chunk.start_line + i)).collect();
+        if keep.iter().all(|&k| k) {
+            continue;
+        }
+        let leading = keep.iter().take_while(|&&k| !k).count();
+        let trailing = keep.iter().rev().take_while(|&&k| !k).count();
+        let content: String =
+            lines.iter().zip(&keep).filter(|(_, &k)| k).map(|(line, _)| *line).collect();
+        if leading < lines.len() {
+            chunk.start_line += leading;
+            chunk.end_line -= trailing;
+        }
+        chunk.content = content;
+        chunk.id = stable_hash(&chunk.content, &chunk.path, chunk.start_line, chunk.end_line);
+        chunk.token_estimate = estimate_tokens(&chunk.content);
+    }
+    chunks.retain(|chunk| !chunk.content.trim().is_empty());
+}
+
+/// Scale each chunk's priority down by its position in the file:
+/// `priority * (1 - decay * index / (n - 1))` for chunks in file order.
+///
+/// The first chunk keeps its priority and the last gets `1 - decay` of it,
+/// so with `decay = 0.1` the last of ten chunks ends at 90%.
+pub fn apply_priority_decay(chunks: &mut [Chunk], decay: f64) {
+    if decay <= 0.0 || chunks.len() < 2 {
+        return;
+    }
+    let last = (chunks.len() - 1) as f64;
+    for (idx, chunk) in chunks.iter_mut().enumerate() {
+        chunk.priority *= 1.0 - decay * (idx as f64 / last);
+    }
 }
 
 /// Re-enrich chunk metadata (indices, hashes, byte offsets) after coalescing.
@@ -111,10 +274,16 @@ fn line_end_to_byte_offset(content: &str, line: usize) -> usize {
 /// - Adjacent or overlapping (next starts within 1 line of previous end)
 /// - At least one is below `min_tokens` AND combined size is below `max_tokens`
 ///
+/// Any chunk left above [`OVERSIZED_CHUNK_FACTOR`] × `max_tokens` (for
+/// example a whole file kept as one section after a failed parse) is then
+/// split into line windows of about `max_tokens`.
+///
 /// # Arguments
 /// * `chunks` - Vector of chunks to process
 /// * `min_tokens` - Minimum token threshold for coalescing
 /// * `max_tokens` - Maximum combined token limit
+/// * `tokenizer` - Counter for merged and split chunks; use the one the
+///   chunks were counted with
 ///
 /// # Returns
 /// Vector of coalesced chunks
@@ -122,12 +291,15 @@ pub fn coalesce_small_chunks_with_max(
     chunks: Vec<Chunk>,
     min_tokens: usize,
     max_tokens: usize,
+    tokenizer: Tokenizer,
 ) -> Vec<Chunk> {
     if chunks.is_empty() {
         return Vec::new();
     }
 
-    let mut sorted = chunks;
+    // Doc chunks duplicate lines of the code chunks around them; keep them
+    // out of merging so they stay standalone.
+    let (docs, mut sorted): (Vec<Chunk>, Vec<Chunk>) = chunks.into_iter().partition(is_doc_chunk);
     sorted.sort_by(|a, b| a.path.cmp(&b.path).then_with(|| a.start_line.cmp(&b.start_line)));
 
     let mut result: Vec<Chunk> = Vec::new();
@@ -147,7 +319,7 @@ pub fn coalesce_small_chunks_with_max(
                     last.content = merged_content.clone();
                     last.priority = last.priority.max(chunk.priority);
                     last.tags = merged_tags;
-                    last.token_estimate = estimate_tokens(&merged_content);
+                    last.token_estimate = tokenizer.count(&merged_content);
                     last.id =
                         stable_hash(&merged_content, &last.path, last.start_line, last.end_line);
                     last.byte_end = chunk.byte_end;
@@ -161,7 +333,82 @@ pub fn coalesce_small_chunks_with_max(
         result.push(chunk);
     }
 
+    if !docs.is_empty() {
+        result.extend(docs);
+        result.sort_by(|a, b| a.path.cmp(&b.path).then_with(|| a.start_line.cmp(&b.start_line)));
+    }
+
+    let ceiling = (max_tokens as f64 * OVERSIZED_CHUNK_FACTOR) as usize;
+    if max_tokens == 0 || result.iter().all(|chunk| chunk.token_estimate <= ceiling) {
+        return result;
+    }
     result
+        .into_iter()
+        .flat_map(|chunk| {
+            if chunk.token_estimate > ceiling {
+                split_oversized_chunk(chunk, max_tokens, tokenizer)
+            } else {
+                vec![chunk]
+            }
+        })
+        .collect()
+}
+
+/// Soft ceiling on chunk size, as a multiple of the chunk token target.
+pub const OVERSIZED_CHUNK_FACTOR: f64 = 1.5;
+
+/// Split `chunk` into line windows of about `max_tokens` with [`chunk_lines`].
+///
+/// Pieces keep the chunk's language, priority, and tags; line ranges are
+/// offset into the original file and counted with `tokenizer`. A chunk that
+/// cannot be split (a single long line) is returned unchanged.
+fn split_oversized_chunk(chunk: Chunk, max_tokens: usize, tokenizer: Tokenizer) -> Vec<Chunk> {
+    let file_info = FileInfo {
+        path: chunk.path.clone().into(),
+        relative_path: chunk.path.clone(),
+        size_bytes: chunk.content.len() as u64,
+        extension: String::new(),
+        language: chunk.language.clone(),
+        id: chunk.file_id.clone(),
+        priority: chunk.priority,
+        token_estimate: chunk.token_estimate,
+        tags: chunk.tags.clone(),
+        is_readme: false,
+        is_config: false,
+        is_doc: false,
+        is_env_file: false,
+        is_symlink: false,
+        symlink_target: None,
+        complexity_estimate: 0,
+        priority_explanation: None,
+    };
+    let mut pieces = chunk_lines(&file_info, &chunk.content, max_tokens, 0);
+    if pieces.len() <= 1 {
+        return vec![chunk];
+    }
+    let offset = chunk.start_line - 1;
+    for piece in &mut pieces {
+        piece.start_line = (piece.start_line + offset).min(chunk.end_line);
+        piece.end_line = (piece.end_line + offset).min(chunk.end_line);
+        piece.id = stable_hash(&piece.content, &piece.path, piece.start_line, piece.end_line);
+        piece.file_id = chunk.file_id.clone();
+        piece.token_estimate = tokenizer.count(&piece.content);
+        piece.content_sha256 = format!("{:x}", Sha256::digest(piece.content.as_bytes()));
+        piece.file_sha256 = chunk.file_sha256.clone();
+    }
+    tracing::debug!(
+        "Split {}:{}-{} ({} tokens) into {} chunks",
+        chunk.path,
+        chunk.start_line,
+        chunk.end_line,
+        chunk.token_estimate,
+        pieces.len()
+    );
+    pieces
+}
+
+fn is_doc_chunk(chunk: &Chunk) -> bool {
+    chunk.tags.iter().any(|tag| tag.starts_with("doc:"))
 }
 
 fn merge_chunk_content(current: &Chunk, next: &Chunk) -> String {
@@ -170,27 +417,121 @@ fn merge_chunk_content(current: &Chunk, next: &Chunk) -> String {
         format!("{}{}", current.content, next.content)
     } else {
         // Overlapping: keep non-overlapping prefix of current + all of next.
-        // overlap_start = number of lines in current that precede the overlap region.
-        let overlap_start = next.start_line.saturating_sub(current.start_line);
+        // Content transforms (comment stripping, whitespace normalization) can
+        // remove lines, so the line ranges say how many lines may be shared but
+        // not where the seam is in `current.content`. Find it by comparing the
+        // tail of current with the head of next, and fall back to the
+        // line-count offset (overlap_start = lines of current that precede the
+        // overlap region) when no lines match.
         let current_lines: Vec<&str> = current.content.split_inclusive('\n').collect();
-        let prefix = if overlap_start < current_lines.len() {
-            current_lines[..overlap_start].join("")
-        } else {
-            current.content.clone()
+        let next_lines: Vec<&str> = next.content.split_inclusive('\n').collect();
+        let overlap_lines = (current.end_line + 1).saturating_sub(next.start_line);
+        let max_shared = overlap_lines.min(current_lines.len()).min(next_lines.len());
+        let shared = (1..=max_shared).rev().find(|&count| {
+            current_lines[current_lines.len() - count..]
+                .iter()
+                .zip(&next_lines[..count])
+                .all(|(a, b)| same_line(a, b))
+        });
+        let prefix_len = match shared {
+            Some(count) => current_lines.len() - count,
+            None => {
+                let overlap_start = next.start_line.saturating_sub(current.start_line);
+                overlap_start.min(current_lines.len())
+            }
         };
-        format!("{}{}", prefix, next.content)
+        format!("{}{}", current_lines[..prefix_len].concat(), next.content)
+    }
+}
+
+fn same_line(a: &str, b: &str) -> bool {
+    a.trim_end_matches(['\r', '\n']) == b.trim_end_matches(['\r', '\n'])
+}
+
+/// Dimensions of the hashed bag-of-words vectors used for similarity.
+const EMBEDDING_DIMS: usize = 1024;
+
+/// Drop chunks that are near-duplicates of a higher-priority chunk from
+/// another file.
+///
+/// Each chunk is embedded as a hashed bag of lowercase words and word
+/// bigrams; two chunks are duplicates when their cosine similarity is at
+/// least `similarity_threshold`. Chunks are visited from highest to lowest
+/// priority (ties broken by path and line), so the survivor of each group is
+/// its highest-priority member. The returned chunks keep their input order.
+pub fn dedup_similar_chunks(chunks: Vec<Chunk>, similarity_threshold: f64) -> Vec<Chunk> {
+    let embeddings: Vec<Vec<f32>> = chunks.iter().map(|c| hash_embedding(&c.content)).collect();
+
+    let mut order: Vec<usize> = (0..chunks.len()).collect();
+    order.sort_by(|&a, &b| {
+        chunks[b]
+            .priority
+            .total_cmp(&chunks[a].priority)
+            .then_with(|| chunks[a].path.cmp(&chunks[b].path))
+            .then_with(|| chunks[a].start_line.cmp(&chunks[b].start_line))
+    });
+
+    let mut kept: Vec<usize> = Vec::new();
+    let mut keep = vec![false; chunks.len()];
+    for idx in order {
+        let duplicate = kept.iter().any(|&other| {
+            chunks[other].path != chunks[idx].path
+                && cosine_similarity(&embeddings[idx], &embeddings[other]) >= similarity_threshold
+        });
+        if !duplicate {
+            kept.push(idx);
+            keep[idx] = true;
+        }
+    }
+
+    chunks.into_iter().zip(keep).filter_map(|(chunk, keep)| keep.then_some(chunk)).collect()
+}
+
+/// L2-normalized hashed bag-of-words vector (FNV-1a over words and bigrams).
+fn hash_embedding(text: &str) -> Vec<f32> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+
+    let mut vector = vec![0.0f32; EMBEDDING_DIMS];
+    let mut add = |feature: &str| {
+        let hash = feature.bytes().fold(0xcbf2_9ce4_8422_2325u64, |h, b| {
+            (h ^ u64::from(b)).wrapping_mul(0x100_0000_01b3)
+        });
+        vector[(hash % EMBEDDING_DIMS as u64) as usize] += 1.0;
+    };
+    for word in &words {
+        add(word);
     }
+    for pair in words.windows(2) {
+        add(&format!("{} {}", pair[0], pair[1]));
+    }
+
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+    vector
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f64 {
+    // Both vectors are already unit length (or all zeros).
+    a.iter().zip(b).map(|(x, y)| f64::from(x * y)).sum()
 }
 
 enum ChunkerKind {
     Code,
     Markdown,
+    Template,
     Line,
 }
 
 fn chunker_for_language(language: &str) -> ChunkerKind {
     match language {
         "markdown" | "restructuredtext" | "asciidoc" => ChunkerKind::Markdown,
+        "jinja" | "django" | "html" => ChunkerKind::Template,
         "python" | "javascript" | "typescript" | "go" | "java" | "rust" | "c" | "cpp"
         | "csharp" | "ruby" | "php" | "swift" | "kotlin" | "scala" => ChunkerKind::Code,
         _ => ChunkerKind::Line,
@@ -199,9 +540,14 @@ fn chunker_for_language(language: &str) -> ChunkerKind {
 
 #[cfg(test)]
 mod tests {
-    use super::coalesce_small_chunks_with_max;
-    use crate::domain::Chunk;
+    use super::{
+        apply_priority_decay, chunk_content, coalesce_small_chunks_with_max, dedup_similar_chunks,
+    };
+    use super::{ChunkOptions, ContentTransforms};
+    use crate::domain::{Chunk, FileInfo};
+    use crate::utils::Tokenizer;
     use std::collections::BTreeSet;
+    use std::path::PathBuf;
 
     fn mk_chunk(
         id: &str,
@@ -238,7 +584,7 @@ mod tests {
             mk_chunk("b", "src/main.rs", 4, 6, "fn b() {}\n", 10),
         ];
 
-        let merged = coalesce_small_chunks_with_max(chunks, 20, 100);
+        let merged = coalesce_small_chunks_with_max(chunks, 20, 100, Tokenizer::Heuristic);
         assert_eq!(merged.len(), 1);
         assert_eq!(merged[0].start_line, 1);
         assert_eq!(merged[0].end_line, 6);
@@ -253,10 +599,59 @@ mod tests {
             mk_chunk("b", "src/main.rs", 4, 6, "fn b() {}\n", 60),
         ];
 
-        let merged = coalesce_small_chunks_with_max(chunks, 80, 100);
+        let merged = coalesce_small_chunks_with_max(chunks, 80, 100, Tokenizer::Heuristic);
         assert_eq!(merged.len(), 2);
     }
 
+    #[test]
+    fn coalesce_splits_chunks_over_the_soft_ceiling() {
+        let body: String =
+            (1..=120).map(|i| format!("    let value_{i} = compute({i});\n")).collect();
+        let content = format!("fn huge() {{\n{body}}}\n");
+        let tokens = crate::utils::estimate_tokens(&content);
+        let max_tokens = tokens / 4;
+        let chunks = vec![
+            mk_chunk("big", "src/huge.rs", 11, 132, &content, tokens),
+            mk_chunk("ok", "src/small.rs", 1, 3, "fn ok() {}\n", max_tokens),
+        ];
+
+        let result = coalesce_small_chunks_with_max(chunks, 1, max_tokens, Tokenizer::Heuristic);
+        let pieces: Vec<_> = result.iter().filter(|c| c.path == "src/huge.rs").collect();
+
+        assert!(pieces.len() >= 3, "expected a split, got {} pieces", pieces.len());
+        assert!(result.iter().all(|c| (c.token_estimate as f64) <= max_tokens as f64 * 1.5));
+        assert_eq!(pieces[0].start_line, 11);
+        assert_eq!(pieces.last().unwrap().end_line, 132);
+        assert!(pieces.windows(2).all(|w| w[1].start_line == w[0].end_line + 1));
+        assert_eq!(pieces.iter().map(|c| c.content.as_str()).collect::<String>(), content);
+        assert_eq!(result.iter().filter(|c| c.path == "src/small.rs").count(), 1);
+    }
+
+    #[test]
+    fn coalesce_counts_merged_and_split_chunks_with_the_given_tokenizer() {
+        let tokenizer = Tokenizer::Cl100k;
+        let chunks = vec![
+            mk_chunk("a", "src/main.rs", 1, 3, "fn a() {}\n", 10),
+            mk_chunk("b", "src/main.rs", 4, 6, "fn b() {}\n", 10),
+        ];
+        let merged = coalesce_small_chunks_with_max(chunks, 20, 100, tokenizer);
+        assert_eq!(merged[0].token_estimate, tokenizer.count(&merged[0].content));
+
+        let content: String = (1..=120).map(|i| format!("let value_{i} = {i};\n")).collect();
+        let chunks = vec![mk_chunk("big", "src/huge.rs", 1, 120, &content, 10_000)];
+        let pieces = coalesce_small_chunks_with_max(chunks, 1, 100, tokenizer);
+        assert!(pieces.len() > 1);
+        assert!(pieces.iter().all(|c| c.token_estimate == tokenizer.count(&c.content)));
+    }
+
+    #[test]
+    fn coalesce_keeps_unsplittable_oversized_chunk() {
+        let chunks = vec![mk_chunk("line", "src/min.rs", 1, 1, "let x = [1, 2, 3];\n", 1000)];
+        let result = coalesce_small_chunks_with_max(chunks, 1, 100, Tokenizer::Heuristic);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].id, "line");
+    }
+
     #[test]
     fn coalesce_produces_stable_ids_for_same_input() {
         let chunks = vec![
@@ -264,8 +659,206 @@ mod tests {
             mk_chunk("b", "src/main.rs", 4, 6, "fn b() {}\n", 10),
         ];
 
-        let first = coalesce_small_chunks_with_max(chunks.clone(), 20, 100);
-        let second = coalesce_small_chunks_with_max(chunks, 20, 100);
+        let first = coalesce_small_chunks_with_max(chunks.clone(), 20, 100, Tokenizer::Heuristic);
+        let second = coalesce_small_chunks_with_max(chunks, 20, 100, Tokenizer::Heuristic);
         assert_eq!(first[0].id, second[0].id);
     }
+
+    #[test]
+    fn coalesce_overlap_drops_shared_lines_once() {
+        let chunks = vec![
+            mk_chunk("a", "src/main.rs", 1, 4, "fn a() {\n    one();\n}\nfn b() {\n", 10),
+            mk_chunk("b", "src/main.rs", 3, 6, "}\nfn b() {\n    two();\n}\n", 10),
+        ];
+
+        let merged = coalesce_small_chunks_with_max(chunks, 20, 100, Tokenizer::Heuristic);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].content, "fn a() {\n    one();\n}\nfn b() {\n    two();\n}\n");
+    }
+
+    #[test]
+    fn coalesce_overlap_finds_seam_when_lines_were_stripped() {
+        // Line 1 (a comment) was stripped from the first chunk, so it holds one
+        // line fewer than its range; a line-count offset would keep `fn b() {`
+        // twice.
+        let chunks = vec![
+            mk_chunk("a", "src/main.rs", 1, 5, "fn a() {\n}\nfn b() {\n    two();\n", 10),
+            mk_chunk("b", "src/main.rs", 4, 6, "fn b() {\n    two();\n}\n", 10),
+        ];
+
+        let merged = coalesce_small_chunks_with_max(chunks, 20, 100, Tokenizer::Heuristic);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].content, "fn a() {\n}\nfn b() {\n    two();\n}\n");
+        // Both functions close with `}`, so only the seam lines must be unique.
+        let seam_lines = |line: &str| merged[0].content.lines().filter(|l| *l == line).count();
+        assert_eq!(seam_lines("fn b() {"), 1, "no line may repeat at the seam");
+        assert_eq!(seam_lines("    two();"), 1, "no line may repeat at the seam");
+    }
+
+    #[test]
+    fn dedup_removes_lower_priority_duplicate() {
+        let header =
+            "// Licensed under the Apache License, Version 2.0\nuse std::collections::HashMap;\n";
+        let mut high = mk_chunk("a", "src/a.rs", 1, 2, header, 20);
+        high.priority = 0.9;
+        let mut low = mk_chunk("b", "src/b.rs", 1, 2, header, 20);
+        low.priority = 0.4;
+        let other =
+            mk_chunk("c", "src/c.rs", 1, 3, "fn parse(input: &str) -> Ast { todo!() }\n", 20);
+
+        let kept = dedup_similar_chunks(vec![low, high, other], 0.95);
+        let ids: Vec<&str> = kept.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "c"]);
+    }
+
+    fn mk_file(rel: &str, extension: &str, language: &str) -> FileInfo {
+        FileInfo {
+            path: PathBuf::from("/tmp").join(rel),
+            relative_path: rel.to_string(),
+            size_bytes: 0,
+            extension: extension.to_string(),
+            language: language.to_string(),
+            id: "x".to_string(),
+            priority: 0.5,
+            token_estimate: 0,
+            tags: BTreeSet::new(),
+            is_readme: false,
+            is_config: false,
+            is_doc: false,
+            is_env_file: false,
+            is_symlink: false,
+            symlink_target: None,
+            complexity_estimate: 0,
+            priority_explanation: None,
+        }
+    }
+
+    #[test]
+    fn priority_decay_lowers_later_chunks_monotonically() {
+        let mut chunks: Vec<Chunk> = (0..10)
+            .map(|i| mk_chunk(&i.to_string(), "src/lib.rs", i * 10 + 1, i * 10 + 10, "x\n", 10))
+            .collect();
+        for chunk in &mut chunks {
+            chunk.priority = 0.8;
+        }
+
+        apply_priority_decay(&mut chunks, 0.1);
+
+        assert!((chunks[0].priority - 0.8).abs() < 1e-9);
+        assert!((chunks[9].priority - 0.72).abs() < 1e-9, "last chunk gets 90%");
+        assert!(chunks.windows(2).all(|w| w[1].priority < w[0].priority));
+    }
+
+    #[test]
+    fn priority_decay_applies_in_chunk_content() {
+        let info = FileInfo { priority: 1.0, ..mk_file("notes.txt", ".txt", "text") };
+        let content: String = (1..=200).map(|i| format!("line {i} of the notes file\n")).collect();
+
+        let flat = chunk_content(&info, &content, &ChunkOptions::new(100, 0)).unwrap();
+        let decayed = chunk_content(
+            &info,
+            &content,
+            &ChunkOptions { priority_decay: 0.5, ..ChunkOptions::new(100, 0) },
+        )
+        .unwrap();
+
+        assert!(decayed.len() > 2);
+        assert!(flat.iter().all(|c| c.priority == 1.0));
+        assert_eq!(decayed[0].priority, 1.0);
+        assert!((decayed.last().unwrap().priority - 0.5).abs() < 1e-9);
+        assert!(decayed.windows(2).all(|w| w[1].priority < w[0].priority));
+    }
+
+    #[test]
+    fn strip_comments_keeps_original_line_range() {
+        let info = mk_file("queries.sql", ".sql", "sql");
+        let content = "-- fetch every user with a long explanatory comment\nSELECT * FROM users;\n";
+        let transforms = ContentTransforms { strip_comments: true, ..Default::default() };
+
+        let plain = chunk_content(&info, content, &ChunkOptions::new(200, 0)).unwrap();
+        let stripped = chunk_content(
+            &info,
+            content,
+            &ChunkOptions { transforms, ..ChunkOptions::new(200, 0) },
+        )
+        .unwrap();
+        assert_eq!(stripped.len(), 1);
+        assert_eq!(stripped[0].content, "\nSELECT * FROM users;\n");
+        assert_eq!((stripped[0].start_line, stripped[0].end_line), (1, 2));
+        assert!(stripped[0].token_estimate < plain[0].token_estimate);
+    }
+
+    #[test]
+    fn normalized_whitespace_gives_tab_and_space_indentation_same_id() {
+        let info = mk_file("src/lib.rs", ".rs", "rust");
+        let transforms = ContentTransforms { normalize_whitespace: true, ..Default::default() };
+        let tabs = "fn add(a: u32, b: u32) -> u32 {\n\ta + b\t\n}\n";
+        let spaces = "fn add(a: u32, b: u32) -> u32 {\n    a + b\n}\n";
+
+        let tab_chunks =
+            chunk_content(&info, tabs, &ChunkOptions { transforms, ..ChunkOptions::new(200, 0) })
+                .unwrap();
+        let space_chunks =
+            chunk_content(&info, spaces, &ChunkOptions { transforms, ..ChunkOptions::new(200, 0) })
+                .unwrap();
+        assert_eq!(tab_chunks[0].content, space_chunks[0].content);
+        assert_eq!(tab_chunks[0].id, space_chunks[0].id);
+
+        let raw = chunk_content(&info, tabs, &ChunkOptions::new(200, 0)).unwrap();
+        assert_ne!(raw[0].id, space_chunks[0].id);
+    }
+
+    #[test]
+    fn extract_docs_adds_a_doc_chunk_per_documented_function() {
+        let info = mk_file("src/lib.rs", ".rs", "rust");
+        let content = "/// Adds.\npub fn add() {}\n\n/// Subs.\npub fn sub() {}\n\n/// Muls.\npub fn mul() {}\n";
+        let transforms = ContentTransforms { extract_docs: true, ..Default::default() };
+
+        let chunks = chunk_content(
+            &info,
+            content,
+            &ChunkOptions { transforms, ..ChunkOptions::new(200, 0) },
+        )
+        .unwrap();
+        assert_eq!(chunks.len(), 6);
+        let (docs, code): (Vec<_>, Vec<_>) =
+            chunks.iter().partition(|c| c.tags.iter().any(|t| t.starts_with("doc:")));
+        assert_eq!(docs.len(), 3);
+        assert_eq!(code.len(), 3);
+        assert!(docs.iter().all(|c| c.language == "markdown"));
+        assert!(code.iter().all(|c| c.language == "rust"));
+        assert_eq!(docs[1].content, "Subs.\n");
+        assert!(docs[1].tags.contains("doc:sub"));
+        // The docs moved out of the code chunks instead of being counted twice.
+        assert!(code.iter().all(|c| !c.content.contains("///")));
+        assert_eq!((code[1].start_line, code[1].content.trim_end()), (5, "pub fn sub() {}"));
+        assert!(chunks.iter().enumerate().all(|(idx, c)| c.chunk_index == idx));
+
+        let coalesced = coalesce_small_chunks_with_max(chunks, 50, 200, Tokenizer::Heuristic);
+        assert_eq!(coalesced.iter().filter(|c| c.language == "markdown").count(), 3);
+    }
+
+    #[test]
+    fn extract_docs_tags_python_docstring_params() {
+        let info = mk_file("app/bank.py", ".py", "python");
+        let content = "def transfer(source, amount):\n    \"\"\"Move funds.\n\n    Args:\n        source (Account): Account to debit.\n        amount: Amount in cents.\n    \"\"\"\n    return True\n";
+        let transforms = ContentTransforms { extract_docs: true, ..Default::default() };
+
+        let chunks = chunk_content(
+            &info,
+            content,
+            &ChunkOptions { transforms, ..ChunkOptions::new(200, 0) },
+        )
+        .unwrap();
+        let doc = chunks.iter().find(|c| c.tags.contains("doc:transfer")).unwrap();
+        assert_eq!(doc.language, "markdown");
+        assert_eq!((doc.start_line, doc.end_line), (2, 7));
+        assert!(doc.content.starts_with("Move funds.\n\nArgs:"));
+        assert!(doc.tags.contains("param:source"));
+        assert!(doc.tags.contains("param:amount"));
+        assert!(chunks
+            .iter()
+            .filter(|c| c.language == "python")
+            .all(|c| !c.content.contains("Move funds")));
+    }
 }
@@ -0,0 +1,205 @@
+//! Jinja2 / Django template chunking at `{% block %}` boundaries.
+
+use crate::chunk::line_chunker::chunk_lines;
+use crate::domain::{Chunk, FileInfo};
+use crate::utils::{estimate_tokens, stable_hash};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// `{% block name %}` / `{%- block name -%}` openings; group 1 is the name.
+static BLOCK_START: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{%-?\s*block\s+([A-Za-z_][A-Za-z0-9_]*)").expect("valid regex"));
+
+/// `{% endblock %}` / `{% endblock name %}` closings.
+static BLOCK_END: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{%-?\s*endblock\b").expect("valid regex"));
+
+/// Chunk a template into one chunk per top-level `{% block %}` plus the
+/// markup between blocks, falling back to line-based chunking for oversize
+/// sections.
+///
+/// Nested blocks stay inside their parent's chunk. Every block opened in a
+/// chunk is tagged `block:<name>`. An unclosed block runs to the end of the file.
+pub fn chunk_template(
+    file_info: &FileInfo,
+    content: &str,
+    max_tokens: usize,
+    overlap_tokens: usize,
+) -> Vec<Chunk> {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    if lines.is_empty() {
+        return Vec::new();
+    }
+
+    // (start, end) line ranges, 0-based and exclusive, with their block names.
+    let mut sections: Vec<(usize, usize, Vec<String>)> = Vec::new();
+    let mut section_start = 0usize;
+    let mut names: Vec<String> = Vec::new();
+    let mut depth = 0usize;
+
+    for (i, line) in lines.iter().enumerate() {
+        let mut events: Vec<(usize, Option<String>)> = BLOCK_START
+            .captures_iter(line)
+            .filter_map(|caps| Some((caps.get(0)?.start(), Some(caps[1].to_string()))))
+            .collect();
+        events.extend(BLOCK_END.find_iter(line).map(|m| (m.start(), None)));
+        events.sort_by_key(|(offset, _)| *offset);
+
+        for (_, event) in events {
+            match event {
+                Some(name) => {
+                    if depth == 0 && i > section_start {
+                        sections.push((section_start, i, std::mem::take(&mut names)));
+                        section_start = i;
+                    }
+                    depth += 1;
+                    names.push(name);
+                }
+                None => depth = depth.saturating_sub(1),
+            }
+        }
+        // Close the section after the line that ends a top-level block.
+        if depth == 0 && !names.is_empty() {
+            sections.push((section_start, i + 1, std::mem::take(&mut names)));
+            section_start = i + 1;
+        }
+    }
+    if section_start < lines.len() {
+        sections.push((section_start, lines.len(), names));
+    }
+
+    let mut result = Vec::new();
+    for (start, end, names) in sections {
+        let section_content = lines[start..end].join("");
+        if section_content.trim().is_empty() {
+            continue;
+        }
+        let mut tags = file_info.tags.clone();
+        tags.extend(names.iter().map(|name| format!("block:{name}")));
+
+        if estimate_tokens(&section_content) <= max_tokens {
+            result.push(Chunk {
+                id: stable_hash(&section_content, &file_info.relative_path, start + 1, end),
+                path: file_info.relative_path.clone(),
+                language: file_info.language.clone(),
+                start_line: start + 1,
+                end_line: end,
+                token_estimate: estimate_tokens(&section_content),
+                content: section_content,
+                priority: file_info.priority,
+                tags,
+                file_id: String::new(),
+                chunk_index: 0,
+                chunks_in_file: 0,
+                byte_start: None,
+                byte_end: None,
+                content_sha256: String::new(),
+                file_sha256: String::new(),
+            });
+        } else {
+            let nested = chunk_lines(file_info, &section_content, max_tokens, overlap_tokens);
+            for mut chunk in nested {
+                chunk.start_line += start;
+                chunk.end_line += start;
+                chunk.id =
+                    stable_hash(&chunk.content, &chunk.path, chunk.start_line, chunk.end_line);
+                chunk.tags.extend(tags.iter().cloned());
+                result.push(chunk);
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::chunk_template;
+    use crate::domain::{Chunk, FileInfo};
+    use std::collections::BTreeSet;
+    use std::path::PathBuf;
+
+    fn block_tags(chunk: &Chunk) -> BTreeSet<&str> {
+        chunk.tags.iter().filter_map(|tag| tag.strip_prefix("block:")).collect()
+    }
+
+    fn template() -> FileInfo {
+        FileInfo {
+            path: PathBuf::from("/tmp/templates/page.html.j2"),
+            relative_path: "templates/page.html.j2".to_string(),
+            size_bytes: 0,
+            extension: ".j2".to_string(),
+            language: "jinja".to_string(),
+            id: "t".to_string(),
+            priority: 0.5,
+            token_estimate: 0,
+            tags: BTreeSet::new(),
+            is_readme: false,
+            is_config: false,
+            is_doc: false,
+            is_env_file: false,
+            is_symlink: false,
+            symlink_target: None,
+            complexity_estimate: 0,
+            priority_explanation: None,
+        }
+    }
+
+    const PAGE: &str = "{% extends \"base.html\" %}\n\
+{% load static %}\n\
+\n\
+{% block title %}Orders{% endblock %}\n\
+\n\
+{% block content %}\n\
+<ul>\n\
+  {% for order in orders %}\n\
+  {% block order_row %}<li>{{ order.id }}</li>{% endblock order_row %}\n\
+  {% endfor %}\n\
+</ul>\n\
+{% endblock content %}\n\
+\n\
+{%- block scripts -%}\n\
+<script src=\"{% static 'orders.js' %}\"></script>\n\
+{%- endblock -%}\n";
+
+    #[test]
+    fn splits_on_top_level_blocks() {
+        let chunks = chunk_template(&template(), PAGE, 400, 0);
+
+        let ranges: Vec<(usize, usize)> =
+            chunks.iter().map(|c| (c.start_line, c.end_line)).collect();
+        assert_eq!(ranges, [(1, 3), (4, 4), (6, 12), (14, 16)]);
+        assert!(block_tags(&chunks[0]).is_empty());
+        assert!(chunks[0].content.starts_with("{% extends"));
+        assert_eq!(block_tags(&chunks[1]), BTreeSet::from(["title"]));
+        // The nested block stays in its parent's chunk and is tagged too.
+        assert_eq!(block_tags(&chunks[2]), BTreeSet::from(["content", "order_row"]));
+        assert!(chunks[2].content.ends_with("{% endblock content %}\n"));
+        assert_eq!(block_tags(&chunks[3]), BTreeSet::from(["scripts"]));
+    }
+
+    #[test]
+    fn oversize_blocks_fall_back_to_line_chunks() {
+        let body = "<p>{{ row }}</p>\n".repeat(200);
+        let content = format!("{{% block content %}}\n{body}{{% endblock %}}\n<footer></footer>\n");
+        let chunks = chunk_template(&template(), &content, 100, 0);
+
+        assert!(chunks.len() > 2);
+        let (footer, blocks) = chunks.split_last().expect("chunks");
+        assert!(blocks.iter().all(|c| block_tags(c) == BTreeSet::from(["content"])));
+        assert_eq!(blocks.last().map(|c| c.end_line), Some(202));
+        assert_eq!((footer.start_line, footer.end_line), (203, 203));
+        assert!(block_tags(footer).is_empty());
+    }
+
+    #[test]
+    fn unclosed_block_runs_to_end_of_file() {
+        let content = "<h1>Hi</h1>\n{% block body %}\n<p>never closed</p>\n";
+        let chunks = chunk_template(&template(), content, 400, 0);
+
+        let ranges: Vec<(usize, usize)> =
+            chunks.iter().map(|c| (c.start_line, c.end_line)).collect();
+        assert_eq!(ranges, [(1, 1), (2, 3)]);
+        assert_eq!(block_tags(&chunks[1]), BTreeSet::from(["body"]));
+    }
+}
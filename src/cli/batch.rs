@@ -0,0 +1,224 @@
+//! Batch command implementation.
+//!
+//! Exports several repositories listed in a TOML batch file:
+//!
+//! ```toml
+//! [[repos]]
+//! path = "services/api"            # relative to the batch file
+//! args = ["--mode", "rag", "--max-tokens", "20000"]
+//!
+//! [[repos]]
+//! repo = "https://github.com/org/web"
+//! ref = "main"
+//! ```
+//!
+//! `args` accepts any `export` flags. Each repository is written to
+//! `<output-dir>/<repo_name>/`, exactly as `export` would.
+
+use anyhow::{Context, Result};
+use clap::{Args, Parser, ValueHint};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use super::export::{prepare_export, ExportArgs};
+use crate::app::export::{execute, ExportOutcome};
+use crate::domain::ScanStats;
+use crate::module::focus_picker::ScanMode;
+
+#[derive(Args)]
+pub struct BatchArgs {
+    /// Batch file (TOML) listing repositories to export.
+    #[arg(value_name = "FILE", value_hint = ValueHint::FilePath)]
+    pub file: PathBuf,
+
+    /// Parent directory for per-repository outputs (entries may override with --output-dir).
+    #[arg(short = 'o', long, value_name = "DIR", value_hint = ValueHint::DirPath)]
+    pub output_dir: Option<PathBuf>,
+
+    /// Export repositories concurrently instead of one after another.
+    #[arg(long)]
+    pub parallel: bool,
+
+    /// Omit timestamps for reproducible diffs.
+    #[arg(long)]
+    pub no_timestamp: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchFile {
+    #[serde(default)]
+    repos: Vec<BatchEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchEntry {
+    path: Option<PathBuf>,
+    #[serde(alias = "url")]
+    repo: Option<String>,
+    #[serde(rename = "ref")]
+    ref_: Option<String>,
+    #[serde(default)]
+    args: Vec<String>,
+}
+
+/// Parses one entry's `args` with the same flags as `export`.
+#[derive(Parser)]
+#[command(no_binary_name = true)]
+struct EntryArgv {
+    #[command(flatten)]
+    export: ExportArgs,
+}
+
+pub fn run(args: BatchArgs) -> Result<()> {
+    let content = std::fs::read_to_string(&args.file)
+        .with_context(|| format!("Failed to read batch file: {}", args.file.display()))?;
+    let batch: BatchFile = toml::from_str(&content)
+        .with_context(|| format!("Failed to parse batch file: {}", args.file.display()))?;
+    if batch.repos.is_empty() {
+        anyhow::bail!("Batch file lists no repositories: {}", args.file.display());
+    }
+    let base_dir = args.file.parent().unwrap_or_else(|| Path::new("."));
+
+    let entries = batch
+        .repos
+        .iter()
+        .enumerate()
+        .map(|(idx, entry)| {
+            build_export_args(entry, base_dir, &args)
+                .with_context(|| format!("Invalid batch entry #{}", idx + 1))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    check_unique_targets(&entries)?;
+
+    let results: Vec<(String, Result<ExportOutcome>)> = if args.parallel {
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = entries
+                .iter()
+                .map(|entry| scope.spawn(move || (entry_label(entry), export_entry(entry))))
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| {
+                    h.join().unwrap_or_else(|_| (String::new(), Err(anyhow::anyhow!("panicked"))))
+                })
+                .collect()
+        })
+    } else {
+        entries.iter().map(|entry| (entry_label(entry), export_entry(entry))).collect()
+    };
+
+    let mut failed = 0usize;
+    let mut total = ScanStats::default();
+    for (label, result) in &results {
+        match result {
+            Ok(outcome) => {
+                total = total + outcome.stats.clone();
+                println!(
+                    "  ok: {} ({} files, {} chunks, {} tokens)",
+                    label,
+                    outcome.stats.files_included,
+                    outcome.stats.chunks_created,
+                    outcome.stats.total_tokens_estimated
+                );
+                if outcome.stats.env_files_redacted > 0 {
+                    eprintln!(
+                        "Warning: {}: {} .env file(s) detected; their contents were fully redacted",
+                        label, outcome.stats.env_files_redacted
+                    );
+                }
+            }
+            Err(e) => {
+                failed += 1;
+                println!("  failed: {}: {:#}", label, e);
+            }
+        }
+    }
+
+    println!("Batch complete:");
+    println!("  repositories: {} ok, {} failed", results.len() - failed, failed);
+    println!("  files: {}", total.files_included);
+    println!("  chunks: {}", total.chunks_created);
+    println!("  tokens: {}", total.total_tokens_estimated);
+    let mut languages: Vec<_> = total.languages_detected.iter().collect();
+    languages.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    if !languages.is_empty() {
+        let top: Vec<String> =
+            languages.iter().take(5).map(|(lang, count)| format!("{lang} ({count})")).collect();
+        println!("  languages: {}", top.join(", "));
+    }
+
+    if failed > 0 {
+        anyhow::bail!("{failed} of {} batch exports failed", results.len());
+    }
+    Ok(())
+}
+
+fn build_export_args(entry: &BatchEntry, base_dir: &Path, batch: &BatchArgs) -> Result<ExportArgs> {
+    let mut export = EntryArgv::try_parse_from(&entry.args)
+        .map_err(|e| anyhow::anyhow!("{}", e.render().to_string().trim_end()))?
+        .export;
+
+    match (&entry.path, &entry.repo) {
+        (Some(_), Some(_)) => anyhow::bail!("Specify either 'path' or 'repo', not both"),
+        (None, None) => anyhow::bail!("Each entry needs a 'path' or 'repo'"),
+        (Some(path), None) => export.path = Some(base_dir.join(path)),
+        (None, Some(repo)) => {
+            export.repo = Some(repo.clone());
+            export.ref_ = entry.ref_.clone().or(export.ref_);
+        }
+    }
+    if export.output_dir.is_none() {
+        export.output_dir.clone_from(&batch.output_dir);
+    }
+    export.no_timestamp |= batch.no_timestamp;
+    // Batch runs print one summary line per entry, so flags that only change
+    // what `export` prints (or keep it running) would be silently dropped.
+    let unsupported = [
+        ("--watch", export.watch),
+        ("--dry-run", export.dry_run),
+        ("--dry-run-redaction", export.dry_run_redaction),
+        ("--explain", export.explain.is_some()),
+        ("--debug-ranking", export.debug_ranking.is_some()),
+        ("--github-annotations", export.github_annotations.is_some()),
+    ];
+    if let Some((flag, _)) = unsupported.iter().find(|(_, set)| *set) {
+        anyhow::bail!("{flag} is not supported in batch exports");
+    }
+    Ok(export)
+}
+
+/// Reject entries that would write into the same `<output-dir>/<repo_name>/`.
+fn check_unique_targets(entries: &[ExportArgs]) -> Result<()> {
+    let mut seen = HashSet::new();
+    for entry in entries {
+        let name = entry_label(entry);
+        let key = (entry.output_dir.clone(), name.clone());
+        if !seen.insert(key) {
+            anyhow::bail!(
+                "Two batch entries export '{name}' to the same output directory; give one a different --output-dir in its args"
+            );
+        }
+    }
+    Ok(())
+}
+
+fn export_entry(args: &ExportArgs) -> Result<ExportOutcome> {
+    let (config, mut options) = prepare_export(args)?;
+    // Batch runs are unattended: never stop at the interactive scan-mode picker.
+    options.scan_mode = options.scan_mode.or(Some(ScanMode::Full));
+    execute(config, options)
+}
+
+fn entry_label(args: &ExportArgs) -> String {
+    let source = match (&args.path, &args.repo) {
+        (Some(path), _) => {
+            path.canonicalize().unwrap_or_else(|_| path.clone()).to_string_lossy().to_string()
+        }
+        (None, Some(repo)) => repo.clone(),
+        (None, None) => String::new(),
+    };
+    let trimmed = source.trim_end_matches('/');
+    let last = trimmed.rsplit(['/', '\\']).next().unwrap_or(trimmed);
+    last.strip_suffix(".git").unwrap_or(last).to_string()
+}
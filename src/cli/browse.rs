@@ -0,0 +1,433 @@
+//! Browse command implementation.
+//!
+//! A two-pane terminal browser over a `*_chunks.jsonl` file from a prior
+//! export: ranked chunks on the left, the selected chunk's highlighted
+//! content on the right.
+//!
+//! Keys: `↑`/`↓` (or `k`/`j`) select, `PgUp`/`PgDn` scroll the content,
+//! `/` searches, `c` copies the chunk to the clipboard, `q` quits.
+
+use anyhow::{Context, Result};
+use clap::{Args, ValueHint};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use serde::Deserialize;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use syntect::easy::HighlightLines;
+use syntect::util::LinesWithEndings;
+
+use crate::render::html::{find_syntax, SYNTAX_SET, THEME_SET};
+
+const HIGHLIGHT_THEME: &str = "base16-ocean.dark";
+const PAGE_LINES: u16 = 10;
+
+#[derive(Args)]
+pub struct BrowseArgs {
+    /// Chunks file written by `export --mode rag|both` (<repo>_chunks.jsonl).
+    #[arg(value_name = "FILE", value_hint = ValueHint::FilePath)]
+    pub file: PathBuf,
+}
+
+/// The subset of a chunks.jsonl record the browser displays.
+#[derive(Debug, Clone, Deserialize)]
+struct BrowseChunk {
+    path: String,
+    #[serde(default)]
+    lang: String,
+    start_line: usize,
+    end_line: usize,
+    #[serde(default)]
+    priority: f64,
+    #[serde(default)]
+    token_estimate: usize,
+    content: String,
+}
+
+pub fn run(args: BrowseArgs) -> Result<()> {
+    let chunks = load_chunks(&args.file)?;
+    if chunks.is_empty() {
+        anyhow::bail!("No chunks in {}", args.file.display());
+    }
+    let mut state = BrowserState::new(chunks);
+
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut state);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, state: &mut BrowserState) -> Result<()> {
+    while !state.should_quit {
+        terminal.draw(|frame| draw(frame, state))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if let Action::Copy(text) = state.handle_key(key) {
+                copy_to_clipboard(&text)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn load_chunks(path: &Path) -> Result<Vec<BrowseChunk>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read chunks file: {}", path.display()))?;
+    let mut chunks = content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter_map(|(idx, line)| {
+            let record = serde_json::from_str::<serde_json::Value>(line)
+                .with_context(|| format!("{}:{}: invalid JSON", path.display(), idx + 1));
+            match record {
+                Ok(value) if value.get("type").and_then(|t| t.as_str()) == Some("header") => None,
+                Ok(value) => {
+                    Some(serde_json::from_value::<BrowseChunk>(value).with_context(|| {
+                        format!("{}:{}: not a chunk record", path.display(), idx + 1)
+                    }))
+                }
+                Err(err) => Some(Err(err)),
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+    chunks.sort_by(|a, b| {
+        b.priority
+            .total_cmp(&a.priority)
+            .then_with(|| a.path.cmp(&b.path))
+            .then_with(|| a.start_line.cmp(&b.start_line))
+    });
+    Ok(chunks)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Normal,
+    Search,
+}
+
+/// Side effects a key press asks the event loop to perform.
+#[derive(Debug, PartialEq, Eq)]
+enum Action {
+    None,
+    Copy(String),
+}
+
+/// Key handling and selection, independent of the terminal.
+struct BrowserState {
+    chunks: Vec<BrowseChunk>,
+    /// Indices into `chunks` matching the current search.
+    visible: Vec<usize>,
+    /// Position within `visible`.
+    selected: usize,
+    scroll: u16,
+    mode: Mode,
+    query: String,
+    status: String,
+    should_quit: bool,
+}
+
+impl BrowserState {
+    fn new(chunks: Vec<BrowseChunk>) -> Self {
+        let visible = (0..chunks.len()).collect();
+        Self {
+            chunks,
+            visible,
+            selected: 0,
+            scroll: 0,
+            mode: Mode::Normal,
+            query: String::new(),
+            status: String::new(),
+            should_quit: false,
+        }
+    }
+
+    fn selected_chunk(&self) -> Option<&BrowseChunk> {
+        self.visible.get(self.selected).map(|&idx| &self.chunks[idx])
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.should_quit = true;
+            return Action::None;
+        }
+        match self.mode {
+            Mode::Normal => self.handle_normal_key(key.code),
+            Mode::Search => {
+                self.handle_search_key(key.code);
+                Action::None
+            }
+        }
+    }
+
+    fn handle_normal_key(&mut self, code: KeyCode) -> Action {
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.should_quit = true,
+            KeyCode::Up | KeyCode::Char('k') => self.select(self.selected.saturating_sub(1)),
+            KeyCode::Down | KeyCode::Char('j') => self.select(self.selected + 1),
+            KeyCode::Home | KeyCode::Char('g') => self.select(0),
+            KeyCode::End | KeyCode::Char('G') => self.select(usize::MAX),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(PAGE_LINES),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(PAGE_LINES),
+            KeyCode::Char('/') => {
+                self.mode = Mode::Search;
+                self.status.clear();
+            }
+            KeyCode::Char('c') => {
+                if let Some(chunk) = self.selected_chunk() {
+                    let text = chunk.content.clone();
+                    self.status = format!("Copied {} line(s)", text.lines().count());
+                    return Action::Copy(text);
+                }
+            }
+            _ => {}
+        }
+        Action::None
+    }
+
+    fn handle_search_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter => self.mode = Mode::Normal,
+            KeyCode::Esc => {
+                self.query.clear();
+                self.apply_filter();
+                self.mode = Mode::Normal;
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.apply_filter();
+            }
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                self.apply_filter();
+            }
+            _ => {}
+        }
+    }
+
+    fn select(&mut self, position: usize) {
+        let last = self.visible.len().saturating_sub(1);
+        let position = position.min(last);
+        if position != self.selected {
+            self.selected = position;
+            self.scroll = 0;
+        }
+    }
+
+    fn apply_filter(&mut self) {
+        self.visible = (0..self.chunks.len())
+            .filter(|&idx| matches_query(&self.chunks[idx], &self.query))
+            .collect();
+        self.selected = 0;
+        self.scroll = 0;
+    }
+}
+
+/// Every whitespace-separated query term must appear (case-insensitively)
+/// in the chunk's path or content.
+fn matches_query(chunk: &BrowseChunk, query: &str) -> bool {
+    let path = chunk.path.to_lowercase();
+    let content = chunk.content.to_lowercase();
+    query
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .all(|term| path.contains(&term) || content.contains(&term))
+}
+
+fn draw(frame: &mut Frame, state: &BrowserState) {
+    let [body, footer] =
+        Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+    let [left, right] =
+        Layout::horizontal([Constraint::Percentage(35), Constraint::Percentage(65)]).areas(body);
+
+    let mut previous_path = None;
+    let items: Vec<ListItem> = state
+        .visible
+        .iter()
+        .map(|&idx| {
+            let chunk = &state.chunks[idx];
+            let range = format!("L{}-{}", chunk.start_line, chunk.end_line);
+            // Show the path once per file, then indent its further chunks.
+            let label = if previous_path == Some(chunk.path.as_str()) {
+                format!("  └ {range}")
+            } else {
+                format!("{} {range}", chunk.path)
+            };
+            previous_path = Some(chunk.path.as_str());
+            ListItem::new(label)
+        })
+        .collect();
+    let list = List::new(items)
+        .block(Block::bordered().title(format!(" Chunks ({}) ", state.visible.len())))
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    let mut list_state = ListState::default().with_selected(Some(state.selected));
+    frame.render_stateful_widget(list, left, &mut list_state);
+
+    let (title, text) = match state.selected_chunk() {
+        Some(chunk) => (
+            format!(
+                " {}:{}-{} · {} tokens · priority {:.3} ",
+                chunk.path, chunk.start_line, chunk.end_line, chunk.token_estimate, chunk.priority
+            ),
+            highlight(&chunk.content, &chunk.lang),
+        ),
+        None => (" No matches ".to_string(), Text::default()),
+    };
+    let content =
+        Paragraph::new(text).block(Block::bordered().title(title)).scroll((state.scroll, 0));
+    frame.render_widget(content, right);
+
+    let footer_text = match state.mode {
+        Mode::Search => format!("/{}", state.query),
+        Mode::Normal if !state.status.is_empty() => state.status.clone(),
+        Mode::Normal => "↑↓ select  PgUp/PgDn scroll  / search  c copy  q quit".to_string(),
+    };
+    frame.render_widget(Paragraph::new(footer_text), footer);
+}
+
+fn highlight(content: &str, language: &str) -> Text<'static> {
+    let Some(theme) =
+        THEME_SET.themes.get(HIGHLIGHT_THEME).or_else(|| THEME_SET.themes.values().next())
+    else {
+        return Text::raw(content.to_string());
+    };
+    let mut highlighter = HighlightLines::new(find_syntax(language), theme);
+    let lines = LinesWithEndings::from(content).map(|line| {
+        match highlighter.highlight_line(line, &SYNTAX_SET) {
+            Ok(regions) => Line::from(
+                regions
+                    .into_iter()
+                    .map(|(style, text)| {
+                        let fg = style.foreground;
+                        Span::styled(
+                            text.trim_end_matches(['\n', '\r']).to_string(),
+                            Style::new().fg(Color::Rgb(fg.r, fg.g, fg.b)),
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+            Err(_) => Line::raw(line.trim_end_matches(['\n', '\r']).to_string()),
+        }
+    });
+    Text::from(lines.collect::<Vec<_>>())
+}
+
+/// Copy via the OSC 52 escape sequence, which most terminals (including
+/// over SSH and tmux with `set-clipboard on`) forward to the system clipboard.
+fn copy_to_clipboard(text: &str) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))?;
+    stdout.flush()?;
+    Ok(())
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for group in bytes.chunks(3) {
+        let n = group
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, &b)| acc | (u32::from(b) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= group.len() {
+                out.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const FIXTURE: &str = concat!(
+        r#"{"chunk_count":3,"schema_version":"1.0","type":"header"}"#,
+        "\n",
+        r#"{"path":"src/util.rs","lang":"rust","start_line":1,"end_line":3,"priority":0.5,"content":"fn helper() {}\n"}"#,
+        "\n",
+        r#"{"path":"README.md","lang":"markdown","start_line":1,"end_line":2,"priority":1.0,"content":"Demo readme\n"}"#,
+        "\n",
+        r#"{"path":"src/auth.rs","lang":"rust","start_line":10,"end_line":20,"priority":0.75,"content":"fn verify_token() {}\n"}"#,
+        "\n"
+    );
+
+    fn state() -> BrowserState {
+        let tmp = TempDir::new().expect("tmp");
+        let path = tmp.path().join("demo_chunks.jsonl");
+        std::fs::write(&path, FIXTURE).expect("write fixture");
+        BrowserState::new(load_chunks(&path).expect("load chunks"))
+    }
+
+    fn press(state: &mut BrowserState, code: KeyCode) -> Action {
+        state.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn selected_path(state: &BrowserState) -> Option<&str> {
+        state.selected_chunk().map(|c| c.path.as_str())
+    }
+
+    #[test]
+    fn chunks_are_listed_by_priority_and_arrows_navigate() {
+        let mut state = state();
+        assert_eq!(selected_path(&state), Some("README.md"));
+
+        press(&mut state, KeyCode::Down);
+        assert_eq!(selected_path(&state), Some("src/auth.rs"));
+        press(&mut state, KeyCode::Down);
+        press(&mut state, KeyCode::Down);
+        assert_eq!(selected_path(&state), Some("src/util.rs"));
+        press(&mut state, KeyCode::Up);
+        assert_eq!(selected_path(&state), Some("src/auth.rs"));
+    }
+
+    #[test]
+    fn search_filters_and_escape_clears() {
+        let mut state = state();
+        press(&mut state, KeyCode::Char('/'));
+        for c in "token".chars() {
+            press(&mut state, KeyCode::Char(c));
+        }
+        press(&mut state, KeyCode::Enter);
+        assert_eq!(state.visible.len(), 1);
+        assert_eq!(selected_path(&state), Some("src/auth.rs"));
+
+        // `q` typed while searching is part of the query, not a quit.
+        press(&mut state, KeyCode::Char('/'));
+        press(&mut state, KeyCode::Char('q'));
+        assert!(!state.should_quit);
+        press(&mut state, KeyCode::Esc);
+        assert_eq!(state.visible.len(), 3);
+    }
+
+    #[test]
+    fn copy_and_quit_keys() {
+        let mut state = state();
+        assert_eq!(
+            press(&mut state, KeyCode::Char('c')),
+            Action::Copy("Demo readme\n".to_string())
+        );
+        assert!(!state.should_quit);
+        press(&mut state, KeyCode::Char('q'));
+        assert!(state.should_quit);
+    }
+
+    #[test]
+    fn base64_matches_rfc_4648_vectors() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    }
+}
@@ -1,38 +1,68 @@
 //! Export command implementation.
 
 use anyhow::Result;
-use clap::Args;
+use clap::builder::{PossibleValue, PossibleValuesParser};
+use clap::{Args, ValueHint};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::time::Duration;
 
+use super::presets::{ci_overrides, preset_overrides, PRESET_NAMES};
 use super::utils::parse_csv;
-use crate::app::export::{execute, ExportExecutionOptions};
-use crate::config::{load_config, merge_cli_with_config, CliOverrides};
-use crate::domain::{OutputMode, RedactionMode};
+use super::watch::watch_and_reexport;
+use crate::app::export::{execute, ExportExecutionOptions, ExportOutcome};
+use crate::config::{load_config, merge_cli_with_config, validate_config, CliOverrides};
+use crate::domain::{
+    ChunkSortKey, Config, FileDisposition, FileDispositionReason, OutputMode, PackingStrategy,
+    RedactionMode,
+};
 use crate::module::focus_picker::ScanMode;
+use crate::rank::RankingSignal;
+use crate::render::github_annotations::{
+    error_annotation, redaction_annotations, running_in_github_actions,
+};
+use crate::utils::detect_ci_environment;
+
+/// Default for --cache-ttl-seconds.
+const DEFAULT_SCAN_CACHE_TTL_SECS: u64 = 60;
 
 #[derive(Args)]
 pub struct ExportArgs {
     /// Local directory path to export.
-    #[arg(short, long, value_name = "PATH")]
+    #[arg(short, long, value_name = "PATH", value_hint = ValueHint::DirPath)]
     pub path: Option<PathBuf>,
 
     /// GitHub repository URL to clone and export.
-    #[arg(short = 'r', long, value_name = "URL")]
+    #[arg(short = 'r', long, value_name = "URL", value_hint = ValueHint::Url)]
     pub repo: Option<String>,
 
     /// Git ref (branch/tag/SHA) when using --repo.
     #[arg(long, value_name = "REF")]
     pub ref_: Option<String>,
 
+    /// Private SSH key for git@ URLs (default: SSH_KEY_PATH, then the SSH agent).
+    #[arg(long, value_name = "PATH", value_hint = ValueHint::FilePath)]
+    pub ssh_key: Option<PathBuf>,
+
     /// Path to config file (repo-context.toml or .r2p.yml).
-    #[arg(short = 'c', long, value_name = "FILE")]
+    #[arg(short = 'c', long, value_name = "FILE", value_hint = ValueHint::FilePath)]
     pub config: Option<PathBuf>,
 
+    /// Apply a built-in preset below explicit flags: security-audit, architecture-review,
+    /// deep-analysis, or quick-scan.
+    #[arg(
+        long,
+        value_name = "NAME",
+        value_parser = PossibleValuesParser::new(PRESET_NAMES.iter().copied()),
+        ignore_case = true
+    )]
+    pub preset: Option<String>,
+
     /// Include only these extensions (comma-separated, e.g., '.py,.ts').
     #[arg(short = 'i', long, value_name = "EXTS")]
     pub include_ext: Option<String>,
 
-    /// Exclude paths matching these globs (comma-separated).
+    /// Exclude paths matching these globs (comma-separated; `!glob` re-includes).
     #[arg(short = 'e', long, value_name = "GLOBS")]
     pub exclude_glob: Option<String>,
 
@@ -52,6 +82,23 @@ pub struct ExportArgs {
     #[arg(long)]
     pub follow_symlinks: bool,
 
+    /// Skip any file whose binary/minified inspection takes longer than this.
+    #[arg(long, value_name = "MS")]
+    pub file_scan_timeout_ms: Option<u64>,
+
+    /// Only scan files at most N directory levels deep (1 = repository root only).
+    #[arg(long, value_name = "N")]
+    pub max_depth: Option<usize>,
+
+    /// Include files with unknown extensions whose content looks like an included language.
+    #[arg(long)]
+    pub sniff_content: bool,
+
+    /// Include .env / .env.* files in the scan; their contents are always replaced with a
+    /// placeholder, even with --no-redact.
+    #[arg(long)]
+    pub include_env_files: bool,
+
     /// Include minified/bundled files.
     #[arg(long)]
     pub include_minified: bool,
@@ -72,12 +119,146 @@ pub struct ExportArgs {
     #[arg(long, value_name = "TOKENS")]
     pub min_chunk_tokens: Option<usize>,
 
-    /// Output format: 'prompt', 'rag', or 'both'.
-    #[arg(short = 'm', long, value_name = "MODE")]
+    /// Count tokens for this model: 'gpt-*' and 'text-embedding*' use its BPE
+    /// tokenizer when built with the 'tiktoken' feature.
+    #[arg(long, value_name = "MODEL")]
+    pub semantic_model: Option<String>,
+
+    /// How chunks fill --max-tokens: 'ranked' (priority order) or 'greedy' (largest first).
+    #[arg(
+        long,
+        value_name = "STRATEGY",
+        value_parser = PossibleValuesParser::new(["ranked", "greedy"]),
+        ignore_case = true
+    )]
+    pub packing_strategy: Option<String>,
+
+    /// Order of chunks in chunk outputs: 'priority' (default), 'path', 'language',
+    /// 'start_line', or 'modified' (most recently modified file first).
+    #[arg(
+        long,
+        value_name = "KEY",
+        value_parser = PossibleValuesParser::new(["priority", "path", "language", "start_line", "modified"]),
+        ignore_case = true
+    )]
+    pub sort_by: Option<String>,
+
+    /// Remove comments from chunk content (line numbers still refer to the original file).
+    #[arg(long)]
+    pub strip_comments: bool,
+
+    /// Expand tabs and trim trailing whitespace so formatting-only edits keep chunk IDs stable.
+    #[arg(long)]
+    pub normalize_whitespace: bool,
+
+    /// Also emit each definition's doc comment as a separate, higher-priority `doc:` chunk.
+    #[arg(long)]
+    pub extract_docs: bool,
+
+    /// Keep one file per group of byte-identical files (highest priority, then shortest path).
+    #[arg(long)]
+    pub dedup_files: bool,
+
+    /// Drop chunks that are near-duplicates of a higher-priority chunk in another file.
+    #[arg(long)]
+    pub dedup_chunks: bool,
+
+    /// Cosine similarity (0-1] at which --dedup-chunks treats chunks as duplicates (default 0.95).
+    #[arg(long, value_name = "SIMILARITY")]
+    pub dedup_threshold: Option<f64>,
+
+    /// Keep only chunks with a tag matching this glob (repeatable; all must match,
+    /// e.g. --require-tag 'def:*').
+    #[arg(long = "require-tag", value_name = "TAG")]
+    pub require_tags: Vec<String>,
+
+    /// Drop chunks with a tag matching this glob (repeatable).
+    #[arg(long = "exclude-tag", value_name = "TAG")]
+    pub exclude_tags: Vec<String>,
+
+    /// Output format: 'prompt', 'rag', 'both', 'html', 'langchain', 'llamaindex',
+    /// 'openai-batch', or 'editor-snippets'.
+    #[arg(short = 'm', long, value_name = "MODE", value_parser = mode_values(), ignore_case = true)]
     pub mode: Option<String>,
 
-    /// Directory for output files.
-    #[arg(short = 'o', long, value_name = "DIR")]
+    /// System prompt for each request in --mode openai-batch.
+    #[arg(long, value_name = "TEXT")]
+    pub system_prompt: Option<String>,
+
+    /// Model name for each request in --mode openai-batch.
+    #[arg(long, value_name = "MODEL")]
+    pub model: Option<String>,
+
+    /// Tera template to render the context pack with instead of the built-in layout.
+    #[arg(long, value_name = "PATH", value_hint = ValueHint::FilePath)]
+    pub template: Option<PathBuf>,
+
+    /// Also write an SPDX 2.3 SBOM (<repo>_sbom.spdx.json) of declared dependencies.
+    #[arg(long)]
+    pub sbom: bool,
+
+    /// Also bundle all written files into <repo>_context_<timestamp>.tar.gz with a
+    /// manifest.json of SHA-256 checksums (content hash instead of timestamp with
+    /// --no-timestamp).
+    #[arg(long)]
+    pub archive: bool,
+
+    /// Write <repo>_context_pack.md.gz and <repo>_chunks.jsonl.gz instead of plain text
+    /// (the report stays uncompressed).
+    #[arg(long)]
+    pub compress: bool,
+
+    /// Print GitHub Actions annotations for redacted secrets and export errors
+    /// (default: on when GITHUB_ACTIONS=true; --github-annotations=false disables).
+    #[arg(long, value_name = "BOOL", num_args = 0..=1, default_missing_value = "true")]
+    pub github_annotations: Option<bool>,
+
+    /// Run scan, ranking, chunking, and budgeting, then print the selection without writing files.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Scan and rank, then print what redaction would match in each selected file
+    /// without chunking or writing files.
+    #[arg(long, conflicts_with = "dry_run")]
+    pub dry_run_redaction: bool,
+
+    /// Reuse the scan cached under the user cache directory when it is younger than
+    /// this many seconds, the scan settings match, and no scanned file or directory
+    /// has changed size or modification time (default 60).
+    #[arg(long, value_name = "SECONDS")]
+    pub cache_ttl_seconds: Option<u64>,
+
+    /// Always rescan the repository instead of reading or writing the scan cache.
+    #[arg(long, conflicts_with = "cache_ttl_seconds")]
+    pub no_scan_cache: bool,
+
+    /// Re-export whenever a matching file under --path changes (Ctrl+C to stop).
+    #[arg(long, conflicts_with_all = ["repo", "dry_run", "dry_run_redaction"])]
+    pub watch: bool,
+
+    /// Print why each discovered file was included or dropped (tsv or json).
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        default_missing_value = "tsv",
+        value_parser = PossibleValuesParser::new(["tsv", "json"]),
+        ignore_case = true
+    )]
+    pub explain: Option<String>,
+
+    /// Merge this export's context pack with another repository's rendered pack
+    /// into <repo>_merged_context_pack.md (repeatable; needs --mode prompt or both).
+    #[arg(long = "merge-with", value_name = "PATH", value_hint = ValueHint::FilePath)]
+    pub merge_with: Vec<PathBuf>,
+
+    /// Print the per-signal ranking breakdown for one file (path relative to the repo root).
+    #[arg(long, value_name = "PATH", value_hint = ValueHint::FilePath)]
+    pub debug_ranking: Option<PathBuf>,
+
+    /// Directory for output files. May use {repo_name}, {date}, {git_commit}, and {mode};
+    /// a templated path is used as-is instead of getting a <repo_name>/ subdirectory.
+    #[arg(short = 'o', long, value_name = "DIR", value_hint = ValueHint::DirPath)]
     pub output_dir: Option<PathBuf>,
 
     /// Omit timestamps for reproducible diffs.
@@ -88,25 +269,97 @@ pub struct ExportArgs {
     #[arg(long, value_name = "DEPTH")]
     pub tree_depth: Option<usize>,
 
+    /// Show directories below this tree depth collapsed as `dir/ (N files)`.
+    #[arg(long, value_name = "DEPTH")]
+    pub tree_collapsed_depth: Option<usize>,
+
     /// Disable automatic secret/credential redaction.
     #[arg(long)]
     pub no_redact: bool,
 
     /// Redaction mode: fast|standard|paranoid|structure-safe.
-    #[arg(long, value_name = "MODE")]
+    #[arg(long, value_name = "MODE", value_parser = redaction_mode_values(), ignore_case = true)]
     pub redaction_mode: Option<String>,
 
     /// Scan mode: full|focused. Overrides interactive prompt.
-    #[arg(long, value_name = "MODE")]
+    #[arg(long, value_name = "MODE", value_parser = scan_mode_values(), ignore_case = true)]
     pub scan_mode: Option<String>,
 
     /// Focus file or module entry for non-interactive focused export.
     /// Example: --focus-file src/main.rs
-    #[arg(long, value_name = "PATH")]
+    #[arg(long, value_name = "PATH", value_hint = ValueHint::FilePath)]
     pub focus_file: Option<PathBuf>,
 }
 
 pub fn run(args: ExportArgs) -> Result<()> {
+    let annotate = args.github_annotations.unwrap_or_else(running_in_github_actions);
+    let result = run_export(&args, annotate);
+    if let Err(err) = &result {
+        if annotate {
+            println!("{}", error_annotation(&format!("repo-context export failed: {err:#}")));
+        }
+    }
+    result
+}
+
+fn run_export(args: &ExportArgs, annotate: bool) -> Result<()> {
+    let (merged, options) = prepare_export(args)?;
+    let max_tokens = merged.max_tokens;
+    let watch_config = args.watch.then(|| merged.clone());
+    let outcome = execute(merged, options.clone())?;
+
+    if args.dry_run {
+        print_dry_run(&outcome, max_tokens);
+    } else if args.dry_run_redaction {
+        print_redaction_dry_run(&outcome);
+    } else {
+        println!("Export complete:");
+        println!("  root: {}", outcome.root_path.display());
+        println!("  files: {}", outcome.stats.files_included);
+        println!("  chunks: {}", outcome.stats.chunks_created);
+        println!("  tokens: {}", outcome.stats.total_tokens_estimated);
+        println!("  chunk p90 tokens: {}", outcome.stats.chunk_distribution.p90_tokens);
+        for file in &outcome.output_files {
+            println!("  wrote: {}", file);
+        }
+    }
+    if outcome.stats.env_files_redacted > 0 {
+        eprintln!(
+            "Warning: {} .env file(s) detected; their contents were fully redacted",
+            outcome.stats.env_files_redacted
+        );
+    }
+    if annotate {
+        for annotation in redaction_annotations(&outcome.redactions) {
+            println!("{annotation}");
+        }
+    }
+
+    if let Some(format) = args.explain.as_deref() {
+        println!();
+        print_explain(&outcome.dispositions, format.eq_ignore_ascii_case("json"))?;
+    }
+
+    if let Some((path, priority, signals)) = &outcome.ranking_debug {
+        println!();
+        print_ranking_debug(path, *priority, signals);
+    }
+
+    if let Some(config) = watch_config {
+        watch_and_reexport(config, options, &outcome)?;
+    }
+
+    Ok(())
+}
+
+/// Resolve config file, preset, and flags into the merged config and
+/// execution options for one export.
+pub(super) fn prepare_export(args: &ExportArgs) -> Result<(Config, ExportExecutionOptions)> {
+    prepare_export_with(args, detect_ci_environment())
+}
+
+/// Like [`prepare_export`], with CI detection already done.
+fn prepare_export_with(args: &ExportArgs, in_ci: bool) -> Result<(Config, ExportExecutionOptions)> {
     if args.path.is_some() && args.repo.is_some() {
         anyhow::bail!("Cannot specify both --path and --repo");
     }
@@ -117,7 +370,15 @@ pub fn run(args: ExportArgs) -> Result<()> {
         _ => cwd.clone(),
     };
 
-    let file_config = load_config(&config_anchor, args.config.as_deref())?;
+    let mut file_config = load_config(&config_anchor, args.config.as_deref())?;
+    if in_ci {
+        eprintln!("info: CI environment detected; applying CI defaults");
+        let ci = ci_overrides(&file_config);
+        file_config = merge_cli_with_config(file_config, ci);
+    }
+    if let Some(name) = args.preset.as_deref() {
+        file_config = merge_cli_with_config(file_config, preset_overrides(name)?);
+    }
     let include_ext =
         parse_csv(&args.include_ext).map(|v| v.into_iter().map(normalize_extension).collect());
     let exclude_glob = parse_csv(&args.exclude_glob).map(|v| v.into_iter().collect());
@@ -128,63 +389,250 @@ pub fn run(args: ExportArgs) -> Result<()> {
         None
     };
     let cli_overrides = CliOverrides {
-        path: args.path,
-        repo_url: args.repo,
-        ref_: args.ref_,
+        path: args.path.clone(),
+        repo_url: args.repo.clone(),
+        ref_: args.ref_.clone(),
+        ssh_key_path: args.ssh_key.clone(),
         include_extensions: include_ext,
         exclude_globs: exclude_glob,
         max_file_bytes: args.max_file_bytes,
         max_total_bytes: args.max_total_bytes,
         respect_gitignore: if args.no_gitignore { Some(false) } else { None },
         follow_symlinks: if args.follow_symlinks { Some(true) } else { None },
+        file_scan_timeout_ms: args.file_scan_timeout_ms,
+        max_depth: args.max_depth,
+        sniff_content: if args.sniff_content { Some(true) } else { None },
+        include_env_files: if args.include_env_files { Some(true) } else { None },
         skip_minified: if args.include_minified { Some(false) } else { None },
         max_tokens: args.max_tokens,
         chunk_tokens: args.chunk_tokens,
         chunk_overlap: args.chunk_overlap,
         min_chunk_tokens: args.min_chunk_tokens,
+        semantic_model: args.semantic_model.clone(),
+        packing_strategy: parse_packing_strategy(args.packing_strategy.as_deref())?,
+        sort_by: parse_sort_key(args.sort_by.as_deref())?,
+        strip_comments: if args.strip_comments { Some(true) } else { None },
+        normalize_whitespace: if args.normalize_whitespace { Some(true) } else { None },
+        extract_docs: if args.extract_docs { Some(true) } else { None },
+        dedup_files: if args.dedup_files { Some(true) } else { None },
+        dedup_chunks: if args.dedup_chunks { Some(true) } else { None },
+        dedup_threshold: args.dedup_threshold,
+        require_tags: (!args.require_tags.is_empty()).then(|| args.require_tags.clone()),
+        exclude_tags: (!args.exclude_tags.is_empty()).then(|| args.exclude_tags.clone()),
         mode,
-        output_dir: args.output_dir,
+        output_dir: args.output_dir.clone(),
         tree_depth: args.tree_depth,
+        tree_collapsed_depth: args.tree_collapsed_depth,
         redact_secrets: if args.no_redact { Some(false) } else { None },
         redaction_mode,
     };
 
     let merged = merge_cli_with_config(file_config, cli_overrides);
+    for warning in validate_config(&merged)? {
+        eprintln!("Warning: {warning}");
+    }
 
     if merged.path.is_none() && merged.repo_url.is_none() {
         anyhow::bail!("Either --path or --repo must be specified");
     }
+    if !args.merge_with.is_empty() && !merged.mode.includes_prompt() {
+        anyhow::bail!("--merge-with needs a context pack; use --mode prompt or --mode both");
+    }
 
-    let scan_mode = parse_scan_mode(args.scan_mode.as_deref())?;
+    let mut scan_mode = parse_scan_mode(args.scan_mode.as_deref())?;
+    if args.watch || in_ci {
+        // Re-runs and CI jobs must not stop at the interactive scan-mode picker.
+        scan_mode = scan_mode.or(Some(ScanMode::Full));
+    }
 
-    let outcome = execute(
-        merged,
-        ExportExecutionOptions {
-            include_timestamp: !args.no_timestamp,
-            explicit_config_path: args.config.clone(),
-            scan_mode,
-            focus_path: args.focus_file.clone(),
-        },
-    )?;
+    let options = ExportExecutionOptions {
+        include_timestamp: !args.no_timestamp && !in_ci,
+        explicit_config_path: args.config.clone(),
+        scan_mode,
+        focus_path: args.focus_file.clone(),
+        template_path: args.template.clone(),
+        system_prompt: args.system_prompt.clone(),
+        model: args.model.clone(),
+        sbom: args.sbom,
+        dry_run: args.dry_run,
+        archive: args.archive,
+        compress: args.compress,
+        explain: args.explain.is_some(),
+        debug_ranking: args.debug_ranking.clone(),
+        merge_with: args.merge_with.clone(),
+        dry_run_redaction: args.dry_run_redaction,
+        // Watch re-exports run because files changed, so they must always rescan.
+        scan_cache_ttl: (!args.no_scan_cache && !args.watch).then(|| {
+            Duration::from_secs(args.cache_ttl_seconds.unwrap_or(DEFAULT_SCAN_CACHE_TTL_SECS))
+        }),
+    };
+    Ok((merged, options))
+}
 
-    println!("Export complete:");
+fn print_dry_run(outcome: &ExportOutcome, max_tokens: Option<usize>) {
+    println!("Dry run (no files written):");
     println!("  root: {}", outcome.root_path.display());
     println!("  files: {}", outcome.stats.files_included);
     println!("  chunks: {}", outcome.stats.chunks_created);
     println!("  tokens: {}", outcome.stats.total_tokens_estimated);
-    for file in outcome.output_files {
-        println!("  wrote: {}", file);
+    println!("  candidate tokens: {}", outcome.candidate_tokens);
+    match max_tokens {
+        Some(limit) => println!(
+            "  budget: {} ({})",
+            limit,
+            if outcome.candidate_tokens > limit { "exceeded" } else { "within budget" }
+        ),
+        None => println!("  budget: none"),
+    }
+    println!();
+    println!("path\tpriority\ttokens\treason");
+    for d in outcome.dispositions.iter().filter(|d| d.priority.is_some()) {
+        println!(
+            "{}\t{:.3}\t{}\t{}",
+            d.path,
+            d.priority.unwrap_or(0.0),
+            d.token_estimate.unwrap_or(0),
+            inclusion_reason(d)
+        );
+    }
+}
+
+/// Print how many matches of each redaction rule every selected file has.
+fn print_redaction_dry_run(outcome: &ExportOutcome) {
+    println!("Redaction dry run (no files written):");
+    println!("  root: {}", outcome.root_path.display());
+    let total: usize = outcome.redaction_matches.values().map(Vec::len).sum();
+    println!("  matches: {} in {} file(s)", total, outcome.redaction_matches.len());
+    println!();
+    for (path, matches) in &outcome.redaction_matches {
+        let mut per_rule: BTreeMap<&str, usize> = BTreeMap::new();
+        for m in matches {
+            *per_rule.entry(m.rule_name.as_str()).or_insert(0) += 1;
+        }
+        for (rule, count) in per_rule {
+            let noun = if count == 1 { "match" } else { "matches" };
+            println!("{count} {noun} of rule {rule} in file {path}");
+        }
+    }
+}
+
+/// Print every discovered file with its inclusion decision, as TSV or JSON.
+fn print_explain(dispositions: &[FileDisposition], json: bool) -> Result<()> {
+    if json {
+        let rows: Vec<serde_json::Value> = dispositions
+            .iter()
+            .map(|d| {
+                serde_json::json!({
+                    "path": d.path,
+                    "priority": d.priority,
+                    "tokens": d.token_estimate,
+                    "included": is_included(d),
+                    "reason": inclusion_reason(d),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+        return Ok(());
     }
 
+    println!("path\tpriority\ttokens\tincluded\treason");
+    for d in dispositions {
+        println!(
+            "{}\t{}\t{}\t{}\t{}",
+            d.path,
+            d.priority.map(|p| format!("{p:.3}")).unwrap_or_else(|| "-".to_string()),
+            d.token_estimate.map(|t| t.to_string()).unwrap_or_else(|| "-".to_string()),
+            is_included(d),
+            inclusion_reason(d)
+        );
+    }
     Ok(())
 }
 
+/// Print the ranking signals behind one file's priority as TSV.
+fn print_ranking_debug(path: &str, priority: f64, signals: &[RankingSignal]) {
+    println!("Ranking signals for {path} (priority {priority:.3}):");
+    println!("signal\traw\tnormalized\tweight\tcontribution");
+    for s in signals {
+        println!(
+            "{}\t{:.3}\t{:.3}\t{:.3}\t{:+.3}",
+            s.name, s.raw, s.normalized, s.weight, s.contribution
+        );
+    }
+}
+
+fn is_included(d: &FileDisposition) -> bool {
+    matches!(
+        d.reason,
+        FileDispositionReason::IncludedFull
+            | FileDispositionReason::IncludedChunked
+            | FileDispositionReason::IncludedSummaryOnly
+    )
+}
+
+/// Short reason a file was kept or dropped.
+///
+/// Kept files are `always_include` (readme/config/entrypoint, selected ahead
+/// of the token budget) or `budget_selected`; everything else reports its
+/// disposition reason (e.g. `dropped_token_budget`, `skipped_size`).
+fn inclusion_reason(d: &FileDisposition) -> &'static str {
+    if !is_included(d) {
+        return d.reason.as_str();
+    }
+    let always_include = d.notes.as_deref().is_some_and(|tags| {
+        tags.split(',').any(|tag| matches!(tag, "readme" | "config" | "entrypoint"))
+    });
+    if always_include {
+        "always_include"
+    } else {
+        "budget_selected"
+    }
+}
+
+// Possible values feed shell completion and `--help`; the parse_* functions
+// below still own the mapping to domain types.
+fn mode_values() -> PossibleValuesParser {
+    PossibleValuesParser::new([
+        PossibleValue::new("prompt"),
+        PossibleValue::new("rag"),
+        PossibleValue::new("both"),
+        PossibleValue::new("html"),
+        PossibleValue::new("langchain"),
+        PossibleValue::new("llamaindex"),
+        PossibleValue::new("openai-batch").alias("openai_batch"),
+        PossibleValue::new("editor-snippets").alias("editor_snippets"),
+    ])
+}
+
+fn redaction_mode_values() -> PossibleValuesParser {
+    PossibleValuesParser::new([
+        PossibleValue::new("fast"),
+        PossibleValue::new("standard"),
+        PossibleValue::new("paranoid"),
+        PossibleValue::new("structure-safe").alias("structure_safe"),
+    ])
+}
+
+fn scan_mode_values() -> PossibleValuesParser {
+    PossibleValuesParser::new([
+        PossibleValue::new("full").alias("f"),
+        PossibleValue::new("focused").alias("focus"),
+    ])
+}
+
 fn parse_mode(mode: Option<&str>) -> Result<OutputMode> {
     match mode.unwrap_or("both").trim().to_ascii_lowercase().as_str() {
         "prompt" => Ok(OutputMode::Prompt),
         "rag" => Ok(OutputMode::Rag),
         "both" => Ok(OutputMode::Both),
-        other => anyhow::bail!("Invalid mode '{other}'. Expected one of: prompt, rag, both"),
+        "html" => Ok(OutputMode::Html),
+        "langchain" => Ok(OutputMode::Langchain),
+        "llamaindex" => Ok(OutputMode::Llamaindex),
+        "openai-batch" | "openai_batch" => Ok(OutputMode::OpenaiBatch),
+        "editor-snippets" | "editor_snippets" => Ok(OutputMode::EditorSnippets),
+        other => anyhow::bail!(
+            "Invalid mode '{other}'. Expected one of: prompt, rag, both, html, langchain, llamaindex, openai-batch, editor-snippets"
+        ),
     }
 }
 
@@ -206,6 +654,35 @@ fn parse_redaction_mode(mode: Option<&str>) -> Result<RedactionMode> {
     }
 }
 
+fn parse_packing_strategy(strategy: Option<&str>) -> Result<Option<PackingStrategy>> {
+    match strategy {
+        None => Ok(None),
+        Some(s) => match s.trim().to_ascii_lowercase().as_str() {
+            "ranked" => Ok(Some(PackingStrategy::Ranked)),
+            "greedy" => Ok(Some(PackingStrategy::Greedy)),
+            other => {
+                anyhow::bail!("Invalid packing strategy '{other}'. Expected one of: ranked, greedy")
+            }
+        },
+    }
+}
+
+fn parse_sort_key(key: Option<&str>) -> Result<Option<ChunkSortKey>> {
+    match key {
+        None => Ok(None),
+        Some(s) => match s.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "priority" => Ok(Some(ChunkSortKey::Priority)),
+            "path" => Ok(Some(ChunkSortKey::Path)),
+            "language" => Ok(Some(ChunkSortKey::Language)),
+            "start_line" => Ok(Some(ChunkSortKey::StartLine)),
+            "modified" => Ok(Some(ChunkSortKey::Modified)),
+            other => anyhow::bail!(
+                "Invalid sort key '{other}'. Expected one of: priority, path, language, start_line, modified"
+            ),
+        },
+    }
+}
+
 fn parse_scan_mode(mode: Option<&str>) -> Result<Option<ScanMode>> {
     match mode {
         None => Ok(None),
@@ -230,3 +707,36 @@ fn normalize_extension(ext: String) -> String {
         trimmed.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    #[command(no_binary_name = true)]
+    struct Argv {
+        #[command(flatten)]
+        export: ExportArgs,
+    }
+
+    fn tree_depth(repo: &std::path::Path, flags: &[&str], in_ci: bool) -> usize {
+        let mut argv = vec!["--path", repo.to_str().unwrap()];
+        argv.extend(flags);
+        let args = Argv::try_parse_from(argv).unwrap().export;
+        prepare_export_with(&args, in_ci).unwrap().0.tree_depth
+    }
+
+    #[test]
+    fn ci_defaults_apply_beneath_config_file_and_flags() {
+        let repo = tempfile::tempdir().unwrap();
+        let default_depth = Config::default().tree_depth;
+        assert_eq!(tree_depth(repo.path(), &[], false), default_depth);
+        assert_eq!(tree_depth(repo.path(), &[], true), 2);
+        assert_eq!(tree_depth(repo.path(), &["--tree-depth", "7"], true), 7);
+
+        std::fs::write(repo.path().join("repo-context.toml"), "tree_depth = 5\n").unwrap();
+        assert_eq!(tree_depth(repo.path(), &[], true), 5);
+        assert_eq!(tree_depth(repo.path(), &["--tree-depth", "7"], true), 7);
+    }
+}
@@ -24,7 +24,7 @@ pub struct InfoArgs {
     #[arg(short = 'i', long, value_name = "EXTS")]
     pub include_ext: Option<String>,
 
-    /// Exclude paths matching these globs (comma-separated)
+    /// Exclude paths matching these globs (comma-separated; `!glob` re-includes)
     #[arg(short = 'e', long, value_name = "GLOBS")]
     pub exclude_glob: Option<String>,
 
@@ -73,6 +73,10 @@ pub fn run(args: InfoArgs) -> Result<()> {
         .respect_gitignore(config.respect_gitignore)
         .follow_symlinks(config.follow_symlinks)
         .skip_minified(config.skip_minified)
+        .file_scan_timeout_ms(config.file_scan_timeout_ms)
+        .max_depth(config.max_depth)
+        .sniff_content(config.sniff_content)
+        .include_env_files(config.include_env_files)
         .include_extensions(config.include_extensions.iter().cloned().collect())
         .exclude_globs(config.exclude_globs.iter().cloned().collect());
 
@@ -131,7 +135,7 @@ pub fn run(args: InfoArgs) -> Result<()> {
     // Directory tree with top-10 files highlighted
     let highlighted: HashSet<String> =
         ranked_files.iter().take(10).map(|f| f.relative_path.clone()).collect();
-    let tree = generate_tree(&root, 4, true, &highlighted)?;
+    let tree = generate_tree(&root, 4, None, true, &highlighted)?;
     println!("\n{}", tree);
 
     Ok(())
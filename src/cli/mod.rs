@@ -2,16 +2,26 @@
 //!
 //! Stable commands:
 //! - `export`: build a deterministic context pack from a repository
+//! - `batch`: export several repositories listed in a TOML batch file
+//! - `browse`: explore an exported chunks.jsonl in a terminal UI
 //! - `info`: inspect repository composition without exporting
+//! - `serve`: expose a repository to LLM agents as an MCP server over stdio
+//! - `completions`: print shell completion scripts
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use tracing::Level;
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
+mod batch;
+mod browse;
+mod completions;
 mod export;
 mod info;
+mod presets;
+mod serve;
 mod utils;
+mod watch;
 
 /// Convert repositories into LLM-friendly context packs
 #[derive(Parser)]
@@ -32,8 +42,20 @@ enum Commands {
     /// Export a repository as an LLM-friendly context pack
     Export(Box<export::ExportArgs>),
 
+    /// Export several repositories listed in a TOML batch file
+    Batch(batch::BatchArgs),
+
+    /// Browse an exported chunks.jsonl file in a terminal UI
+    Browse(browse::BrowseArgs),
+
     /// Display repository information without exporting
     Info(info::InfoArgs),
+
+    /// Serve a repository over the Model Context Protocol (stdio)
+    Serve(serve::ServeArgs),
+
+    /// Generate shell completions (bash, zsh, fish, powershell, elvish)
+    Completions(completions::CompletionsArgs),
 }
 
 /// Entry point for CLI execution.
@@ -63,6 +85,10 @@ pub fn run() -> Result<()> {
 
     match cli.command {
         Commands::Export(args) => export::run(*args),
+        Commands::Batch(args) => batch::run(args),
+        Commands::Browse(args) => browse::run(args),
         Commands::Info(args) => info::run(args),
+        Commands::Serve(args) => serve::run(args),
+        Commands::Completions(args) => completions::run(args),
     }
 }
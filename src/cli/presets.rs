@@ -0,0 +1,193 @@
+//! Built-in export presets (`export --preset <NAME>`).
+//!
+//! A preset is a [`CliOverrides`] layer merged above the config file and
+//! below explicit CLI flags, so `--preset quick-scan --max-tokens 50000`
+//! keeps the preset's other settings but uses the flag's token budget.
+
+use anyhow::Result;
+
+use crate::config::CliOverrides;
+use crate::domain::{Config, OutputMode, RedactionMode};
+
+/// Preset names accepted by `--preset`.
+pub const PRESET_NAMES: &[&str] =
+    &["security-audit", "architecture-review", "deep-analysis", "quick-scan"];
+
+/// Look up a preset by name.
+pub fn preset_overrides(name: &str) -> Result<CliOverrides> {
+    match name.trim().to_ascii_lowercase().as_str() {
+        "security-audit" => Ok(security_audit()),
+        "architecture-review" => Ok(architecture_review()),
+        "deep-analysis" => Ok(deep_analysis()),
+        "quick-scan" => Ok(quick_scan()),
+        other => {
+            anyhow::bail!("Unknown preset '{other}'. Expected one of: {}", PRESET_NAMES.join(", "))
+        }
+    }
+}
+
+/// `security-audit`: a large prompt budget with the strictest redaction.
+///
+/// - `max_tokens`: 200,000
+/// - `redact_secrets`: true, `redaction_mode`: paranoid
+/// - `skip_minified`: false (bundles can hide embedded credentials)
+/// - `mode`: prompt
+fn security_audit() -> CliOverrides {
+    CliOverrides {
+        max_tokens: Some(200_000),
+        redact_secrets: Some(true),
+        redaction_mode: Some(RedactionMode::Paranoid),
+        skip_minified: Some(false),
+        mode: Some(OutputMode::Prompt),
+        ..CliOverrides::default()
+    }
+}
+
+/// `architecture-review`: the full directory tree with a mid-sized budget.
+///
+/// - `tree_depth`: 12
+/// - `max_tokens`: 120,000
+/// - `chunk_tokens`: 1,200 (fewer, larger chunks keep modules intact)
+/// - `mode`: prompt
+fn architecture_review() -> CliOverrides {
+    CliOverrides {
+        tree_depth: Some(12),
+        max_tokens: Some(120_000),
+        chunk_tokens: Some(1_200),
+        mode: Some(OutputMode::Prompt),
+        ..CliOverrides::default()
+    }
+}
+
+/// `deep-analysis`: as much of the repository as the limits allow.
+///
+/// - `max_tokens`: 500,000
+/// - `max_file_bytes`: 5 MiB, `max_total_bytes`: 100 MB
+/// - `tree_depth`: 8
+/// - `mode`: both
+fn deep_analysis() -> CliOverrides {
+    CliOverrides {
+        max_tokens: Some(500_000),
+        max_file_bytes: Some(5 * 1_048_576),
+        max_total_bytes: Some(100_000_000),
+        tree_depth: Some(8),
+        mode: Some(OutputMode::Both),
+        ..CliOverrides::default()
+    }
+}
+
+/// `quick-scan`: a small, fast overview.
+///
+/// - `max_tokens`: 20,000
+/// - `max_file_bytes`: 200,000
+/// - `tree_depth`: 2
+/// - `redaction_mode`: fast
+/// - `mode`: prompt
+fn quick_scan() -> CliOverrides {
+    CliOverrides {
+        max_tokens: Some(20_000),
+        max_file_bytes: Some(200_000),
+        tree_depth: Some(2),
+        redaction_mode: Some(RedactionMode::Fast),
+        mode: Some(OutputMode::Prompt),
+        ..CliOverrides::default()
+    }
+}
+
+/// Defaults layered under the config file, preset, and flags when running in
+/// CI: each applies only where `file_config` kept the built-in default.
+///
+/// - `tree_depth`: 2
+///
+/// CI runs also skip timestamps and the interactive scan-mode picker; those
+/// are execution options, set in `prepare_export`.
+pub fn ci_overrides(file_config: &Config) -> CliOverrides {
+    let defaults = Config::default();
+    CliOverrides {
+        tree_depth: (file_config.tree_depth == defaults.tree_depth).then_some(2),
+        ..CliOverrides::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::merge_cli_with_config;
+
+    fn preset_config(name: &str) -> Config {
+        merge_cli_with_config(Config::default(), preset_overrides(name).expect("preset"))
+    }
+
+    #[test]
+    fn security_audit_preset_values() {
+        let config = preset_config("security-audit");
+        assert_eq!(config.max_tokens, Some(200_000));
+        assert!(config.redact_secrets);
+        assert_eq!(config.redaction_mode, RedactionMode::Paranoid);
+        assert!(!config.skip_minified);
+        assert_eq!(config.mode, OutputMode::Prompt);
+    }
+
+    #[test]
+    fn architecture_review_preset_values() {
+        let config = preset_config("architecture-review");
+        assert_eq!(config.tree_depth, 12);
+        assert_eq!(config.max_tokens, Some(120_000));
+        assert_eq!(config.chunk_tokens, 1_200);
+        assert_eq!(config.mode, OutputMode::Prompt);
+    }
+
+    #[test]
+    fn deep_analysis_preset_values() {
+        let config = preset_config("deep-analysis");
+        assert_eq!(config.max_tokens, Some(500_000));
+        assert_eq!(config.max_file_bytes, 5 * 1_048_576);
+        assert_eq!(config.max_total_bytes, 100_000_000);
+        assert_eq!(config.tree_depth, 8);
+        assert_eq!(config.mode, OutputMode::Both);
+    }
+
+    #[test]
+    fn quick_scan_preset_values() {
+        let config = preset_config("quick-scan");
+        assert_eq!(config.max_tokens, Some(20_000));
+        assert_eq!(config.max_file_bytes, 200_000);
+        assert_eq!(config.tree_depth, 2);
+        assert_eq!(config.redaction_mode, RedactionMode::Fast);
+        assert_eq!(config.mode, OutputMode::Prompt);
+    }
+
+    #[test]
+    fn explicit_flags_override_preset() {
+        let layered = merge_cli_with_config(Config::default(), quick_scan());
+        let merged = merge_cli_with_config(
+            layered,
+            CliOverrides { max_tokens: Some(50_000), ..CliOverrides::default() },
+        );
+        assert_eq!(merged.max_tokens, Some(50_000));
+        assert_eq!(merged.tree_depth, 2);
+    }
+
+    #[test]
+    fn unknown_preset_is_an_error() {
+        let err = preset_overrides("nope").expect_err("unknown preset");
+        assert!(err.to_string().contains("quick-scan"));
+    }
+
+    #[test]
+    fn ci_overrides_apply_below_config_file_and_explicit_flags() {
+        let ci = merge_cli_with_config(Config::default(), ci_overrides(&Config::default()));
+        assert_eq!(ci.tree_depth, 2);
+        assert_eq!(ci.max_tokens, Config::default().max_tokens);
+
+        let flagged = merge_cli_with_config(
+            ci,
+            CliOverrides { tree_depth: Some(6), ..CliOverrides::default() },
+        );
+        assert_eq!(flagged.tree_depth, 6);
+
+        let file_config = Config { tree_depth: 5, ..Config::default() };
+        let overrides = ci_overrides(&file_config);
+        assert_eq!(merge_cli_with_config(file_config, overrides).tree_depth, 5);
+    }
+}
@@ -0,0 +1,218 @@
+//! Serve command implementation (MCP over stdio).
+//!
+//! Speaks JSON-RPC 2.0, one message per line, as described by the Model
+//! Context Protocol stdio transport. Logs go to stderr so stdout stays a
+//! clean protocol stream.
+
+use anyhow::Result;
+use clap::Args;
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+
+use crate::app::snapshot::RepoSnapshot;
+use crate::config::{load_config, merge_cli_with_config, CliOverrides};
+
+const PROTOCOL_VERSION: &str = "2024-11-05";
+const DEFAULT_SEARCH_LIMIT: usize = 10;
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+#[derive(Args)]
+pub struct ServeArgs {
+    /// Local directory path to serve.
+    #[arg(value_name = "PATH", default_value = ".")]
+    pub path: PathBuf,
+
+    /// Path to config file (repo-context.toml).
+    #[arg(short = 'c', long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Disable automatic secret/credential redaction.
+    #[arg(long)]
+    pub no_redact: bool,
+
+    /// Don't widen sparse search_chunks queries with prefix completions (auth → authentication).
+    #[arg(long)]
+    pub no_query_expand: bool,
+}
+
+pub fn run(args: ServeArgs) -> Result<()> {
+    let root = args.path.canonicalize()?;
+    if !root.is_dir() {
+        anyhow::bail!("Path is not a directory: {}", root.display());
+    }
+
+    let file_config = load_config(&root, args.config.as_deref())?;
+    let config = merge_cli_with_config(
+        file_config,
+        CliOverrides {
+            path: Some(root.clone()),
+            redact_secrets: if args.no_redact { Some(false) } else { None },
+            ..CliOverrides::default()
+        },
+    );
+
+    let mut snapshot = RepoSnapshot::build(&config)?;
+    snapshot.query_expansion = !args.no_query_expand;
+    tracing::info!(
+        "Serving {} files / {} chunks from {}",
+        snapshot.files.len(),
+        snapshot.chunks.len(),
+        root.display()
+    );
+
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout().lock();
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => handle_message(&snapshot, &message),
+            Err(e) => Some(error_response(Value::Null, PARSE_ERROR, &format!("Parse error: {e}"))),
+        };
+        if let Some(response) = response {
+            writeln!(stdout, "{}", response)?;
+            stdout.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle one JSON-RPC message. Notifications (no `id`) get no response.
+fn handle_message(snapshot: &RepoSnapshot, message: &Value) -> Option<Value> {
+    let id = message.get("id").cloned();
+    let Some(method) = message.get("method").and_then(Value::as_str) else {
+        return Some(error_response(id.unwrap_or(Value::Null), INVALID_REQUEST, "Missing method"));
+    };
+    let id = id?;
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+    let result = match method {
+        "initialize" => Ok(json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "capabilities": { "tools": {} },
+            "serverInfo": { "name": "repo-context", "version": env!("CARGO_PKG_VERSION") },
+        })),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+        "tools/call" => call_tool(snapshot, &params),
+        other => Err((METHOD_NOT_FOUND, format!("Method not found: {other}"))),
+    };
+
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => error_response(id, code, &message),
+    })
+}
+
+fn tool_definitions() -> Value {
+    json!([
+        {
+            "name": "search_chunks",
+            "description": "Search repository chunks by keyword relevance (BM25).",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "description": "Search terms" },
+                    "limit": { "type": "integer", "minimum": 1, "description": "Maximum results" },
+                },
+                "required": ["query"],
+            },
+        },
+        {
+            "name": "get_file_content",
+            "description": "Read a repository file by relative path (secrets redacted).",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string", "description": "Path relative to the repository root" },
+                },
+                "required": ["path"],
+            },
+        },
+        {
+            "name": "list_files",
+            "description": "List repository files in priority order.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "limit": { "type": "integer", "minimum": 1, "description": "Maximum files" },
+                },
+            },
+        },
+    ])
+}
+
+fn call_tool(snapshot: &RepoSnapshot, params: &Value) -> Result<Value, (i64, String)> {
+    let name = params
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or((INVALID_PARAMS, "Missing tool name".to_string()))?;
+    let arguments = params.get("arguments").cloned().unwrap_or_else(|| json!({}));
+    let limit = arguments.get("limit").and_then(Value::as_u64).map(|n| n as usize);
+
+    let outcome = match name {
+        "search_chunks" => {
+            let query = arguments
+                .get("query")
+                .and_then(Value::as_str)
+                .ok_or((INVALID_PARAMS, "search_chunks requires 'query'".to_string()))?;
+            let hits = snapshot.search(query, limit.unwrap_or(DEFAULT_SEARCH_LIMIT));
+            let results: Vec<Value> = hits
+                .iter()
+                .map(|hit| {
+                    json!({
+                        "id": hit.chunk.id,
+                        "path": hit.chunk.path,
+                        "start_line": hit.chunk.start_line,
+                        "end_line": hit.chunk.end_line,
+                        "score": (hit.score * 1000.0).round() / 1000.0,
+                        "content": hit.chunk.content,
+                    })
+                })
+                .collect();
+            Ok(json!(results).to_string())
+        }
+        "get_file_content" => {
+            let path = arguments
+                .get("path")
+                .and_then(Value::as_str)
+                .ok_or((INVALID_PARAMS, "get_file_content requires 'path'".to_string()))?;
+            snapshot.read_file(path).map_err(|e| format!("{e:#}"))
+        }
+        "list_files" => {
+            let files: Vec<Value> = snapshot
+                .files
+                .iter()
+                .take(limit.unwrap_or(usize::MAX))
+                .map(|f| {
+                    json!({
+                        "path": f.relative_path,
+                        "language": f.language,
+                        "priority": (f.priority * 1000.0).round() / 1000.0,
+                        "tags": f.tags,
+                    })
+                })
+                .collect();
+            Ok(json!(files).to_string())
+        }
+        other => return Err((INVALID_PARAMS, format!("Unknown tool: {other}"))),
+    };
+
+    // Tool failures are reported in-band so the model can see them.
+    Ok(match outcome {
+        Ok(text) => json!({ "content": [{ "type": "text", "text": text }], "isError": false }),
+        Err(text) => json!({ "content": [{ "type": "text", "text": text }], "isError": true }),
+    })
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
@@ -0,0 +1,148 @@
+//! `export --watch` loop.
+//!
+//! Re-runs the export whenever a watched file changes. Events are debounced
+//! so a multi-file save triggers one export, and Ctrl+C is deferred until
+//! any in-progress export has finished writing.
+
+use anyhow::{Context, Result};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::app::export::{execute, ExportExecutionOptions, ExportOutcome};
+use crate::domain::Config;
+use crate::scan::scanner::FileScanner;
+
+const DEBOUNCE: Duration = Duration::from_millis(500);
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Watch `config.path` and re-export on changes until interrupted.
+pub(super) fn watch_and_reexport(
+    config: Config,
+    options: ExportExecutionOptions,
+    initial: &ExportOutcome,
+) -> Result<()> {
+    let root = initial.root_path.clone();
+    let ignored_dirs: Vec<PathBuf> = initial
+        .output_files
+        .iter()
+        .filter_map(|f| Path::new(f).parent().map(Path::to_path_buf))
+        .map(|dir| dir.canonicalize().unwrap_or(dir))
+        .collect();
+    // Only changes the export would pick up trigger a re-export.
+    let scanner = FileScanner::from_config(root.clone(), &config);
+
+    let stop = Arc::new(AtomicBool::new(false));
+    {
+        let stop = Arc::clone(&stop);
+        ctrlc::set_handler(move || stop.store(true, Ordering::SeqCst))
+            .context("Failed to install Ctrl+C handler")?;
+    }
+
+    let (tx, rx) = mpsc::channel::<notify::Result<Event>>();
+    let mut watcher = notify::recommended_watcher(tx).context("Failed to start file watcher")?;
+    watcher
+        .watch(&root, RecursiveMode::Recursive)
+        .with_context(|| format!("Failed to watch {}", root.display()))?;
+    println!("[watch] watching {} (Ctrl+C to stop)", root.display());
+
+    let mut last_tokens = initial.stats.total_tokens_estimated;
+    while !stop.load(Ordering::SeqCst) {
+        let first = match rx.recv_timeout(POLL_INTERVAL) {
+            Ok(event) => event,
+            Err(mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        };
+
+        let mut changed = BTreeSet::new();
+        collect_changes(first, &scanner, &ignored_dirs, &mut changed);
+        // Keep draining until the tree has been quiet for the debounce window.
+        while let Ok(event) = rx.recv_timeout(DEBOUNCE) {
+            collect_changes(event, &scanner, &ignored_dirs, &mut changed);
+        }
+        if changed.is_empty() {
+            continue;
+        }
+
+        match execute(config.clone(), options.clone()) {
+            Ok(outcome) => {
+                let tokens = outcome.stats.total_tokens_estimated;
+                let delta = tokens as i64 - last_tokens as i64;
+                last_tokens = tokens;
+                println!(
+                    "[watch] re-exported: {} file{} changed, {:+} new tokens",
+                    changed.len(),
+                    if changed.len() == 1 { "" } else { "s" },
+                    delta
+                );
+            }
+            Err(e) => eprintln!("[watch] export failed: {e:#}"),
+        }
+    }
+
+    println!("[watch] stopped");
+    Ok(())
+}
+
+fn collect_changes(
+    event: notify::Result<Event>,
+    scanner: &FileScanner,
+    ignored_dirs: &[PathBuf],
+    changed: &mut BTreeSet<PathBuf>,
+) {
+    let event = match event {
+        Ok(event) => event,
+        Err(e) => {
+            tracing::warn!("File watcher error: {e}");
+            return;
+        }
+    };
+    if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)) {
+        return;
+    }
+    for path in event.paths {
+        if is_watched(&path, scanner, ignored_dirs) {
+            changed.insert(path);
+        }
+    }
+}
+
+fn is_watched(path: &Path, scanner: &FileScanner, ignored_dirs: &[PathBuf]) -> bool {
+    !ignored_dirs.iter().any(|dir| path.starts_with(dir)) && scanner.would_include(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::fs;
+
+    #[test]
+    fn watches_only_paths_the_scan_would_include() {
+        let repo = tempfile::tempdir().unwrap();
+        let root = repo.path();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(root.join(".gitignore"), "generated/\n").unwrap();
+        let config = Config {
+            include_extensions: HashSet::from([".rs".to_string()]),
+            exclude_globs: HashSet::from(["vendor/**".to_string()]),
+            ..Config::default()
+        };
+        let scanner = FileScanner::from_config(root.to_path_buf(), &config);
+        let ignored = vec![root.join("out")];
+        let watched = |rel: &str| is_watched(&root.join(rel), &scanner, &ignored);
+
+        assert!(watched("src/lib.rs"));
+        assert!(watched("Dockerfile"));
+        assert!(!watched("out/lib.rs"));
+        assert!(!watched(".git/index"));
+        assert!(!watched("notes.txt"));
+        assert!(!watched("vendor/dep.rs"));
+        assert!(!watched("generated/api.rs"));
+        assert!(!watched("node_modules/pkg/index.rs"));
+    }
+}
@@ -4,8 +4,13 @@
 
 use crate::domain::Config;
 use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+static ENV_VAR_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}").expect("valid env var regex"));
 
 /// Loads configuration from a file or discovers one in the repository.
 ///
@@ -38,7 +43,7 @@ pub fn load_config(repo_root: &Path, config_path: Option<&Path>) -> Result<Confi
 
     // Python lines 380-395: Parse config, but silently return default on error
     // if auto-discovered (not explicitly provided by user).
-    let parsed = match ext.as_str() {
+    let mut parsed = match ext.as_str() {
         "toml" => match parse_toml_config(&content, &config_file) {
             Ok(cfg) => cfg,
             Err(e) => {
@@ -69,9 +74,158 @@ pub fn load_config(repo_root: &Path, config_path: Option<&Path>) -> Result<Confi
         }
     };
 
+    interpolate_env_vars(&mut parsed);
     Ok(parsed)
 }
 
+/// Expands `${ENV_VAR_NAME}` references in config string fields.
+///
+/// Only fields where interpolation makes sense are touched: `repo_url`,
+/// `output_dir`, and each `exclude_globs` entry. A reference to a variable
+/// that is not set is replaced with an empty string and logged as a warning.
+pub fn interpolate_env_vars(config: &mut Config) {
+    if let Some(url) = config.repo_url.as_deref() {
+        config.repo_url = Some(interpolate_str(url, "repo_url"));
+    }
+
+    if let Some(dir) = config.output_dir.to_str() {
+        if ENV_VAR_PATTERN.is_match(dir) {
+            config.output_dir = PathBuf::from(interpolate_str(dir, "output_dir"));
+        }
+    }
+
+    if config.exclude_globs.iter().any(|glob| ENV_VAR_PATTERN.is_match(glob)) {
+        config.exclude_globs = config
+            .exclude_globs
+            .iter()
+            .map(|glob| interpolate_str(glob, "exclude_globs"))
+            .collect();
+    }
+}
+
+fn interpolate_str(value: &str, field: &str) -> String {
+    ENV_VAR_PATTERN
+        .replace_all(value, |caps: &regex::Captures<'_>| {
+            let name = &caps[1];
+            match std::env::var(name) {
+                Ok(val) => val,
+                Err(_) => {
+                    tracing::warn!(
+                        "Environment variable '{}' referenced in config field '{}' is not set",
+                        name,
+                        field
+                    );
+                    String::new()
+                }
+            }
+        })
+        .into_owned()
+}
+
+/// A non-fatal problem found by [`validate_config`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigWarning {
+    /// Config field the warning refers to.
+    pub field: &'static str,
+    /// Human-readable explanation.
+    pub message: String,
+}
+
+impl std::fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Token budgets below this are almost always a typo.
+const LOW_MAX_TOKENS_THRESHOLD: usize = 1000;
+
+/// Validates a merged configuration before running an export.
+///
+/// Values that can never work are reported as errors; values that are legal
+/// but likely mistakes are returned as warnings for the caller to surface.
+///
+/// # Errors
+/// Returns an error for a zero token budget, a chunk overlap larger than the
+/// chunk size, a dedup threshold outside `(0, 1]`, or an exclude or tag
+/// filter glob that does not compile.
+pub fn validate_config(config: &Config) -> Result<Vec<ConfigWarning>> {
+    let mut warnings = Vec::new();
+
+    if let Some(max_tokens) = config.max_tokens {
+        if max_tokens == 0 {
+            anyhow::bail!("Invalid config: max_tokens must be greater than 0");
+        }
+        if max_tokens < LOW_MAX_TOKENS_THRESHOLD {
+            warnings.push(ConfigWarning {
+                field: "max_tokens",
+                message: format!(
+                    "{max_tokens} is very low and will likely exclude nearly every file"
+                ),
+            });
+        }
+    }
+
+    if config.chunk_tokens == 0 {
+        anyhow::bail!("Invalid config: chunk_tokens must be greater than 0");
+    }
+    if config.chunk_overlap > config.chunk_tokens {
+        anyhow::bail!(
+            "Invalid config: chunk_overlap ({}) must not exceed chunk_tokens ({})",
+            config.chunk_overlap,
+            config.chunk_tokens
+        );
+    }
+    if config.min_chunk_tokens > config.chunk_tokens {
+        warnings.push(ConfigWarning {
+            field: "min_chunk_tokens",
+            message: format!(
+                "{} is larger than chunk_tokens ({}); small-chunk coalescing will merge aggressively",
+                config.min_chunk_tokens, config.chunk_tokens
+            ),
+        });
+    }
+
+    if !(config.dedup_threshold > 0.0 && config.dedup_threshold <= 1.0) {
+        anyhow::bail!(
+            "Invalid config: dedup_threshold ({}) must be greater than 0 and at most 1",
+            config.dedup_threshold
+        );
+    }
+
+    if !(0.0..=1.0).contains(&config.priority_decay) {
+        anyhow::bail!(
+            "Invalid config: priority_decay ({}) must be between 0 and 1",
+            config.priority_decay
+        );
+    }
+
+    if config.file_scan_timeout_ms == Some(0) {
+        anyhow::bail!("Invalid config: file_scan_timeout_ms must be greater than 0");
+    }
+    if config.max_depth == Some(0) {
+        anyhow::bail!("Invalid config: max_depth must be at least 1");
+    }
+    // `stitch_budget_fraction` and `rerank_top_k` have no checks because the
+    // config has no such fields: `stitch:` tags are only rendered, and BM25
+    // reranking scores every chunk without a top-k cut-off.
+
+    for tag in config.require_tags.iter().chain(&config.exclude_tags) {
+        globset::Glob::new(tag)
+            .with_context(|| format!("Invalid config: tag filter '{tag}' is not a valid glob"))?;
+    }
+
+    let mut globs: Vec<&String> = config.exclude_globs.iter().collect();
+    globs.sort();
+    for glob in globs {
+        globset::Glob::new(glob).with_context(|| {
+            format!("Invalid config: exclude_globs entry '{glob}' is not a valid glob")
+        })?;
+    }
+
+    Ok(warnings)
+}
+
 /// Parse TOML config, supporting nested [repo-context] or [r2p] sections.
 ///
 /// Matches Python's _parse_toml behavior (lines 262-267).
@@ -248,4 +402,102 @@ mod tests {
         assert!(globs.contains("build"), "should contain build");
         assert!(globs.contains("node_modules"), "should contain node_modules");
     }
+
+    #[test]
+    fn test_validate_config_defaults_ok() {
+        let warnings = validate_config(&Config::default()).expect("valid");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_validate_config_zero_max_tokens_is_error() {
+        let cfg = Config { max_tokens: Some(0), ..Config::default() };
+        assert!(validate_config(&cfg).is_err());
+    }
+
+    #[test]
+    fn test_validate_config_low_max_tokens_warns() {
+        let cfg = Config { max_tokens: Some(500), ..Config::default() };
+        let warnings = validate_config(&cfg).expect("valid");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].field, "max_tokens");
+    }
+
+    #[test]
+    fn test_validate_config_overlap_exceeds_chunk_tokens_is_error() {
+        let cfg = Config { chunk_tokens: 100, chunk_overlap: 200, ..Config::default() };
+        let err = validate_config(&cfg).expect_err("overlap > chunk size");
+        assert!(err.to_string().contains("chunk_overlap"));
+    }
+
+    #[test]
+    fn test_validate_config_min_chunk_tokens_above_chunk_tokens_warns() {
+        let cfg = Config { chunk_tokens: 300, min_chunk_tokens: 400, ..Config::default() };
+        let warnings = validate_config(&cfg).expect("valid");
+        assert!(warnings.iter().any(|w| w.field == "min_chunk_tokens"));
+    }
+
+    #[test]
+    fn test_validate_config_invalid_glob_is_error() {
+        let cfg =
+            Config { exclude_globs: ["src/[unclosed".to_string()].into(), ..Config::default() };
+        let err = validate_config(&cfg).expect_err("bad glob");
+        assert!(format!("{err:#}").contains("src/[unclosed"));
+    }
+
+    #[test]
+    fn test_interpolate_env_vars_in_repo_url_and_output_dir() {
+        std::env::set_var("RC_TEST_INTERP_HOST", "github.com");
+        std::env::set_var("RC_TEST_INTERP_OUT", "/tmp/rc-interp");
+
+        let mut cfg = Config {
+            repo_url: Some("https://${RC_TEST_INTERP_HOST}/owner/repo".to_string()),
+            output_dir: PathBuf::from("${RC_TEST_INTERP_OUT}/packs"),
+            ..Config::default()
+        };
+        interpolate_env_vars(&mut cfg);
+
+        assert_eq!(cfg.repo_url.as_deref(), Some("https://github.com/owner/repo"));
+        assert_eq!(cfg.output_dir, PathBuf::from("/tmp/rc-interp/packs"));
+    }
+
+    #[test]
+    fn test_interpolate_env_vars_in_exclude_globs() {
+        std::env::set_var("RC_TEST_INTERP_GLOB_DIR", "generated");
+
+        let mut cfg = Config {
+            exclude_globs: ["${RC_TEST_INTERP_GLOB_DIR}/**".to_string(), "dist/**".to_string()]
+                .into_iter()
+                .collect(),
+            ..Config::default()
+        };
+        interpolate_env_vars(&mut cfg);
+
+        assert!(cfg.exclude_globs.contains("generated/**"));
+        assert!(cfg.exclude_globs.contains("dist/**"));
+    }
+
+    #[test]
+    fn test_interpolate_missing_env_var_becomes_empty() {
+        std::env::remove_var("RC_TEST_INTERP_MISSING");
+
+        let mut cfg = Config {
+            repo_url: Some("https://example.com/${RC_TEST_INTERP_MISSING}repo".to_string()),
+            ..Config::default()
+        };
+        interpolate_env_vars(&mut cfg);
+
+        assert_eq!(cfg.repo_url.as_deref(), Some("https://example.com/repo"));
+    }
+
+    #[test]
+    fn test_load_config_interpolates_env_vars() {
+        std::env::set_var("RC_TEST_INTERP_LOAD", "vendor");
+        let tmp = TempDir::new().expect("tmp");
+        let path = tmp.path().join("repo-context.toml");
+        fs::write(&path, "exclude_globs = [\"${RC_TEST_INTERP_LOAD}/**\"]\n").expect("write");
+
+        let cfg = load_config(tmp.path(), None).expect("config");
+        assert!(cfg.exclude_globs.contains("vendor/**"));
+    }
 }
@@ -3,7 +3,9 @@
 //! CLI argument merging with config.
 
 use crate::config::loader::load_config;
-use crate::domain::{Config, OutputMode, RedactionMode};
+use crate::domain::{
+    ChunkSortKey, Config, OutputMode, PackingStrategy, RedactionMode, SshKeyConfig,
+};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
@@ -13,6 +15,7 @@ pub struct CliOverrides {
     pub path: Option<PathBuf>,
     pub repo_url: Option<String>,
     pub ref_: Option<String>,
+    pub ssh_key_path: Option<PathBuf>,
     pub include_extensions: Option<HashSet<String>>,
     pub exclude_globs: Option<HashSet<String>>,
     pub max_file_bytes: Option<u64>,
@@ -20,13 +23,29 @@ pub struct CliOverrides {
     pub respect_gitignore: Option<bool>,
     pub follow_symlinks: Option<bool>,
     pub skip_minified: Option<bool>,
+    pub file_scan_timeout_ms: Option<u64>,
+    pub max_depth: Option<usize>,
+    pub sniff_content: Option<bool>,
+    pub include_env_files: Option<bool>,
     pub max_tokens: Option<usize>,
     pub chunk_tokens: Option<usize>,
     pub chunk_overlap: Option<usize>,
     pub min_chunk_tokens: Option<usize>,
+    pub semantic_model: Option<String>,
+    pub packing_strategy: Option<PackingStrategy>,
+    pub sort_by: Option<ChunkSortKey>,
+    pub strip_comments: Option<bool>,
+    pub normalize_whitespace: Option<bool>,
+    pub extract_docs: Option<bool>,
+    pub dedup_files: Option<bool>,
+    pub dedup_chunks: Option<bool>,
+    pub dedup_threshold: Option<f64>,
+    pub require_tags: Option<Vec<String>>,
+    pub exclude_tags: Option<Vec<String>>,
     pub mode: Option<OutputMode>,
     pub output_dir: Option<PathBuf>,
     pub tree_depth: Option<usize>,
+    pub tree_collapsed_depth: Option<usize>,
     pub redact_secrets: Option<bool>,
     pub redaction_mode: Option<RedactionMode>,
 }
@@ -44,6 +63,12 @@ pub fn merge_cli_with_config(mut base_config: Config, cli: CliOverrides) -> Conf
     if let Some(ref_) = cli.ref_ {
         base_config.ref_ = Some(ref_);
     }
+    if let Some(private_key_path) = cli.ssh_key_path {
+        // A key from the command line replaces the configured one entirely:
+        // its public key and passphrase belong to a different key.
+        base_config.ssh_key =
+            Some(SshKeyConfig { private_key_path, public_key_path: None, passphrase: None });
+    }
 
     if let Some(include_extensions) = cli.include_extensions {
         base_config.include_extensions = include_extensions;
@@ -64,6 +89,18 @@ pub fn merge_cli_with_config(mut base_config: Config, cli: CliOverrides) -> Conf
     if let Some(follow_symlinks) = cli.follow_symlinks {
         base_config.follow_symlinks = follow_symlinks;
     }
+    if let Some(file_scan_timeout_ms) = cli.file_scan_timeout_ms {
+        base_config.file_scan_timeout_ms = Some(file_scan_timeout_ms);
+    }
+    if let Some(max_depth) = cli.max_depth {
+        base_config.max_depth = Some(max_depth);
+    }
+    if let Some(sniff_content) = cli.sniff_content {
+        base_config.sniff_content = sniff_content;
+    }
+    if let Some(include_env_files) = cli.include_env_files {
+        base_config.include_env_files = include_env_files;
+    }
     if let Some(skip_minified) = cli.skip_minified {
         base_config.skip_minified = skip_minified;
     }
@@ -80,6 +117,39 @@ pub fn merge_cli_with_config(mut base_config: Config, cli: CliOverrides) -> Conf
     if let Some(min_chunk_tokens) = cli.min_chunk_tokens {
         base_config.min_chunk_tokens = min_chunk_tokens;
     }
+    if let Some(semantic_model) = cli.semantic_model {
+        base_config.semantic_model = Some(semantic_model);
+    }
+    if let Some(packing_strategy) = cli.packing_strategy {
+        base_config.packing_strategy = packing_strategy;
+    }
+    if let Some(sort_by) = cli.sort_by {
+        base_config.sort_by = sort_by;
+    }
+    if let Some(strip_comments) = cli.strip_comments {
+        base_config.strip_comments = strip_comments;
+    }
+    if let Some(normalize_whitespace) = cli.normalize_whitespace {
+        base_config.normalize_whitespace = normalize_whitespace;
+    }
+    if let Some(extract_docs) = cli.extract_docs {
+        base_config.extract_docs = extract_docs;
+    }
+    if let Some(dedup_files) = cli.dedup_files {
+        base_config.dedup_files = dedup_files;
+    }
+    if let Some(dedup_chunks) = cli.dedup_chunks {
+        base_config.dedup_chunks = dedup_chunks;
+    }
+    if let Some(dedup_threshold) = cli.dedup_threshold {
+        base_config.dedup_threshold = dedup_threshold;
+    }
+    if let Some(require_tags) = cli.require_tags {
+        base_config.require_tags = require_tags;
+    }
+    if let Some(exclude_tags) = cli.exclude_tags {
+        base_config.exclude_tags = exclude_tags;
+    }
     if let Some(mode) = cli.mode {
         base_config.mode = mode;
     }
@@ -89,6 +159,9 @@ pub fn merge_cli_with_config(mut base_config: Config, cli: CliOverrides) -> Conf
     if let Some(tree_depth) = cli.tree_depth {
         base_config.tree_depth = tree_depth;
     }
+    if let Some(tree_collapsed_depth) = cli.tree_collapsed_depth {
+        base_config.tree_collapsed_depth = Some(tree_collapsed_depth);
+    }
     if let Some(redact_secrets) = cli.redact_secrets {
         base_config.redact_secrets = redact_secrets;
     }
@@ -132,6 +205,15 @@ pub fn merge_repo_config(
     if config.max_total_bytes == defaults.max_total_bytes {
         config.max_total_bytes = repo_config.max_total_bytes;
     }
+    if config.file_scan_timeout_ms.is_none() {
+        config.file_scan_timeout_ms = repo_config.file_scan_timeout_ms;
+    }
+    if config.max_depth.is_none() {
+        config.max_depth = repo_config.max_depth;
+    }
+    if config.sniff_content == defaults.sniff_content {
+        config.sniff_content = repo_config.sniff_content;
+    }
     if config.max_tokens.is_none() && repo_config.max_tokens.is_some() {
         config.max_tokens = repo_config.max_tokens;
     }
@@ -144,12 +226,54 @@ pub fn merge_repo_config(
     if config.min_chunk_tokens == defaults.min_chunk_tokens {
         config.min_chunk_tokens = repo_config.min_chunk_tokens;
     }
+    if config.semantic_model.is_none() {
+        config.semantic_model = repo_config.semantic_model;
+    }
+    if config.packing_strategy == defaults.packing_strategy {
+        config.packing_strategy = repo_config.packing_strategy;
+    }
+    if config.sort_by == defaults.sort_by {
+        config.sort_by = repo_config.sort_by;
+    }
+    if config.strip_comments == defaults.strip_comments {
+        config.strip_comments = repo_config.strip_comments;
+    }
+    if config.normalize_whitespace == defaults.normalize_whitespace {
+        config.normalize_whitespace = repo_config.normalize_whitespace;
+    }
+    if config.extract_docs == defaults.extract_docs {
+        config.extract_docs = repo_config.extract_docs;
+    }
+    if config.dedup_files == defaults.dedup_files {
+        config.dedup_files = repo_config.dedup_files;
+    }
+    if config.dedup_chunks == defaults.dedup_chunks {
+        config.dedup_chunks = repo_config.dedup_chunks;
+    }
+    if config.dedup_threshold == defaults.dedup_threshold {
+        config.dedup_threshold = repo_config.dedup_threshold;
+    }
+    if config.priority_decay == defaults.priority_decay {
+        config.priority_decay = repo_config.priority_decay;
+    }
+    if config.custom_tree_sitter_queries.is_empty() {
+        config.custom_tree_sitter_queries = repo_config.custom_tree_sitter_queries;
+    }
+    if config.require_tags.is_empty() {
+        config.require_tags = repo_config.require_tags;
+    }
+    if config.exclude_tags.is_empty() {
+        config.exclude_tags = repo_config.exclude_tags;
+    }
     if config.mode == defaults.mode {
         config.mode = repo_config.mode;
     }
     if config.tree_depth == defaults.tree_depth {
         config.tree_depth = repo_config.tree_depth;
     }
+    if config.tree_collapsed_depth.is_none() {
+        config.tree_collapsed_depth = repo_config.tree_collapsed_depth;
+    }
     if config.ranking_weights.readme == defaults.ranking_weights.readme {
         config.ranking_weights = repo_config.ranking_weights;
     }
@@ -6,5 +6,5 @@
 pub mod loader;
 pub mod merge;
 
-pub use loader::load_config;
+pub use loader::{interpolate_env_vars, load_config, validate_config, ConfigWarning};
 pub use merge::{merge_cli_with_config, merge_repo_config, CliOverrides};
@@ -1,8 +1,10 @@
 #![allow(missing_docs)]
 
-use crate::domain::{OutputMode, RankingWeights, RedactionConfig, RedactionMode};
+use crate::domain::{
+    ChunkSortKey, OutputMode, PackingStrategy, RankingWeights, RedactionConfig, RedactionMode,
+};
 use serde::{de, Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
 /// Main configuration for repo-context.
@@ -14,6 +16,18 @@ pub struct Config {
     pub repo_url: Option<String>,
     #[serde(default, alias = "ref")]
     pub ref_: Option<String>,
+    /// HTTP(S) proxy for remote clones; overrides `HTTPS_PROXY` / `HTTP_PROXY`.
+    #[serde(default)]
+    pub proxy_url: Option<String>,
+    /// Comma-separated hosts that bypass the proxy; overrides `NO_PROXY`.
+    #[serde(default)]
+    pub no_proxy: Option<String>,
+    /// Self-hosted Gitea / Forgejo hosts, in addition to `gitea.com` and `codeberg.org`.
+    #[serde(default)]
+    pub gitea_hosts: Vec<String>,
+    /// SSH key for `git@` clones; falls back to `SSH_KEY_PATH`, then the SSH agent.
+    #[serde(default)]
+    pub ssh_key: Option<SshKeyConfig>,
 
     #[serde(
         default = "default_include_extensions",
@@ -39,6 +53,21 @@ pub struct Config {
     pub follow_symlinks: bool,
     #[serde(default = "default_true")]
     pub skip_minified: bool,
+    /// Skip a file whose binary/minified inspection takes longer than this.
+    #[serde(default)]
+    pub file_scan_timeout_ms: Option<u64>,
+    /// Only scan files at most this many directory levels below the root
+    /// (1 = root-level files only).
+    #[serde(default)]
+    pub max_depth: Option<usize>,
+    /// Include files with a missing or non-code extension when their first
+    /// bytes look like an included language (shebang, `fn`, `def`, ...).
+    #[serde(default)]
+    pub sniff_content: bool,
+    /// Include `.env` / `.env.*` files. Their contents are always replaced
+    /// with a placeholder, even with redaction off.
+    #[serde(default)]
+    pub include_env_files: bool,
 
     pub max_tokens: Option<usize>,
 
@@ -48,9 +77,56 @@ pub struct Config {
     pub chunk_overlap: usize,
     #[serde(default = "default_min_chunk_tokens")]
     pub min_chunk_tokens: usize,
+    /// Model the chunks are counted for; `gpt-*` and `text-embedding*` names
+    /// count tokens with its BPE tokenizer (`tiktoken` feature).
+    #[serde(default)]
+    pub semantic_model: Option<String>,
 
     #[serde(default)]
     pub full_inventory: bool,
+    #[serde(default)]
+    pub packing_strategy: PackingStrategy,
+    /// Order of chunks in the chunk outputs.
+    #[serde(default)]
+    pub sort_by: ChunkSortKey,
+
+    /// Remove comments from chunk content (line ranges still refer to the original file).
+    #[serde(default)]
+    pub strip_comments: bool,
+
+    /// Expand tabs and trim trailing whitespace and blank edge lines in chunk content.
+    #[serde(default)]
+    pub normalize_whitespace: bool,
+
+    /// Emit each definition's doc comment as a separate `doc:` chunk.
+    #[serde(default)]
+    pub extract_docs: bool,
+
+    /// Keep only one file out of each group with identical content.
+    #[serde(default)]
+    pub dedup_files: bool,
+
+    /// Drop chunks that are near-duplicates of a higher-priority chunk in another file.
+    #[serde(default)]
+    pub dedup_chunks: bool,
+    /// Cosine similarity at or above which two chunks count as duplicates.
+    #[serde(default = "default_dedup_threshold")]
+    pub dedup_threshold: f64,
+    /// Lower chunk priority by position in the file; the last chunk gets
+    /// `1 - priority_decay` of the file's priority (0.0 = no decay).
+    #[serde(default)]
+    pub priority_decay: f64,
+    /// Tree-sitter node kinds that replace a language's built-in chunk
+    /// boundaries, e.g. `rust = ["function_item", "impl_item"]`.
+    #[serde(default)]
+    pub custom_tree_sitter_queries: HashMap<String, Vec<String>>,
+
+    /// Keep only chunks with a tag matching every one of these globs.
+    #[serde(default)]
+    pub require_tags: Vec<String>,
+    /// Drop chunks with a tag matching any of these globs.
+    #[serde(default)]
+    pub exclude_tags: Vec<String>,
 
     #[serde(default)]
     pub mode: OutputMode,
@@ -58,6 +134,9 @@ pub struct Config {
     pub output_dir: PathBuf,
     #[serde(default = "default_tree_depth")]
     pub tree_depth: usize,
+    /// Directories deeper than this are rendered collapsed in the tree.
+    #[serde(default)]
+    pub tree_collapsed_depth: Option<usize>,
 
     #[serde(default = "default_true")]
     pub redact_secrets: bool,
@@ -79,6 +158,10 @@ impl Default for Config {
             path: None,
             repo_url: None,
             ref_: None,
+            proxy_url: None,
+            no_proxy: None,
+            gitea_hosts: Vec::new(),
+            ssh_key: None,
             include_extensions: default_include_extensions(),
             exclude_globs: default_exclude_globs(),
             max_file_bytes: default_max_file_bytes(),
@@ -86,14 +169,32 @@ impl Default for Config {
             respect_gitignore: true,
             follow_symlinks: false,
             skip_minified: true,
+            file_scan_timeout_ms: None,
+            max_depth: None,
+            sniff_content: false,
+            include_env_files: false,
             max_tokens: None,
             chunk_tokens: default_chunk_tokens(),
             chunk_overlap: default_chunk_overlap(),
             min_chunk_tokens: default_min_chunk_tokens(),
+            semantic_model: None,
             full_inventory: false,
+            packing_strategy: PackingStrategy::Ranked,
+            sort_by: ChunkSortKey::Priority,
+            strip_comments: false,
+            normalize_whitespace: false,
+            extract_docs: false,
+            dedup_files: false,
+            dedup_chunks: false,
+            dedup_threshold: default_dedup_threshold(),
+            priority_decay: 0.0,
+            custom_tree_sitter_queries: HashMap::new(),
+            require_tags: Vec::new(),
+            exclude_tags: Vec::new(),
             mode: OutputMode::Both,
             output_dir: default_output_dir(),
             tree_depth: default_tree_depth(),
+            tree_collapsed_depth: None,
             redact_secrets: true,
             redaction_mode: RedactionMode::Standard,
             ranking_weights: RankingWeights::default(),
@@ -111,6 +212,16 @@ pub struct ModuleConfig {
     pub css_files: Vec<PathBuf>,
 }
 
+/// SSH key used to authenticate `git@host:...` clones.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SshKeyConfig {
+    pub private_key_path: PathBuf,
+    #[serde(default)]
+    pub public_key_path: Option<PathBuf>,
+    #[serde(default)]
+    pub passphrase: Option<String>,
+}
+
 fn default_true() -> bool {
     true
 }
@@ -129,6 +240,9 @@ fn default_chunk_overlap() -> usize {
 fn default_min_chunk_tokens() -> usize {
     200
 }
+fn default_dedup_threshold() -> f64 {
+    0.95
+}
 fn default_output_dir() -> PathBuf {
     std::env::var_os("HOME")
         .map(PathBuf::from)
@@ -181,6 +295,9 @@ pub fn default_include_extensions() -> HashSet<String> {
         ".ini",
         ".cfg",
         ".html",
+        ".jinja",
+        ".jinja2",
+        ".j2",
         ".css",
         ".scss",
         ".less",
@@ -14,8 +14,12 @@ pub enum FileDispositionReason {
     SkippedGitignore,
     SkippedGlob,
     SkippedMinified,
+    SkippedTimeout,
+    SkippedDuplicateSymlink,
+    SkippedDepth,
     DroppedByteBudget,
     DroppedTokenBudget,
+    DroppedDuplicateContent,
     ExcludedNoiseDir,
     ErrorReadingMetadata,
 }
@@ -34,8 +38,12 @@ impl FileDispositionReason {
             Self::SkippedGitignore => "skipped_gitignore",
             Self::SkippedGlob => "skipped_glob",
             Self::SkippedMinified => "skipped_minified",
+            Self::SkippedTimeout => "skipped_timeout",
+            Self::SkippedDuplicateSymlink => "skipped_duplicate_symlink",
+            Self::SkippedDepth => "skipped_depth",
             Self::DroppedByteBudget => "dropped_byte_budget",
             Self::DroppedTokenBudget => "dropped_token_budget",
+            Self::DroppedDuplicateContent => "dropped_duplicate_content",
             Self::ExcludedNoiseDir => "excluded_noise_dir",
             Self::ErrorReadingMetadata => "error_reading_metadata",
         }
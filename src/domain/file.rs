@@ -35,4 +35,20 @@ pub struct FileInfo {
     /// Whether this is documentation.
     #[serde(default)]
     pub is_doc: bool,
+    /// Whether this is a dotenv file (`.env`, `.env.*`) holding raw secrets.
+    #[serde(default)]
+    pub is_env_file: bool,
+    /// Whether the file was reached through a symbolic link.
+    #[serde(default)]
+    pub is_symlink: bool,
+    /// The symlink's target as stored in the link, for symlinked files.
+    #[serde(default)]
+    pub symlink_target: Option<String>,
+    /// Estimated cyclomatic complexity (0 for non-code files).
+    #[serde(default)]
+    pub complexity_estimate: usize,
+    /// Signals behind `priority` (`readme:0.90 + complexity:0.03 = 0.93`);
+    /// only filled in when the ranker is asked for explanations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority_explanation: Option<String>,
 }
@@ -27,6 +27,7 @@ pub fn get_language(extension: &str, filename: &str) -> String {
         ".json" => "json",
         ".ini" | ".cfg" => "ini",
         ".html" => "html",
+        ".jinja" | ".jinja2" | ".j2" => "jinja",
         ".css" => "css",
         ".scss" => "scss",
         ".less" => "less",
@@ -11,12 +11,16 @@ mod redaction;
 mod stats;
 
 pub use chunk::Chunk;
-pub use config::{default_exclude_globs, default_include_extensions, Config, ModuleConfig};
+pub use config::{
+    default_exclude_globs, default_include_extensions, Config, ModuleConfig, SshKeyConfig,
+};
 pub use disposition::{FileDisposition, FileDispositionReason};
 pub use file::FileInfo;
 pub use language::get_language;
-pub use output::{OutputMode, RedactionMode};
+pub use output::{ChunkSortKey, OutputMode, PackingStrategy, RedactionMode};
 pub use ranking::RankingWeights;
 #[allow(unused_imports)]
-pub use redaction::{CustomRedactionRule, EntropyConfig, ParanoidConfig, RedactionConfig};
-pub use stats::{ScanStats, REPORT_SCHEMA_VERSION};
+pub use redaction::{
+    CustomRedactionRule, EntropyConfig, ParanoidConfig, RedactionConfig, RedactionPlaceholderFormat,
+};
+pub use stats::{compute_chunk_percentiles, ChunkPercentiles, ScanStats, REPORT_SCHEMA_VERSION};
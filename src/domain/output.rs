@@ -11,6 +11,38 @@ pub enum OutputMode {
     /// Generate both prompt and RAG outputs (default).
     #[default]
     Both,
+    /// Generate a self-contained HTML page for human browsing.
+    Html,
+    /// Generate LangChain `Document` JSON Lines.
+    Langchain,
+    /// Generate LlamaIndex `TextNode` JSON Lines.
+    Llamaindex,
+    /// Generate OpenAI Batch API request JSON Lines.
+    #[serde(rename = "openai-batch")]
+    OpenaiBatch,
+    /// Generate Monaco / VS Code editor snippet JSON Lines.
+    #[serde(rename = "editor-snippets")]
+    EditorSnippets,
+}
+
+impl OutputMode {
+    /// Whether this mode renders the prompt context pack.
+    pub fn includes_prompt(self) -> bool {
+        matches!(self, Self::Prompt | Self::Both)
+    }
+
+    /// Whether this mode emits retrieval chunks (JSONL-style outputs).
+    pub fn includes_rag(self) -> bool {
+        matches!(
+            self,
+            Self::Rag
+                | Self::Both
+                | Self::Langchain
+                | Self::Llamaindex
+                | Self::OpenaiBatch
+                | Self::EditorSnippets
+        )
+    }
 }
 
 /// Redaction mode controls aggressiveness and syntax safety.
@@ -27,3 +59,31 @@ pub enum RedactionMode {
     /// Redaction with AST validation for syntax safety.
     StructureSafe,
 }
+
+/// How chunks are selected when a token budget is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PackingStrategy {
+    /// Take chunks in priority order, skipping any that no longer fit (default).
+    #[default]
+    Ranked,
+    /// First-fit decreasing: place the largest chunks first to fill the budget.
+    Greedy,
+}
+
+/// Order of chunks in the chunk outputs (`--sort-by`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ChunkSortKey {
+    /// Highest priority first, then path and line (default).
+    #[default]
+    Priority,
+    /// Path, then start line.
+    Path,
+    /// Language name, then priority order.
+    Language,
+    /// Start line, then path.
+    StartLine,
+    /// Most recently modified file first, then path and line.
+    Modified,
+}
@@ -31,6 +31,17 @@ pub struct RankingWeights {
     pub lock_file: f64,
     #[serde(default = "w_vendored")]
     pub vendored: f64,
+    #[serde(default = "w_complexity")]
+    pub complexity_weight: f64,
+    /// Bonus added to the `src/lib.rs` / `src/main.rs` of Cargo workspace members.
+    #[serde(default = "w_workspace_crate_root")]
+    pub workspace_crate_root: f64,
+    /// Bonus added to GitHub Actions workflow files on top of `config`.
+    #[serde(default = "w_ci_workflow_boost")]
+    pub ci_workflow_boost: f64,
+    /// Blend weight for each ranking plugin, matched by position.
+    #[serde(default)]
+    pub plugin_weights: Vec<f64>,
 }
 
 impl Default for RankingWeights {
@@ -49,6 +60,10 @@ impl Default for RankingWeights {
             generated: w_generated(),
             lock_file: w_lock_file(),
             vendored: w_vendored(),
+            complexity_weight: w_complexity(),
+            workspace_crate_root: w_workspace_crate_root(),
+            ci_workflow_boost: w_ci_workflow_boost(),
+            plugin_weights: Vec::new(),
         }
     }
 }
@@ -92,3 +107,12 @@ fn w_lock_file() -> f64 {
 fn w_vendored() -> f64 {
     0.10
 }
+fn w_complexity() -> f64 {
+    0.05
+}
+fn w_workspace_crate_root() -> f64 {
+    0.05
+}
+fn w_ci_workflow_boost() -> f64 {
+    0.15
+}
@@ -21,6 +21,29 @@ pub struct RedactionConfig {
     pub source_safe_patterns: Vec<String>,
     #[serde(default = "default_true_redaction")]
     pub structure_safe_redaction: bool,
+    #[serde(default)]
+    pub placeholder_format: RedactionPlaceholderFormat,
+}
+
+/// How rule placeholders such as `[AWS_ACCESS_KEY_REDACTED]` are written.
+/// Entropy and paranoid redactions use the same format, with the rule names
+/// `entropy_detected` and `paranoid_redacted`.
+///
+/// In TOML: `placeholder_format = "xml"`, or
+/// `placeholder_format = { custom = "<<{rule}>>" }`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RedactionPlaceholderFormat {
+    /// `[AWS_ACCESS_KEY_REDACTED]` (the rules' own placeholders).
+    #[default]
+    Bracketed,
+    /// `<SECRET type="aws_access_key">`.
+    Xml,
+    /// `REDACTED_1`, `REDACTED_2`, ... numbered per redacted text.
+    Numbered,
+    /// Template with `{rule}` (rule name), `{RULE}` (upper-cased), and `{n}`
+    /// (sequence number), e.g. `[{RULE}_REDACTED]`.
+    Custom(String),
 }
 
 /// One custom redaction rule loaded from config.
@@ -63,6 +86,7 @@ impl Default for RedactionConfig {
             safe_file_patterns: default_safe_file_patterns(),
             source_safe_patterns: default_source_safe_patterns(),
             structure_safe_redaction: true,
+            placeholder_format: RedactionPlaceholderFormat::default(),
         }
     }
 }
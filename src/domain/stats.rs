@@ -1,5 +1,6 @@
 #![allow(missing_docs)]
 
+use super::Chunk;
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 
@@ -25,6 +26,12 @@ pub struct ScanStats {
     pub files_skipped_glob: usize,
     #[serde(default)]
     pub files_skipped_minified: usize,
+    /// Files whose content inspection exceeded the per-file scan timeout.
+    #[serde(default)]
+    pub files_skipped_timeout: usize,
+    /// Files below the scanner's `max_depth`.
+    #[serde(default)]
+    pub files_skipped_depth: usize,
     #[serde(default)]
     pub files_skipped: usize,
     pub files_dropped_budget: usize,
@@ -62,9 +69,169 @@ pub struct ScanStats {
     pub redacted_chunks: usize,
     #[serde(default)]
     pub redacted_files: usize,
+    #[serde(default)]
+    pub chunks_deduplicated: usize,
+    /// Files dropped because another file had identical content.
+    #[serde(default)]
+    pub files_deduplicated: usize,
+    /// Files with some, but not all, chunks kept under the token budget.
+    #[serde(default)]
+    pub partial_files: usize,
+    /// `.env` files whose entire content was replaced by redaction.
+    #[serde(default)]
+    pub env_files_redacted: usize,
+    /// Token sizes of the candidate chunks, before the token budget.
+    #[serde(default)]
+    pub chunk_distribution: ChunkPercentiles,
+}
+
+/// Percentiles (nearest rank) and mean of chunk token estimates.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChunkPercentiles {
+    pub p50_tokens: usize,
+    pub p90_tokens: usize,
+    pub p99_tokens: usize,
+    pub p100_tokens: usize,
+    pub mean_tokens: f64,
+    /// Number of chunks the distribution was computed over.
+    #[serde(default)]
+    pub chunk_count: usize,
+}
+
+/// Token distribution of `chunks`; all zero when there are none.
+pub fn compute_chunk_percentiles(chunks: &[Chunk]) -> ChunkPercentiles {
+    let mut tokens: Vec<usize> = chunks.iter().map(|c| c.token_estimate).collect();
+    if tokens.is_empty() {
+        return ChunkPercentiles::default();
+    }
+    tokens.sort_unstable();
+    let rank = |percentile: usize| tokens[(tokens.len() * percentile).div_ceil(100).max(1) - 1];
+    ChunkPercentiles {
+        p50_tokens: rank(50),
+        p90_tokens: rank(90),
+        p99_tokens: rank(99),
+        p100_tokens: rank(100),
+        mean_tokens: tokens.iter().sum::<usize>() as f64 / tokens.len() as f64,
+        chunk_count: tokens.len(),
+    }
+}
+
+impl std::ops::Add for ScanStats {
+    type Output = ScanStats;
+
+    fn add(self, other: ScanStats) -> ScanStats {
+        self.merge(other)
+    }
 }
 
 impl ScanStats {
+    /// Combine the statistics of two scans, e.g. the repositories of a batch.
+    ///
+    /// Counters, byte totals, and processing time are summed;
+    /// `languages_detected` and `redaction_counts` are summed per key; and
+    /// `dropped_files` are appended. Percentiles cannot be combined without
+    /// the chunks, so `chunk_distribution` keeps the larger of each (an upper
+    /// bound) and a mean weighted by each side's `chunk_count`.
+    pub fn merge(mut self, other: ScanStats) -> ScanStats {
+        // Destructured so that a new field cannot be left out of the merge.
+        let ScanStats {
+            files_discovered,
+            files_scanned,
+            files_included,
+            files_skipped_size,
+            files_skipped_binary,
+            files_skipped_extension,
+            files_skipped_gitignore,
+            files_skipped_glob,
+            files_skipped_minified,
+            files_skipped_timeout,
+            files_skipped_depth,
+            files_skipped,
+            files_dropped_budget,
+            candidate_files,
+            files_selected_prompt,
+            files_selected_rag,
+            total_bytes_scanned,
+            total_bytes_discovered,
+            total_bytes_candidates,
+            total_bytes_included,
+            chunks_created,
+            prompt_chunks_rendered,
+            rag_chunks_rendered,
+            total_tokens_estimated,
+            total_tokens_estimated_prompt,
+            total_tokens_estimated_rag,
+            languages_detected,
+            processing_time_seconds,
+            dropped_files,
+            redaction_counts,
+            redacted_chunks,
+            redacted_files,
+            chunks_deduplicated,
+            files_deduplicated,
+            partial_files,
+            env_files_redacted,
+            chunk_distribution,
+        } = other;
+
+        let distribution = &mut self.chunk_distribution;
+        let (left, right) =
+            (distribution.chunk_count as f64, chunk_distribution.chunk_count as f64);
+        distribution.mean_tokens = if left + right > 0.0 {
+            (distribution.mean_tokens * left + chunk_distribution.mean_tokens * right)
+                / (left + right)
+        } else {
+            0.0
+        };
+        distribution.p50_tokens = distribution.p50_tokens.max(chunk_distribution.p50_tokens);
+        distribution.p90_tokens = distribution.p90_tokens.max(chunk_distribution.p90_tokens);
+        distribution.p99_tokens = distribution.p99_tokens.max(chunk_distribution.p99_tokens);
+        distribution.p100_tokens = distribution.p100_tokens.max(chunk_distribution.p100_tokens);
+        distribution.chunk_count += chunk_distribution.chunk_count;
+
+        self.files_discovered += files_discovered;
+        self.files_scanned += files_scanned;
+        self.files_included += files_included;
+        self.files_skipped_size += files_skipped_size;
+        self.files_skipped_binary += files_skipped_binary;
+        self.files_skipped_extension += files_skipped_extension;
+        self.files_skipped_gitignore += files_skipped_gitignore;
+        self.files_skipped_glob += files_skipped_glob;
+        self.files_skipped_minified += files_skipped_minified;
+        self.files_skipped_timeout += files_skipped_timeout;
+        self.files_skipped_depth += files_skipped_depth;
+        self.files_skipped += files_skipped;
+        self.files_dropped_budget += files_dropped_budget;
+        self.candidate_files += candidate_files;
+        self.files_selected_prompt += files_selected_prompt;
+        self.files_selected_rag += files_selected_rag;
+        self.total_bytes_scanned += total_bytes_scanned;
+        self.total_bytes_discovered += total_bytes_discovered;
+        self.total_bytes_candidates += total_bytes_candidates;
+        self.total_bytes_included += total_bytes_included;
+        self.chunks_created += chunks_created;
+        self.prompt_chunks_rendered += prompt_chunks_rendered;
+        self.rag_chunks_rendered += rag_chunks_rendered;
+        self.total_tokens_estimated += total_tokens_estimated;
+        self.total_tokens_estimated_prompt += total_tokens_estimated_prompt;
+        self.total_tokens_estimated_rag += total_tokens_estimated_rag;
+        for (language, count) in languages_detected {
+            *self.languages_detected.entry(language).or_insert(0) += count;
+        }
+        self.processing_time_seconds += processing_time_seconds;
+        self.dropped_files.extend(dropped_files);
+        for (rule, count) in redaction_counts {
+            *self.redaction_counts.entry(rule).or_insert(0) += count;
+        }
+        self.redacted_chunks += redacted_chunks;
+        self.redacted_files += redacted_files;
+        self.chunks_deduplicated += chunks_deduplicated;
+        self.files_deduplicated += files_deduplicated;
+        self.partial_files += partial_files;
+        self.env_files_redacted += env_files_redacted;
+        self
+    }
+
     /// Produce a stable JSON value for report emission.
     pub fn to_report_value(&self) -> serde_json::Value {
         let mut langs: Vec<(&String, &usize)> = self.languages_detected.iter().collect();
@@ -101,14 +268,33 @@ impl ScanStats {
             "languages_detected": languages_detected,
             "redaction_counts": self.redaction_counts,
             "processing_time_seconds": self.processing_time_seconds,
+            "chunk_distribution": self.chunk_distribution,
         });
 
+        if self.files_skipped_timeout > 0 {
+            value["files_skipped"]["timeout"] = serde_json::json!(self.files_skipped_timeout);
+        }
+        if self.files_skipped_depth > 0 {
+            value["files_skipped"]["depth"] = serde_json::json!(self.files_skipped_depth);
+        }
         if self.redacted_files > 0 {
             value["redacted_files"] = serde_json::json!(self.redacted_files);
         }
         if self.redacted_chunks > 0 {
             value["redacted_chunks"] = serde_json::json!(self.redacted_chunks);
         }
+        if self.partial_files > 0 {
+            value["partial_files"] = serde_json::json!(self.partial_files);
+        }
+        if self.env_files_redacted > 0 {
+            value["env_files_redacted"] = serde_json::json!(self.env_files_redacted);
+        }
+        if self.files_deduplicated > 0 {
+            value["files_deduplicated"] = serde_json::json!(self.files_deduplicated);
+        }
+        if self.chunks_deduplicated > 0 {
+            value["chunks_deduplicated"] = serde_json::json!(self.chunks_deduplicated);
+        }
         if !self.dropped_files.is_empty() {
             value["dropped_files"] = serde_json::json!(self.dropped_files);
         }
@@ -116,3 +302,128 @@ impl ScanStats {
         value
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{compute_chunk_percentiles, ChunkPercentiles, ScanStats};
+    use crate::domain::Chunk;
+    use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+    fn chunk_with_tokens(token_estimate: usize) -> Chunk {
+        Chunk {
+            id: format!("c{token_estimate}"),
+            path: "src/lib.rs".to_string(),
+            language: "rust".to_string(),
+            start_line: 1,
+            end_line: 1,
+            content: String::new(),
+            priority: 0.5,
+            tags: BTreeSet::new(),
+            token_estimate,
+            file_id: String::new(),
+            chunk_index: 0,
+            chunks_in_file: 1,
+            byte_start: None,
+            byte_end: None,
+            content_sha256: String::new(),
+            file_sha256: String::new(),
+        }
+    }
+
+    #[test]
+    fn chunk_percentiles_use_nearest_rank() {
+        // Shuffled 1..=100 tokens, so each percentile equals its rank.
+        let chunks: Vec<Chunk> = (0..100).map(|i| chunk_with_tokens((i * 37) % 100 + 1)).collect();
+        assert_eq!(
+            compute_chunk_percentiles(&chunks),
+            ChunkPercentiles {
+                p50_tokens: 50,
+                p90_tokens: 90,
+                p99_tokens: 99,
+                p100_tokens: 100,
+                mean_tokens: 50.5,
+                chunk_count: 100,
+            }
+        );
+
+        let skewed: Vec<Chunk> =
+            [10, 10, 10, 10, 10, 10, 10, 10, 10, 400].into_iter().map(chunk_with_tokens).collect();
+        let percentiles = compute_chunk_percentiles(&skewed);
+        assert_eq!(
+            (percentiles.p50_tokens, percentiles.p90_tokens, percentiles.p99_tokens),
+            (10, 10, 400)
+        );
+        assert_eq!(percentiles.mean_tokens, 49.0);
+        assert_eq!(compute_chunk_percentiles(&[]), ChunkPercentiles::default());
+    }
+
+    #[test]
+    fn merging_sums_counters_and_unions_languages() {
+        let a = ScanStats {
+            files_scanned: 10,
+            files_included: 4,
+            total_bytes_included: 1_000,
+            total_tokens_estimated: 300,
+            languages_detected: HashMap::from([("rust".to_string(), 3), ("toml".to_string(), 1)]),
+            redaction_counts: BTreeMap::from([("jwt".to_string(), 1)]),
+            dropped_files: vec![HashMap::from([("path".to_string(), "a.bin".into())])],
+            ..ScanStats::default()
+        };
+        let b = ScanStats {
+            files_scanned: 5,
+            files_included: 2,
+            files_skipped_binary: 1,
+            total_bytes_included: 500,
+            total_tokens_estimated: 120,
+            languages_detected: HashMap::from([("python".to_string(), 2)]),
+            redaction_counts: BTreeMap::from([("jwt".to_string(), 2)]),
+            dropped_files: vec![HashMap::from([("path".to_string(), "b.bin".into())])],
+            ..ScanStats::default()
+        };
+
+        let merged = a.clone().merge(b.clone());
+
+        assert_eq!(merged.files_scanned, 15);
+        assert_eq!(merged.files_included, 6);
+        assert_eq!(merged.files_skipped_binary, 1);
+        assert_eq!(merged.total_bytes_included, 1_500);
+        assert_eq!(merged.total_tokens_estimated, 420);
+        assert_eq!(
+            merged.languages_detected,
+            HashMap::from([
+                ("rust".to_string(), 3),
+                ("toml".to_string(), 1),
+                ("python".to_string(), 2)
+            ])
+        );
+        assert_eq!(merged.redaction_counts["jwt"], 3);
+        assert_eq!(merged.dropped_files.len(), 2);
+        assert_eq!((a + b).to_report_value(), merged.to_report_value());
+    }
+
+    #[test]
+    fn merging_weights_the_mean_by_the_measured_chunks() {
+        let distribution = |tokens: &[usize]| {
+            compute_chunk_percentiles(
+                &tokens.iter().copied().map(chunk_with_tokens).collect::<Vec<_>>(),
+            )
+        };
+        // The budget kept fewer chunks than were measured; the mean must
+        // still weight each side by its candidates.
+        let a = ScanStats {
+            chunks_created: 1,
+            chunk_distribution: distribution(&[10, 10, 10]),
+            ..ScanStats::default()
+        };
+        let b = ScanStats {
+            chunks_created: 1,
+            chunk_distribution: distribution(&[50]),
+            ..ScanStats::default()
+        };
+
+        let merged = a.merge(b).chunk_distribution;
+        assert_eq!(merged.mean_tokens, 20.0);
+        assert_eq!(merged.chunk_count, 4);
+        assert_eq!(merged.p100_tokens, 50);
+    }
+}
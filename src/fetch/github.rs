@@ -2,10 +2,11 @@
 //!
 //! Provides functionality to clone GitHub repositories to temporary directories.
 
-use crate::fetch::RepoContext;
+use crate::domain::SshKeyConfig;
+use crate::fetch::{ProxySettings, RepoContext};
 use crate::utils::redact_url_credentials;
 use anyhow::{Context, Result};
-use git2::{FetchOptions, ObjectType, Repository};
+use git2::{Cred, CredentialType, FetchOptions, ObjectType, RemoteCallbacks, Repository};
 use std::env;
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -15,13 +16,20 @@ use std::time::{SystemTime, UNIX_EPOCH};
 /// # Arguments
 /// * `url` - GitHub repository URL
 /// * `ref_` - Optional git reference (branch, tag, or commit)
+/// * `proxy` - HTTP proxy settings applied to every fetch
+/// * `ssh_key` - Key for `git@` URLs; the SSH agent is used when `None`
 ///
 /// # Returns
 /// RepoContext pointing to the cloned repository
 ///
 /// # Errors
 /// Returns an error if cloning fails or temp directory cannot be created
-pub fn clone_repository(url: &str, ref_: Option<&str>) -> Result<RepoContext> {
+pub fn clone_repository(
+    url: &str,
+    ref_: Option<&str>,
+    proxy: &ProxySettings,
+    ssh_key: Option<&SshKeyConfig>,
+) -> Result<RepoContext> {
     let temp_dir = build_temp_repo_dir();
     std::fs::create_dir_all(&temp_dir)
         .with_context(|| format!("Failed creating temp directory: {}", temp_dir.display()))?;
@@ -34,20 +42,14 @@ pub fn clone_repository(url: &str, ref_: Option<&str>) -> Result<RepoContext> {
 
     if let Some(reference) = ref_ {
         // Specific ref: try shallow clone targeting the branch first, fall back to full clone.
-        let repo = try_shallow_clone_with_branch(url, &temp_dir, reference).or_else(|_| {
-            Repository::clone(url, &temp_dir).with_context(|| {
-                format!("Failed cloning repository from {}", redact_url_credentials(url))
-            })
-        })?;
+        let repo = try_shallow_clone_with_branch(url, &temp_dir, reference, proxy, ssh_key)
+            .or_else(|_| full_clone(url, &temp_dir, proxy, ssh_key))?;
         checkout_ref(&repo, reference)?;
         Ok(RepoContext::new(temp_dir, true))
     } else {
         // No specific ref: shallow clone (depth=1) the default branch.
-        let repo = shallow_clone(url, &temp_dir).or_else(|_| {
-            Repository::clone(url, &temp_dir).with_context(|| {
-                format!("Failed cloning repository from {}", redact_url_credentials(url))
-            })
-        })?;
+        let repo = shallow_clone(url, &temp_dir, proxy, ssh_key)
+            .or_else(|_| full_clone(url, &temp_dir, proxy, ssh_key))?;
         let _ = repo; // drop
         Ok(RepoContext::new(temp_dir, true))
     }
@@ -70,11 +72,17 @@ fn normalize_github_url(url: &str) -> String {
 }
 
 /// Attempt a shallow clone (depth=1) targeting a specific branch name.
-fn try_shallow_clone_with_branch(url: &str, dest: &Path, branch: &str) -> Result<Repository> {
+fn try_shallow_clone_with_branch(
+    url: &str,
+    dest: &Path,
+    branch: &str,
+    proxy: &ProxySettings,
+    ssh_key: Option<&SshKeyConfig>,
+) -> Result<Repository> {
     let mut builder = git2::build::RepoBuilder::new();
     builder.branch(branch);
 
-    let mut fo = FetchOptions::new();
+    let mut fo = fetch_options(url, proxy, ssh_key);
     fo.depth(1);
     builder.fetch_options(fo);
 
@@ -82,8 +90,13 @@ fn try_shallow_clone_with_branch(url: &str, dest: &Path, branch: &str) -> Result
 }
 
 /// Shallow clone (depth=1) the default branch.
-fn shallow_clone(url: &str, dest: &Path) -> Result<Repository> {
-    let mut fo = FetchOptions::new();
+fn shallow_clone(
+    url: &str,
+    dest: &Path,
+    proxy: &ProxySettings,
+    ssh_key: Option<&SshKeyConfig>,
+) -> Result<Repository> {
+    let mut fo = fetch_options(url, proxy, ssh_key);
     fo.depth(1);
 
     let mut builder = git2::build::RepoBuilder::new();
@@ -92,6 +105,110 @@ fn shallow_clone(url: &str, dest: &Path) -> Result<Repository> {
     builder.clone(url, dest).with_context(|| format!("Shallow clone from {url} failed"))
 }
 
+/// Full clone, used when a shallow clone is not possible.
+fn full_clone(
+    url: &str,
+    dest: &Path,
+    proxy: &ProxySettings,
+    ssh_key: Option<&SshKeyConfig>,
+) -> Result<Repository> {
+    let mut builder = git2::build::RepoBuilder::new();
+    builder.fetch_options(fetch_options(url, proxy, ssh_key));
+
+    builder
+        .clone(url, dest)
+        .with_context(|| format!("Failed cloning repository from {}", redact_url_credentials(url)))
+}
+
+/// Fetch options for `url`: the resolved proxy, plus SSH credentials for
+/// `git@` / `ssh://` URLs.
+fn fetch_options(
+    url: &str,
+    proxy: &ProxySettings,
+    ssh_key: Option<&SshKeyConfig>,
+) -> FetchOptions<'static> {
+    let mut fo = proxy.fetch_options(url);
+    if is_ssh_url(url) {
+        fo.remote_callbacks(build_git_callbacks(ssh_key));
+    }
+    fo
+}
+
+/// Returns `true` for scp-style (`git@host:path`) and `ssh://` URLs.
+pub fn is_ssh_url(url: &str) -> bool {
+    url.starts_with("git@") || url.starts_with("ssh://")
+}
+
+/// The SSH key to authenticate with: `configured`, else `SSH_KEY_PATH`.
+///
+/// `None` means the SSH agent is used.
+pub fn resolve_ssh_key(configured: Option<&SshKeyConfig>) -> Option<SshKeyConfig> {
+    resolve_ssh_key_with(configured, |name| env::var(name).ok())
+}
+
+/// Like [`resolve_ssh_key`], reading environment variables through `lookup`.
+pub fn resolve_ssh_key_with(
+    configured: Option<&SshKeyConfig>,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Option<SshKeyConfig> {
+    configured.cloned().or_else(|| {
+        lookup("SSH_KEY_PATH").filter(|path| !path.trim().is_empty()).map(|path| SshKeyConfig {
+            private_key_path: PathBuf::from(path.trim()),
+            public_key_path: None,
+            passphrase: None,
+        })
+    })
+}
+
+/// One way of answering an SSH credential request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SshCredential {
+    /// Key pair read from disk.
+    KeyFile(SshKeyConfig),
+    /// Keys held by the running SSH agent (`SSH_AUTH_SOCK`).
+    Agent,
+}
+
+/// Credentials to offer, in order: the key file when one is configured,
+/// then the SSH agent.
+pub fn ssh_credential_plan(ssh_key: Option<&SshKeyConfig>) -> Vec<SshCredential> {
+    ssh_key.cloned().map(SshCredential::KeyFile).into_iter().chain([SshCredential::Agent]).collect()
+}
+
+/// Remote callbacks that authenticate SSH clones following
+/// [`ssh_credential_plan`].
+///
+/// libgit2 calls the credentials callback again after each rejected
+/// credential; once the plan is exhausted the clone fails instead of looping.
+pub fn build_git_callbacks(ssh_key: Option<&SshKeyConfig>) -> RemoteCallbacks<'static> {
+    let plan = ssh_credential_plan(ssh_key);
+    let mut attempt = 0;
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.credentials(move |_url, username_from_url, allowed| {
+        let username = username_from_url.unwrap_or("git");
+        if allowed.contains(CredentialType::USERNAME) {
+            return Cred::username(username);
+        }
+        if !allowed.contains(CredentialType::SSH_KEY) {
+            return Err(git2::Error::from_str("SSH key authentication not offered by remote"));
+        }
+        let credential = plan.get(attempt).ok_or_else(|| {
+            git2::Error::from_str("SSH authentication failed: no usable key or agent identity")
+        })?;
+        attempt += 1;
+        match credential {
+            SshCredential::KeyFile(key) => Cred::ssh_key(
+                username,
+                key.public_key_path.as_deref(),
+                &key.private_key_path,
+                key.passphrase.as_deref(),
+            ),
+            SshCredential::Agent => Cred::ssh_key_from_agent(username),
+        }
+    });
+    callbacks
+}
+
 fn checkout_ref(repo: &Repository, reference: &str) -> Result<()> {
     let object = repo
         .revparse_single(reference)
@@ -113,3 +230,60 @@ fn build_temp_repo_dir() -> PathBuf {
     let pid = std::process::id();
     env::temp_dir().join(format!("repo-context-{pid}-{nanos}"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{is_ssh_url, resolve_ssh_key_with, ssh_credential_plan, SshCredential};
+    use crate::domain::SshKeyConfig;
+    use std::path::PathBuf;
+
+    fn key(path: &str) -> SshKeyConfig {
+        SshKeyConfig {
+            private_key_path: PathBuf::from(path),
+            public_key_path: None,
+            passphrase: None,
+        }
+    }
+
+    #[test]
+    fn agent_is_used_without_a_key() {
+        assert_eq!(resolve_ssh_key_with(None, |_| None), None);
+        assert_eq!(resolve_ssh_key_with(None, |_| Some("  ".to_string())), None);
+        assert_eq!(ssh_credential_plan(None), [SshCredential::Agent]);
+    }
+
+    #[test]
+    fn key_file_is_tried_before_the_agent() {
+        let configured = SshKeyConfig {
+            private_key_path: PathBuf::from("/keys/deploy"),
+            public_key_path: Some(PathBuf::from("/keys/deploy.pub")),
+            passphrase: Some("secret".to_string()),
+        };
+        let resolved =
+            resolve_ssh_key_with(Some(&configured), |_| Some("/env/id_ed25519".to_string()));
+        assert_eq!(resolved.as_ref(), Some(&configured));
+        assert_eq!(
+            ssh_credential_plan(resolved.as_ref()),
+            [SshCredential::KeyFile(configured), SshCredential::Agent]
+        );
+    }
+
+    #[test]
+    fn ssh_key_path_env_is_the_fallback_key() {
+        let resolved = resolve_ssh_key_with(None, |name| {
+            (name == "SSH_KEY_PATH").then(|| "/home/ci/.ssh/id_ed25519".to_string())
+        });
+        assert_eq!(resolved, Some(key("/home/ci/.ssh/id_ed25519")));
+        assert_eq!(
+            ssh_credential_plan(resolved.as_ref()),
+            [SshCredential::KeyFile(key("/home/ci/.ssh/id_ed25519")), SshCredential::Agent]
+        );
+    }
+
+    #[test]
+    fn only_ssh_urls_get_ssh_callbacks() {
+        assert!(is_ssh_url("git@github.com:owner/repo.git"));
+        assert!(is_ssh_url("ssh://git@github.com/owner/repo.git"));
+        assert!(!is_ssh_url("https://github.com/owner/repo.git"));
+    }
+}
@@ -1,6 +1,6 @@
 //! HuggingFace repository cloning (Spaces, models, datasets)
 
-use crate::fetch::RepoContext;
+use crate::fetch::{ProxySettings, RepoContext};
 use anyhow::{Context, Result};
 use git2::{ObjectType, Repository};
 use std::env;
@@ -95,7 +95,11 @@ pub fn parse_huggingface_url(url: &str) -> Result<HfParsed> {
 }
 
 /// Clone a HuggingFace repository to a temporary directory.
-pub fn clone_repository(url: &str, ref_: Option<&str>) -> Result<RepoContext> {
+pub fn clone_repository(
+    url: &str,
+    ref_: Option<&str>,
+    proxy: &ProxySettings,
+) -> Result<RepoContext> {
     let parsed = parse_huggingface_url(url)?;
 
     // Resolve the ref: prefer explicit argument, then URL-embedded ref.
@@ -118,7 +122,10 @@ pub fn clone_repository(url: &str, ref_: Option<&str>) -> Result<RepoContext> {
     std::fs::create_dir_all(&temp_dir)
         .with_context(|| format!("Failed creating temp directory: {}", temp_dir.display()))?;
 
-    let repo = Repository::clone(&clone_url, &temp_dir)
+    let mut builder = git2::build::RepoBuilder::new();
+    builder.fetch_options(proxy.fetch_options(&clone_url));
+    let repo = builder
+        .clone(&clone_url, &temp_dir)
         .with_context(|| format!("Failed cloning HuggingFace repository from {clone_url}"))?;
 
     if let Some(ref reference) = resolved_ref {
@@ -1,25 +1,37 @@
-//! Repository fetching (local, GitHub, HuggingFace)
+//! Repository fetching (local, GitHub, HuggingFace, Gitea/Forgejo)
 
+use crate::domain::SshKeyConfig;
 use anyhow::Result;
 use std::path::Path;
 
 pub mod context;
+pub mod gitea;
 pub mod github;
 pub mod huggingface;
 pub mod local;
+pub mod proxy;
 
 pub use context::RepoContext;
+pub use proxy::ProxySettings;
 
 /// Fetch a repository from local path or remote URL.
 ///
 /// Dispatches to the appropriate fetcher based on the URL host:
 /// - `github.com` → [`github::clone_repository`]
 /// - `huggingface.co` / `hf.co` → [`huggingface::clone_repository`]
+/// - `gitea.com`, `codeberg.org`, or a host in `gitea_hosts` →
+///   [`gitea::clone_repository`], authenticated with `GITEA_TOKEN` when set
 /// - Local path → [`local::validate_local_path`]
+///
+/// Remote clones go through `proxy` (see [`ProxySettings`]); `git@` clones
+/// authenticate with `ssh_key`, or the SSH agent when it is `None`.
 pub fn fetch_repository(
     path: Option<&Path>,
     repo_url: Option<&str>,
     ref_: Option<&str>,
+    proxy: &ProxySettings,
+    gitea_hosts: &[String],
+    ssh_key: Option<&SshKeyConfig>,
 ) -> Result<RepoContext> {
     if let Some(p) = path {
         local::validate_local_path(p)
@@ -35,10 +47,13 @@ pub fn fetch_repository(
             );
         }
         if huggingface::is_huggingface_url(url) {
-            huggingface::clone_repository(url, ref_)
+            huggingface::clone_repository(url, ref_, proxy)
+        } else if gitea::is_gitea_url(url, gitea_hosts) {
+            let token = std::env::var("GITEA_TOKEN").ok();
+            gitea::clone_repository(url, ref_, token.as_deref(), proxy, ssh_key)
         } else {
             // Default: GitHub (handles both HTTPS and SSH)
-            github::clone_repository(url, ref_)
+            github::clone_repository(url, ref_, proxy, ssh_key)
         }
     } else {
         anyhow::bail!("Either path or repo_url must be specified")
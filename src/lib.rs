@@ -31,6 +31,7 @@
 
 #![warn(missing_docs)]
 
+pub mod analysis;
 pub mod app;
 pub mod chunk;
 pub mod cli;
@@ -274,6 +274,11 @@ mod tests {
             is_readme: false,
             is_config: false,
             is_doc: false,
+            is_env_file: false,
+            is_symlink: false,
+            symlink_target: None,
+            complexity_estimate: 0,
+            priority_explanation: None,
         }
     }
 }
@@ -79,7 +79,53 @@ pub fn score_query_against_chunks(chunks: &[Chunk], query: &str) -> Vec<f64> {
         .collect()
 }
 
-fn tokenize(text: &str) -> Vec<String> {
+/// Shortest query token that is expanded by [`expand_query_terms`].
+const MIN_EXPANSION_PREFIX: usize = 4;
+
+/// Corpus terms that extend a query token, for short queries such as `auth`
+/// that should also reach `authentication` or `authorize`.
+///
+/// For each token of at least four characters, returns up to `n` longer
+/// corpus terms starting with it, most frequent first (ties alphabetical).
+/// Terms already in `tokens` are not repeated.
+pub fn expand_query_terms(tokens: &[String], chunks: &[Chunk], n: usize) -> Vec<String> {
+    let prefixes: Vec<&str> = tokens
+        .iter()
+        .map(String::as_str)
+        .filter(|token| token.len() >= MIN_EXPANSION_PREFIX)
+        .collect();
+    if prefixes.is_empty() || n == 0 {
+        return Vec::new();
+    }
+
+    let mut term_counts: HashMap<String, usize> = HashMap::new();
+    for chunk in chunks {
+        for term in tokenize(&chunk.content) {
+            if prefixes.iter().any(|prefix| term.len() > prefix.len() && term.starts_with(prefix)) {
+                *term_counts.entry(term).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut expansions: Vec<String> = Vec::new();
+    for prefix in prefixes {
+        let mut completions: Vec<(&String, usize)> = term_counts
+            .iter()
+            .filter(|(term, _)| term.len() > prefix.len() && term.starts_with(prefix))
+            .map(|(term, count)| (term, *count))
+            .collect();
+        completions.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        for (term, _) in completions.into_iter().take(n) {
+            if !tokens.contains(term) && !expansions.contains(term) {
+                expansions.push(term.clone());
+            }
+        }
+    }
+    expansions
+}
+
+/// Lowercased alphanumeric/underscore terms of at least two characters.
+pub(crate) fn tokenize(text: &str) -> Vec<String> {
     text.split(|c: char| !c.is_alphanumeric() && c != '_')
         .filter_map(|token| {
             let lower = token.trim().to_ascii_lowercase();
@@ -94,7 +140,7 @@ fn tokenize(text: &str) -> Vec<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::score_query_against_chunks;
+    use super::{expand_query_terms, score_query_against_chunks};
     use crate::domain::Chunk;
     use std::collections::BTreeSet;
 
@@ -143,4 +189,41 @@ mod tests {
         assert_eq!(scores.len(), 2);
         assert!(scores[0] > scores[1]);
     }
+
+    #[test]
+    fn expand_query_terms_adds_frequent_prefix_completions() {
+        let chunk = |content: &str| Chunk {
+            id: content.to_string(),
+            path: "src/auth.rs".to_string(),
+            language: "rust".to_string(),
+            start_line: 1,
+            end_line: 1,
+            content: content.to_string(),
+            priority: 0.5,
+            tags: BTreeSet::new(),
+            token_estimate: 10,
+            file_id: String::new(),
+            chunk_index: 0,
+            chunks_in_file: 0,
+            byte_start: None,
+            byte_end: None,
+            content_sha256: String::new(),
+            file_sha256: String::new(),
+        };
+        let chunks = vec![
+            chunk("fn authentication() { authentication_flow(); }"),
+            chunk("// authentication and authorize checks"),
+            chunk("let author = oauth();"),
+        ];
+
+        let tokens = vec!["auth".to_string(), "db".to_string()];
+        assert_eq!(
+            expand_query_terms(&tokens, &chunks, 2),
+            ["authentication", "authentication_flow"]
+        );
+        let all = expand_query_terms(&tokens, &chunks, 5);
+        assert!(all.contains(&"authorize".to_string()));
+        assert!(!all.contains(&"oauth".to_string()));
+        assert!(expand_query_terms(&["db".to_string()], &chunks, 5).is_empty());
+    }
 }
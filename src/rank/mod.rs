@@ -1,16 +1,18 @@
 //! File ranking by importance.
 
-use crate::domain::{FileInfo, RankingWeights};
+use crate::domain::{Chunk, FileInfo, RankingWeights};
 use anyhow::Result;
 use serde_json::Value as JsonValue;
 use std::collections::HashMap;
 use std::path::Path;
 
 pub mod bm25;
+pub mod plugin;
 pub mod ranker;
 mod workspace;
 
-pub use ranker::FileRanker;
+pub use plugin::{EntrypointBoostPlugin, RankingContext, RankingPlugin, ReadmeBoostPlugin};
+pub use ranker::{FileRanker, RankingSignal};
 
 /// Ranks files by importance using default weights.
 ///
@@ -44,15 +46,109 @@ pub fn rank_files_with_weights(
     Ok(files)
 }
 
+/// Per-signal priority breakdown for the file at `relative_path`.
+///
+/// `files` are the ranked files of the run; they make up the scanned-file set
+/// the ranker sees, as in [`rank_files_with_weights`].
+///
+/// # Returns
+/// `None` when no file in `files` has that relative path
+pub fn debug_rank_file(
+    root_path: &Path,
+    files: &[FileInfo],
+    weights: RankingWeights,
+    relative_path: &str,
+) -> Option<Vec<RankingSignal>> {
+    let file = files.iter().find(|f| f.relative_path == relative_path)?;
+    let scanned_files = files.iter().map(|f| f.relative_path.clone()).collect();
+    let ranker = FileRanker::with_weights(root_path, scanned_files, weights);
+    Some(ranker.debug_rank(file))
+}
+
 /// Same as `rank_files_with_weights` but also returns manifest info extracted during ranking.
 /// The manifest info includes `scripts`, `name`, `description` from `package.json` and similar.
+///
+/// With `explain`, each file's `priority_explanation` is filled in as well.
 pub fn rank_files_with_manifest(
     root_path: &Path,
     mut files: Vec<FileInfo>,
     weights: RankingWeights,
+    explain: bool,
 ) -> Result<(Vec<FileInfo>, HashMap<String, JsonValue>)> {
     let scanned_files = files.iter().map(|f| f.relative_path.clone()).collect();
-    let ranker = FileRanker::with_weights(root_path, scanned_files, weights);
+    let ranker =
+        FileRanker::with_weights(root_path, scanned_files, weights).with_explanations(explain);
     ranker.rank_files(&mut files);
     Ok((files, ranker.get_manifest_info().clone()))
 }
+
+/// Selects chunks for a token budget with first-fit decreasing bin packing.
+///
+/// Chunks are tried largest first and kept whenever they still fit in the
+/// remaining budget, which fills the budget more completely than taking
+/// chunks in priority order. Ties in size go to the higher-priority chunk.
+/// The selection is returned in priority order (then path and line).
+pub fn pack_chunks_to_budget(mut chunks: Vec<Chunk>, budget: usize) -> Vec<Chunk> {
+    let by_priority = |a: &Chunk, b: &Chunk| {
+        b.priority
+            .total_cmp(&a.priority)
+            .then_with(|| a.path.cmp(&b.path))
+            .then_with(|| a.start_line.cmp(&b.start_line))
+    };
+    chunks.sort_by(|a, b| b.token_estimate.cmp(&a.token_estimate).then_with(|| by_priority(a, b)));
+
+    let mut remaining = budget;
+    let mut packed: Vec<Chunk> = Vec::new();
+    for chunk in chunks {
+        if chunk.token_estimate <= remaining {
+            remaining -= chunk.token_estimate;
+            packed.push(chunk);
+        }
+    }
+    packed.sort_by(by_priority);
+    packed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(path: &str, priority: f64, tokens: usize) -> Chunk {
+        Chunk {
+            id: path.to_string(),
+            path: path.to_string(),
+            language: "rust".to_string(),
+            start_line: 1,
+            end_line: 1,
+            content: String::new(),
+            priority,
+            tags: Default::default(),
+            token_estimate: tokens,
+            file_id: String::new(),
+            chunk_index: 0,
+            chunks_in_file: 1,
+            byte_start: None,
+            byte_end: None,
+            content_sha256: String::new(),
+            file_sha256: String::new(),
+        }
+    }
+
+    #[test]
+    fn greedy_packing_fills_budget_better_than_ranked_order() {
+        let chunks = vec![chunk("a.rs", 0.9, 30), chunk("b.rs", 0.8, 30), chunk("c.rs", 0.7, 70)];
+
+        let mut ranked_used = 0;
+        for c in &chunks {
+            if ranked_used + c.token_estimate <= 100 {
+                ranked_used += c.token_estimate;
+            }
+        }
+        let packed = pack_chunks_to_budget(chunks, 100);
+        let packed_used: usize = packed.iter().map(|c| c.token_estimate).sum();
+
+        assert_eq!(ranked_used, 60);
+        assert_eq!(packed_used, 100);
+        assert_eq!(packed.iter().map(|c| c.path.as_str()).collect::<Vec<_>>(), ["a.rs", "c.rs"]);
+    }
+}
@@ -0,0 +1,73 @@
+//! Ranking plugins.
+//!
+//! A [`RankingPlugin`] contributes an extra score that [`FileRanker`] blends
+//! into a file's priority after the built-in signals:
+//!
+//! `priority = (1 - w) * priority + w * score`
+//!
+//! where `w` is the plugin's entry in [`RankingWeights::plugin_weights`]
+//! (index-matched to the plugin list; missing entries mean `0.0`). A plugin
+//! returning `None` leaves the priority unchanged.
+//!
+//! [`FileRanker`]: super::FileRanker
+//! [`RankingWeights::plugin_weights`]: crate::domain::RankingWeights::plugin_weights
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::domain::FileInfo;
+
+/// Repository-wide inputs available to every plugin.
+#[derive(Debug, Clone, Copy)]
+pub struct RankingContext<'a> {
+    /// Repository root.
+    pub root_path: &'a Path,
+    /// Relative paths of every scanned file.
+    pub files: &'a HashSet<String>,
+}
+
+/// A custom ranking signal.
+pub trait RankingPlugin: Send + Sync {
+    /// Short identifier used in logs.
+    fn name(&self) -> &str;
+
+    /// Score `file` in `[0, 1]`, or `None` to abstain.
+    fn score(&self, file: &FileInfo, context: &RankingContext<'_>) -> Option<f64>;
+}
+
+/// Scores README files `1.0` (the same test the built-in ranker uses).
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ReadmeBoostPlugin;
+
+impl RankingPlugin for ReadmeBoostPlugin {
+    fn name(&self) -> &str {
+        "readme-boost"
+    }
+
+    fn score(&self, file: &FileInfo, _context: &RankingContext<'_>) -> Option<f64> {
+        file_name(file).starts_with("readme").then_some(1.0)
+    }
+}
+
+/// Scores conventional entrypoints (`main.rs`, `index.ts`, `cli.py`, ...)
+/// `1.0`, preferring the shallowest one when a repository has several.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EntrypointBoostPlugin;
+
+impl RankingPlugin for EntrypointBoostPlugin {
+    fn name(&self) -> &str {
+        "entrypoint-boost"
+    }
+
+    fn score(&self, file: &FileInfo, _context: &RankingContext<'_>) -> Option<f64> {
+        if !super::ranker::is_common_entrypoint(&file_name(file)) {
+            return None;
+        }
+        let depth = file.relative_path.matches('/').count() as f64;
+        Some(1.0 / (1.0 + 0.25 * depth))
+    }
+}
+
+fn file_name(file: &FileInfo) -> String {
+    file.path.file_name().and_then(|n| n.to_str()).unwrap_or("").to_lowercase()
+}
@@ -1,6 +1,9 @@
 //! File ranker implementation with manifest-aware entrypoint detection.
 
+use crate::analysis::complexity::{estimate_cyclomatic_complexity, supports_language};
+use crate::analysis::license::{detect_license_file, license_mismatch};
 use crate::domain::{FileInfo, RankingWeights};
+use crate::rank::plugin::{RankingContext, RankingPlugin};
 use crate::rank::workspace::discover_workspace_graph;
 use crate::utils::{
     is_likely_generated, is_lock_file, is_vendored, normalize_path, read_file_safe,
@@ -64,7 +67,29 @@ pub struct FileRanker {
     detected_languages: HashSet<String>,
     manifest_info: HashMap<String, JsonValue>,
     workspace_members: Vec<String>,
+    workspace_crate_roots: HashSet<String>,
     weights: RankingWeights,
+    plugins: Vec<Box<dyn RankingPlugin>>,
+    explain: bool,
+}
+
+/// One signal's part in a file's priority, as reported by
+/// [`FileRanker::debug_rank`].
+///
+/// `contribution` is the change the signal made to the running priority
+/// (after the cap at 1.0), so the contributions sum to the final priority.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankingSignal {
+    /// Signal name (`readme`, `core_source`, `complexity`, `plugin:<name>`, ...).
+    pub name: String,
+    /// Value observed for the file (1/0 for flags, the estimate for complexity).
+    pub raw: f64,
+    /// `raw` mapped into `0.0..=1.0`.
+    pub normalized: f64,
+    /// Weight from [`RankingWeights`] applied to the signal.
+    pub weight: f64,
+    /// Change to the priority caused by this signal.
+    pub contribution: f64,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -118,13 +143,32 @@ impl FileRanker {
             detected_languages: HashSet::new(),
             manifest_info: HashMap::new(),
             workspace_members: Vec::new(),
+            workspace_crate_roots: HashSet::new(),
             weights,
+            plugins: Vec::new(),
+            explain: false,
         };
         ranker.load_manifests();
         ranker.validate_entrypoints();
         ranker
     }
 
+    /// Adds ranking plugins, blended using `weights.plugin_weights` by position.
+    #[must_use]
+    pub fn with_plugins(mut self, plugins: Vec<Box<dyn RankingPlugin>>) -> Self {
+        self.plugins.extend(plugins);
+        self
+    }
+
+    /// Fills in `FileInfo::priority_explanation` for every ranked file.
+    ///
+    /// Off by default: each explanation re-runs the signal breakdown.
+    #[must_use]
+    pub fn with_explanations(mut self, explain: bool) -> Self {
+        self.explain = explain;
+        self
+    }
+
     /// Assigns a priority score to a single file.
     ///
     /// # Arguments
@@ -145,6 +189,27 @@ impl FileRanker {
         file.is_doc = signals.is_doc;
         file.priority = self.score_signals(signals);
 
+        if supports_language(&file.language) {
+            file.complexity_estimate = estimate_complexity(file, &content_sample);
+            file.priority = (file.priority
+                + self.weights.complexity_weight * complexity_bonus(file.complexity_estimate))
+            .min(1.0);
+        }
+        let is_crate_root = self.workspace_crate_roots.contains(&rel_normalized);
+        if is_crate_root {
+            file.priority = (file.priority + self.weights.workspace_crate_root).min(1.0);
+        }
+        if signals.is_ci_workflow {
+            file.priority = (file.priority + self.weights.ci_workflow_boost).min(1.0);
+            for event in workflow_triggers(&content_sample) {
+                file.tags.insert(format!("trigger:{event}"));
+            }
+        }
+        file.priority = self.blend_plugin_scores(file);
+        if self.explain {
+            file.priority_explanation = Some(explain_priority(&self.debug_rank(file)));
+        }
+
         if signals.is_readme {
             file.tags.insert("readme".to_string());
         }
@@ -156,10 +221,14 @@ impl FileRanker {
         }
         if signals.is_ci_workflow {
             file.tags.insert("workflow".to_string());
+            file.tags.insert("ci-workflow".to_string());
         }
         if signals.is_entrypoint {
             file.tags.insert("entrypoint".to_string());
         }
+        if is_crate_root {
+            file.tags.insert("crate-root".to_string());
+        }
         if signals.is_lock_file {
             file.tags.insert("lock-file".to_string());
         }
@@ -171,6 +240,22 @@ impl FileRanker {
         }
     }
 
+    fn blend_plugin_scores(&self, file: &FileInfo) -> f64 {
+        let context = RankingContext { root_path: &self.root_path, files: &self.scanned_files };
+        let mut priority = file.priority;
+        for (idx, plugin) in self.plugins.iter().enumerate() {
+            let weight = self.weights.plugin_weights.get(idx).copied().unwrap_or(0.0);
+            if weight == 0.0 {
+                continue;
+            }
+            if let Some(score) = plugin.score(file, &context) {
+                let weight = weight.clamp(0.0, 1.0);
+                priority = (1.0 - weight) * priority + weight * score.clamp(0.0, 1.0);
+            }
+        }
+        priority
+    }
+
     fn collect_signals(
         &self,
         file: &FileInfo,
@@ -202,34 +287,109 @@ impl FileRanker {
         }
     }
 
+    /// Base categories in precedence order: the first one present sets the
+    /// base priority, otherwise `weights.default` does.
+    fn categories(&self, s: FileSignals) -> [(&'static str, bool, f64); 12] {
+        let w = &self.weights;
+        [
+            ("readme", s.is_readme, w.readme),
+            ("contribution_doc", s.is_contribution_doc, w.contribution_doc),
+            ("main_doc", s.is_main_doc, w.main_doc),
+            ("vendored", s.is_vendored, w.vendored),
+            ("lock_file", s.is_lock_file, w.lock_file),
+            ("generated", s.is_generated, w.generated),
+            ("config", s.is_ci_workflow || s.is_config, w.config),
+            ("entrypoint", s.is_entrypoint, w.entrypoint),
+            ("test", s.is_test, w.test),
+            ("example", s.is_example, w.example),
+            ("core_source", s.is_core_source, w.core_source),
+            ("api_definition", s.is_api_definition, w.api_definition),
+        ]
+    }
+
     fn score_signals(&self, s: FileSignals) -> f64 {
-        if s.is_readme {
-            self.weights.readme
-        } else if s.is_contribution_doc {
-            self.weights.contribution_doc
-        } else if s.is_main_doc {
-            self.weights.main_doc
-        } else if s.is_vendored {
-            self.weights.vendored
-        } else if s.is_lock_file {
-            self.weights.lock_file
-        } else if s.is_generated {
-            self.weights.generated
-        } else if s.is_ci_workflow || s.is_config {
-            self.weights.config
-        } else if s.is_entrypoint {
-            self.weights.entrypoint
-        } else if s.is_test {
-            self.weights.test
-        } else if s.is_example {
-            self.weights.example
-        } else if s.is_core_source {
-            self.weights.core_source
-        } else if s.is_api_definition {
-            self.weights.api_definition
+        self.categories(s)
+            .iter()
+            .find(|(_, present, _)| *present)
+            .map_or(self.weights.default, |(_, _, weight)| *weight)
+    }
+
+    /// Breaks the priority [`Self::rank_file`] assigns to `file` down by signal.
+    ///
+    /// Lists every base category (only the first present one contributes),
+    /// then the complexity, crate-root, and CI-workflow adjustments and each
+    /// plugin blend, in the order they are applied.
+    pub fn debug_rank(&self, file: &FileInfo) -> Vec<RankingSignal> {
+        let rel_normalized = normalize_path(&file.relative_path);
+        let rel_lower = rel_normalized.to_lowercase();
+        let name = file.path.file_name().and_then(|n| n.to_str()).unwrap_or("").to_lowercase();
+        let content_sample =
+            read_file_safe(&file.path, Some(2000), None).map(|(s, _)| s).unwrap_or_default();
+        let signals =
+            self.collect_signals(file, &name, &rel_normalized, &rel_lower, &content_sample);
+
+        let flag = |present: bool| if present { 1.0 } else { 0.0 };
+        let signal = |name: &str, raw: f64, normalized: f64, weight: f64, contribution: f64| {
+            RankingSignal { name: name.to_string(), raw, normalized, weight, contribution }
+        };
+        let mut breakdown = Vec::new();
+        let mut matched = false;
+        for (name, present, weight) in self.categories(signals) {
+            let contribution = if present && !matched { weight } else { 0.0 };
+            matched |= present;
+            breakdown.push(signal(name, flag(present), flag(present), weight, contribution));
+        }
+        let default = if matched { 0.0 } else { self.weights.default };
+        breakdown.push(signal(
+            "default",
+            flag(!matched),
+            flag(!matched),
+            self.weights.default,
+            default,
+        ));
+        let mut priority = self.score_signals(signals);
+
+        let weight = self.weights.complexity_weight;
+        let (complexity, bonus, next) = if supports_language(&file.language) {
+            let complexity = estimate_complexity(file, &content_sample);
+            let bonus = complexity_bonus(complexity);
+            (complexity as f64, bonus, (priority + weight * bonus).min(1.0))
         } else {
-            self.weights.default
+            (0.0, 0.0, priority)
+        };
+        breakdown.push(signal("complexity", complexity, bonus, weight, next - priority));
+        priority = next;
+
+        let is_crate_root = self.workspace_crate_roots.contains(&rel_normalized);
+        let weight = self.weights.workspace_crate_root;
+        let next = if is_crate_root { (priority + weight).min(1.0) } else { priority };
+        let raw = flag(is_crate_root);
+        breakdown.push(signal("workspace_crate_root", raw, raw, weight, next - priority));
+        priority = next;
+
+        let weight = self.weights.ci_workflow_boost;
+        let next = if signals.is_ci_workflow { (priority + weight).min(1.0) } else { priority };
+        let raw = flag(signals.is_ci_workflow);
+        breakdown.push(signal("ci_workflow", raw, raw, weight, next - priority));
+        priority = next;
+
+        let context = RankingContext { root_path: &self.root_path, files: &self.scanned_files };
+        for (idx, plugin) in self.plugins.iter().enumerate() {
+            let weight = self.weights.plugin_weights.get(idx).copied().unwrap_or(0.0);
+            let score = if weight == 0.0 { None } else { plugin.score(file, &context) };
+            let (raw, normalized, next) = match score {
+                Some(score) => {
+                    let (blend, normalized) = (weight.clamp(0.0, 1.0), score.clamp(0.0, 1.0));
+                    (score, normalized, (1.0 - blend) * priority + blend * normalized)
+                }
+                None => (0.0, 0.0, priority),
+            };
+            let name = format!("plugin:{}", plugin.name());
+            breakdown.push(signal(&name, raw, normalized, weight, next - priority));
+            priority = next;
         }
+
+        breakdown
     }
 
     /// Ranks all files in the provided slice.
@@ -294,12 +454,58 @@ impl FileRanker {
         self.parse_package_json();
         self.parse_go_mod();
         self.parse_cargo_toml();
+        self.detect_license();
+        self.parse_readme_sections();
 
         if self.root_path.join("setup.py").exists() {
             self.detected_languages.insert("python".to_string());
         }
     }
 
+    /// Record the license from LICENSE/COPYING as `license`, falling back to
+    /// the manifest's declared license, and flag disagreements between them.
+    fn detect_license(&mut self) {
+        let declared = self.manifest_info.get("license_declared").and_then(JsonValue::as_str);
+        let Some(detected) = detect_license_file(&self.root_path) else {
+            if let Some(declared) = declared.map(str::to_string) {
+                self.manifest_info.insert("license".to_string(), JsonValue::String(declared));
+            }
+            return;
+        };
+
+        if let Some(declared) = declared.filter(|d| license_mismatch(d, detected.spdx_id)) {
+            tracing::warn!(
+                "Declared license '{}' does not match {} ({})",
+                declared,
+                detected.file,
+                detected.spdx_id
+            );
+            self.manifest_info.insert("license_mismatch".to_string(), JsonValue::Bool(true));
+        }
+        self.manifest_info
+            .insert("license".to_string(), JsonValue::String(detected.spdx_id.to_string()));
+        self.manifest_info.insert("license_file".to_string(), JsonValue::String(detected.file));
+    }
+
+    /// Record the root README's h1-h3 sections as `readme_sections`.
+    fn parse_readme_sections(&mut self) {
+        let Some(readme) = ["README.md", "readme.md", "README.markdown", "README"]
+            .into_iter()
+            .find(|name| self.scanned_files.contains(*name))
+        else {
+            return;
+        };
+        let Ok((content, _)) = read_file_safe(&self.root_path.join(readme), None, None) else {
+            return;
+        };
+        let sections = extract_readme_sections(&content);
+        if sections.is_empty() {
+            return;
+        }
+        let sections = sections.into_iter().map(|(k, v)| (k, JsonValue::String(v))).collect();
+        self.manifest_info.insert("readme_sections".to_string(), JsonValue::Object(sections));
+    }
+
     fn parse_pyproject(&mut self) {
         let path = self.root_path.join("pyproject.toml");
         if !path.exists() {
@@ -361,6 +567,11 @@ impl FileRanker {
             }
         }
 
+        if let Some(license) = value.get("license").and_then(JsonValue::as_str) {
+            self.manifest_info
+                .insert("license_declared".to_string(), JsonValue::String(license.to_string()));
+        }
+
         for key in ["main", "module", "types"] {
             if let Some(v) = value.get(key).and_then(JsonValue::as_str) {
                 self.entrypoint_candidates.insert(normalize_path(v));
@@ -430,6 +641,12 @@ impl FileRanker {
                         self.manifest_info
                             .insert("name".to_string(), JsonValue::String(name.to_string()));
                     }
+                    if let Some(license) = table.get("license").and_then(toml::Value::as_str) {
+                        self.manifest_info.insert(
+                            "license_declared".to_string(),
+                            JsonValue::String(license.to_string()),
+                        );
+                    }
                 }
             }
         }
@@ -438,8 +655,11 @@ impl FileRanker {
             let mut members: Vec<String> = graph.member_roots.into_iter().collect();
             members.sort();
             for member in &members {
-                self.entrypoint_candidates.insert(normalize_path(&format!("{member}/src/main.rs")));
-                self.entrypoint_candidates.insert(normalize_path(&format!("{member}/src/lib.rs")));
+                for root in ["src/main.rs", "src/lib.rs"] {
+                    let path = normalize_path(&format!("{member}/{root}"));
+                    self.entrypoint_candidates.insert(path.clone());
+                    self.workspace_crate_roots.insert(path);
+                }
             }
             self.workspace_members = members.clone();
             self.manifest_info.insert(
@@ -470,7 +690,53 @@ impl FileRanker {
     }
 }
 
-fn is_common_entrypoint(name: &str) -> bool {
+/// Split a Markdown README into sections keyed by heading.
+///
+/// Each h1-h3 ATX heading (`#` to `###`) starts a section that runs until the
+/// next h1-h3 heading; deeper headings stay inside their parent's content.
+/// Keys are the lowercased heading text (`## Usage` → `usage`), values the
+/// trimmed body. Lines inside fenced code blocks are never headings, and the
+/// first section wins when a heading repeats.
+pub fn extract_readme_sections(content: &str) -> HashMap<String, String> {
+    fn heading(line: &str) -> Option<&str> {
+        let hashes = line.bytes().take_while(|&b| b == b'#').count();
+        let rest = &line[hashes..];
+        if !(1..=3).contains(&hashes) || !(rest.is_empty() || rest.starts_with([' ', '\t'])) {
+            return None;
+        }
+        Some(rest.trim().trim_end_matches('#').trim_end())
+    }
+
+    let mut sections = HashMap::new();
+    let mut current: Option<(String, Vec<&str>)> = None;
+    let mut in_fence = false;
+    let mut flush = |section: Option<(String, Vec<&str>)>| {
+        if let Some((name, body)) = section {
+            if !name.is_empty() {
+                sections.entry(name).or_insert_with(|| body.join("\n").trim().to_string());
+            }
+        }
+    };
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        match heading(line).filter(|_| !in_fence) {
+            Some(title) => flush(current.replace((title.to_lowercase(), Vec::new()))),
+            None => {
+                if let Some((_, body)) = current.as_mut() {
+                    body.push(line);
+                }
+            }
+        }
+    }
+    flush(current);
+    sections
+}
+
+pub(super) fn is_common_entrypoint(name: &str) -> bool {
     matches!(
         name,
         "main.py" | "main.go" | "main.rs" | "index.js" | "index.ts" | "app.py" | "cli.py"
@@ -542,8 +808,76 @@ fn is_contribution_doc(rel: &str, name: &str) -> bool {
         .any(|prefix| name_lower.starts_with(prefix) || rel_lower.contains(&format!("/{prefix}")))
 }
 
+/// Complexity of `file` estimated from its leading `sample`, scaled up by
+/// the scanned size when the sample is shorter, so ranking never reads a file
+/// in full.
+fn estimate_complexity(file: &FileInfo, sample: &str) -> usize {
+    let sampled = estimate_cyclomatic_complexity(sample, &file.language);
+    let size = file.size_bytes as usize;
+    if sample.is_empty() || size <= sample.len() {
+        return sampled;
+    }
+    (sampled as f64 * size as f64 / sample.len() as f64).round() as usize
+}
+
+/// Map complexity onto `[0, 1)` so the boost never exceeds `complexity_weight`
+/// and stays a tiebreaker within a ranking category.
+fn complexity_bonus(complexity: usize) -> f64 {
+    let c = complexity as f64;
+    c / (c + 10.0)
+}
+
 fn is_ci_workflow(rel: &str) -> bool {
-    rel.starts_with(".github/workflows/")
+    rel.starts_with(".github/workflows/") && (rel.ends_with(".yml") || rel.ends_with(".yaml"))
+}
+
+/// Trigger events named by a workflow's top-level `on:` key, in any of its
+/// scalar (`on: push`), flow list (`on: [push, pull_request]`), or block
+/// (mapping or `- event` list) forms. YAML 1.1 parsers read a bare `on` as
+/// `true`, so `true:` is accepted too.
+fn workflow_triggers(content: &str) -> Vec<String> {
+    let unquote = |s: &str| s.trim().trim_matches(['"', '\'']).to_string();
+    let mut lines = content.lines();
+    while let Some(line) = lines.next() {
+        let Some(rest) =
+            ["on:", "\"on\":", "'on':", "true:"].iter().find_map(|key| line.strip_prefix(key))
+        else {
+            continue;
+        };
+        let rest = rest.split('#').next().unwrap_or("").trim();
+        if !rest.is_empty() {
+            return rest
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .split(',')
+                .map(unquote)
+                .filter(|event| !event.is_empty())
+                .collect();
+        }
+
+        let mut events = Vec::new();
+        let mut item_indent = None;
+        for line in lines.by_ref() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let indent = line.len() - line.trim_start().len();
+            if indent == 0 {
+                break;
+            }
+            if indent != *item_indent.get_or_insert(indent) {
+                continue;
+            }
+            let item = trimmed.strip_prefix("- ").unwrap_or(trimmed);
+            let event = unquote(item.split(':').next().unwrap_or(""));
+            if !event.is_empty() {
+                events.push(event);
+            }
+        }
+        return events;
+    }
+    Vec::new()
 }
 
 fn is_config_file(name: &str, rel: &str) -> bool {
@@ -554,10 +888,31 @@ fn is_api_definition(name: &str) -> bool {
     ["api", "interface", "types", "models", "schema"].iter().any(|needle| name.contains(needle))
 }
 
+/// Compact `name:contribution` sum of the signals that moved the priority,
+/// e.g. `readme:0.90 + complexity:0.03 = 0.93`.
+fn explain_priority(signals: &[RankingSignal]) -> String {
+    let mut out = String::new();
+    for signal in signals.iter().filter(|s| s.contribution.abs() >= 0.005) {
+        if out.is_empty() {
+            out.push_str(&format!("{}:{:.2}", signal.name, signal.contribution));
+        } else {
+            let sign = if signal.contribution < 0.0 { '-' } else { '+' };
+            out.push_str(&format!(" {sign} {}:{:.2}", signal.name, signal.contribution.abs()));
+        }
+    }
+    let total: f64 = signals.iter().map(|s| s.contribution).sum();
+    if out.is_empty() {
+        format!("{total:.2}")
+    } else {
+        format!("{out} = {total:.2}")
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{FileRanker, JsonValue};
-    use crate::domain::FileInfo;
+    use super::{extract_readme_sections, workflow_triggers, FileRanker, JsonValue};
+    use crate::analysis::estimate_cyclomatic_complexity;
+    use crate::domain::{FileInfo, RankingWeights};
     use std::collections::{BTreeSet, HashSet};
     use std::fs;
     use tempfile::TempDir;
@@ -576,6 +931,11 @@ mod tests {
             is_readme: false,
             is_config: false,
             is_doc: false,
+            is_env_file: false,
+            is_symlink: false,
+            symlink_target: None,
+            complexity_estimate: 0,
+            priority_explanation: None,
         }
     }
 
@@ -636,6 +996,77 @@ mod tests {
         assert!(contributing.tags.contains("contribution"));
     }
 
+    #[test]
+    fn detects_license_and_declared_mismatch() {
+        let tmp = TempDir::new().expect("tmp");
+        fs::write(
+            tmp.path().join("Cargo.toml"),
+            "[package]\nname='x'\nversion='0.1.0'\nlicense='Apache-2.0'\n",
+        )
+        .expect("write cargo");
+        fs::write(
+            tmp.path().join("LICENSE"),
+            "MIT License\n\nPermission is hereby granted, free of charge, to any person\n",
+        )
+        .expect("write license");
+
+        let ranker = FileRanker::new(tmp.path(), HashSet::new());
+        let info = ranker.get_manifest_info();
+        assert_eq!(info.get("license").and_then(JsonValue::as_str), Some("MIT"));
+        assert_eq!(info.get("license_file").and_then(JsonValue::as_str), Some("LICENSE"));
+        assert_eq!(info.get("license_mismatch").and_then(JsonValue::as_bool), Some(true));
+    }
+
+    #[test]
+    fn complexity_breaks_ties_within_category() {
+        let tmp = TempDir::new().expect("tmp");
+        fs::create_dir_all(tmp.path().join("src")).expect("mkdir src");
+        let simple_path = tmp.path().join("src/simple.rs");
+        let complex_path = tmp.path().join("src/complex.rs");
+        fs::write(&simple_path, "pub fn one() -> i32 {\n    1\n}\n").expect("write simple");
+        fs::write(
+            &complex_path,
+            "pub fn pick(x: i32) -> i32 {\n    if x > 0 && x < 5 {\n        match x {\n            1 => 1,\n            _ => 2,\n        }\n    } else {\n        0\n    }\n}\n",
+        )
+        .expect("write complex");
+
+        let scanned = HashSet::from(["src/simple.rs".to_string(), "src/complex.rs".to_string()]);
+        let ranker = FileRanker::new(tmp.path(), scanned);
+
+        let mut simple = make_file(&simple_path, "src/simple.rs", ".rs", "rust");
+        let mut complex = make_file(&complex_path, "src/complex.rs", ".rs", "rust");
+        ranker.rank_file(&mut simple);
+        ranker.rank_file(&mut complex);
+
+        assert_eq!(simple.complexity_estimate, 1);
+        assert_eq!(complex.complexity_estimate, 5);
+        assert!(complex.priority > simple.priority);
+        assert!(complex.priority - simple.priority < ranker.weights.complexity_weight);
+    }
+
+    #[test]
+    fn complexity_of_large_files_is_scaled_from_the_sample() {
+        let tmp = TempDir::new().expect("tmp");
+        fs::create_dir_all(tmp.path().join("src")).expect("mkdir src");
+        let path = tmp.path().join("src/branches.rs");
+        let branch = "    if x > 0 { y += 1; }\n";
+        let body = branch.repeat(400);
+        fs::write(&path, format!("fn f(x: i32, mut y: i32) {{\n{body}}}\n")).expect("write");
+        let full = estimate_cyclomatic_complexity(&fs::read_to_string(&path).unwrap(), "rust");
+
+        let ranker = FileRanker::new(tmp.path(), HashSet::from(["src/branches.rs".to_string()]));
+        let mut file = make_file(&path, "src/branches.rs", ".rs", "rust");
+        file.size_bytes = fs::metadata(&path).unwrap().len();
+        ranker.rank_file(&mut file);
+
+        assert!(file.size_bytes > 2000);
+        assert!(
+            file.complexity_estimate.abs_diff(full) * 20 <= full,
+            "{} vs {full}",
+            file.complexity_estimate
+        );
+    }
+
     #[test]
     fn workspace_members_add_member_entrypoints() {
         let tmp = TempDir::new().expect("tmp");
@@ -658,4 +1089,227 @@ mod tests {
             .and_then(JsonValue::as_array)
             .is_some());
     }
+
+    #[test]
+    fn workspace_crate_roots_rank_above_utility_files() {
+        let tmp = TempDir::new().expect("tmp");
+        fs::write(tmp.path().join("Cargo.toml"), "[workspace]\nmembers=[\"crates/*\"]\n")
+            .expect("write root cargo");
+        let mut rels = Vec::new();
+        for name in ["alpha", "beta"] {
+            let dir = tmp.path().join(format!("crates/{name}"));
+            fs::create_dir_all(dir.join("src")).expect("mkdir src");
+            fs::write(dir.join("Cargo.toml"), format!("[package]\nname=\"{name}\"\n"))
+                .expect("write member cargo");
+            for file in ["lib.rs", "util.rs", "helpers.rs"] {
+                let body = "pub fn f(x: u32) -> u32 { if x > 1 { x } else { 0 } }\n".repeat(20);
+                fs::write(dir.join("src").join(file), body).expect("write source");
+                rels.push(format!("crates/{name}/src/{file}"));
+            }
+        }
+
+        let scanned: HashSet<String> = rels.iter().cloned().collect();
+        let ranker = FileRanker::new(tmp.path(), scanned);
+        let mut files: Vec<FileInfo> =
+            rels.iter().map(|rel| make_file(&tmp.path().join(rel), rel, ".rs", "rust")).collect();
+        ranker.rank_files(&mut files);
+
+        let top: Vec<&str> = files[..2].iter().map(|f| f.relative_path.as_str()).collect();
+        assert_eq!(top, ["crates/alpha/src/lib.rs", "crates/beta/src/lib.rs"]);
+        assert!(files[0].tags.contains("crate-root"));
+        assert!(!files[2].tags.contains("crate-root"));
+        assert!(files[1].priority > files[2].priority);
+    }
+
+    #[test]
+    fn debug_rank_contributions_sum_to_rank_file_priority() {
+        let tmp = TempDir::new().expect("tmp");
+        fs::create_dir_all(tmp.path().join("src")).expect("mkdir");
+        let path = tmp.path().join("src/engine.rs");
+        fs::write(&path, "pub fn run(x: u32) -> u32 {\n    if x > 2 { x } else { 0 }\n}\n")
+            .expect("write");
+        let ranker = FileRanker::new(tmp.path(), HashSet::from(["src/engine.rs".to_string()]));
+
+        let mut file = make_file(&path, "src/engine.rs", ".rs", "rust");
+        ranker.rank_file(&mut file);
+        let breakdown = ranker.debug_rank(&file);
+
+        let names: HashSet<&str> = breakdown.iter().map(|s| s.name.as_str()).collect();
+        assert!(names.len() >= 3);
+        assert!(names.contains("core_source") && names.contains("complexity"));
+        let core = breakdown.iter().find(|s| s.name == "core_source").expect("core_source");
+        assert_eq!(core.raw, 1.0);
+        assert_eq!(core.contribution, ranker.weights.core_source);
+        let total: f64 = breakdown.iter().map(|s| s.contribution).sum();
+        assert!((total - file.priority).abs() < 1e-9, "{total} != {}", file.priority);
+    }
+
+    #[test]
+    fn explanations_name_the_signals_behind_the_priority() {
+        let tmp = TempDir::new().expect("tmp");
+        let path = tmp.path().join("README.md");
+        fs::write(&path, "# Demo\n").expect("write");
+        let scanned = HashSet::from(["README.md".to_string()]);
+
+        let mut readme = make_file(&path, "README.md", ".md", "markdown");
+        FileRanker::new(tmp.path(), scanned.clone()).rank_file(&mut readme);
+        assert_eq!(readme.priority_explanation, None);
+
+        FileRanker::new(tmp.path(), scanned).with_explanations(true).rank_file(&mut readme);
+        let explanation = readme.priority_explanation.expect("explanation");
+        assert!(explanation.starts_with("readme:"), "{explanation}");
+        assert!(explanation.ends_with(&format!("= {:.2}", readme.priority)), "{explanation}");
+    }
+
+    #[test]
+    fn workflow_triggers_cover_scalar_list_and_mapping_forms() {
+        assert_eq!(workflow_triggers("name: CI\non: push\n"), ["push"]);
+        assert_eq!(
+            workflow_triggers("on: [push, \"pull_request\"]\njobs: {}\n"),
+            ["push", "pull_request"]
+        );
+        let mapping = "name: CI\non:\n  push:\n    branches: [main]\n  # nightly\n  pull_request:\n  workflow_dispatch:\njobs:\n  build:\n    runs-on: ubuntu-latest\n";
+        assert_eq!(workflow_triggers(mapping), ["push", "pull_request", "workflow_dispatch"]);
+        assert!(workflow_triggers("jobs:\n  build: {}\n").is_empty());
+    }
+
+    #[test]
+    fn github_workflows_are_tagged_and_boosted() {
+        let tmp = TempDir::new().expect("tmp");
+        fs::create_dir_all(tmp.path().join(".github/workflows")).expect("mkdir workflows");
+        let rel = ".github/workflows/ci.yml";
+        fs::write(
+            tmp.path().join(rel),
+            "name: CI\non:\n  push:\n  pull_request:\njobs:\n  test:\n    runs-on: ubuntu-latest\n",
+        )
+        .expect("write workflow");
+
+        let ranker = FileRanker::new(tmp.path(), HashSet::from([rel.to_string()]));
+        let mut file = make_file(&tmp.path().join(rel), rel, ".yml", "yaml");
+        ranker.rank_file(&mut file);
+        assert!(file.tags.contains("ci-workflow"));
+        assert!(file.tags.contains("trigger:push"));
+        assert!(file.tags.contains("trigger:pull_request"));
+        let expected = (ranker.weights.config + ranker.weights.ci_workflow_boost).min(1.0);
+        assert!((file.priority - expected).abs() < 1e-9);
+    }
+
+    struct ImportantPlugin;
+
+    impl crate::rank::RankingPlugin for ImportantPlugin {
+        fn name(&self) -> &str {
+            "important"
+        }
+
+        fn score(
+            &self,
+            file: &FileInfo,
+            _context: &crate::rank::RankingContext<'_>,
+        ) -> Option<f64> {
+            (file.relative_path.rsplit('/').next() == Some("important.rs")).then_some(1.0)
+        }
+    }
+
+    #[test]
+    fn plugin_score_is_blended_by_weight() {
+        let tmp = TempDir::new().expect("tmp");
+        fs::create_dir_all(tmp.path().join("src")).expect("mkdir src");
+        let rels = ["src/lib.rs", "src/important.rs", "tests/it.rs"];
+        for rel in rels {
+            fs::create_dir_all(tmp.path().join(rel).parent().unwrap()).expect("mkdir");
+            fs::write(tmp.path().join(rel), "fn x() {}\n").expect("write source");
+        }
+        let scanned: HashSet<String> = rels.iter().map(|r| r.to_string()).collect();
+        let weights = RankingWeights { plugin_weights: vec![1.0], ..RankingWeights::default() };
+        let ranker = FileRanker::with_weights(tmp.path(), scanned, weights)
+            .with_plugins(vec![Box::new(ImportantPlugin)]);
+
+        let mut files: Vec<FileInfo> =
+            rels.iter().map(|rel| make_file(&tmp.path().join(rel), rel, ".rs", "rust")).collect();
+        ranker.rank_files(&mut files);
+        assert_eq!(files[0].relative_path, "src/important.rs");
+        assert_eq!(files[0].priority, 1.0);
+
+        // Without a weight the plugin has no effect.
+        let unweighted = FileRanker::new(tmp.path(), HashSet::new())
+            .with_plugins(vec![Box::new(ImportantPlugin)]);
+        let mut important = make_file(&tmp.path().join(rels[1]), rels[1], ".rs", "rust");
+        unweighted.rank_file(&mut important);
+        assert!(important.priority < 1.0);
+    }
+
+    #[test]
+    fn builtin_plugins_score_readme_and_entrypoints() {
+        use crate::rank::{
+            EntrypointBoostPlugin, RankingContext, RankingPlugin, ReadmeBoostPlugin,
+        };
+
+        let root = std::path::Path::new("/repo");
+        let files = HashSet::new();
+        let context = RankingContext { root_path: root, files: &files };
+        let readme = make_file(&root.join("README.md"), "README.md", ".md", "markdown");
+        let main = make_file(&root.join("src/main.rs"), "src/main.rs", ".rs", "rust");
+
+        assert_eq!(ReadmeBoostPlugin.score(&readme, &context), Some(1.0));
+        assert_eq!(ReadmeBoostPlugin.score(&main, &context), None);
+        assert_eq!(EntrypointBoostPlugin.score(&main, &context), Some(0.8));
+        assert_eq!(EntrypointBoostPlugin.score(&readme, &context), None);
+    }
+
+    const MULTI_SECTION_README: &str = "# Demo
+
+A demo project.
+
+## Installation
+
+```sh
+# not a heading
+cargo install demo
+```
+
+## Usage
+
+Run `demo --help`.
+
+#### Flags
+
+`--verbose` prints more.
+
+### API ###
+
+Call `demo::run()`.
+
+## Usage
+
+Ignored duplicate.
+";
+
+    #[test]
+    fn readme_sections_split_on_h1_to_h3_headings() {
+        let sections = extract_readme_sections(MULTI_SECTION_README);
+
+        assert_eq!(sections["demo"], "A demo project.");
+        assert_eq!(sections["installation"], "```sh\n# not a heading\ncargo install demo\n```");
+        assert_eq!(
+            sections["usage"],
+            "Run `demo --help`.\n\n#### Flags\n\n`--verbose` prints more."
+        );
+        assert_eq!(sections["api"], "Call `demo::run()`.");
+        assert!(!sections.contains_key("not a heading"));
+        assert!(!sections.contains_key("flags"));
+        assert_eq!(sections.len(), 4);
+    }
+
+    #[test]
+    fn readme_sections_are_stored_in_manifest_info() {
+        let tmp = TempDir::new().expect("tmp");
+        fs::write(tmp.path().join("README.md"), MULTI_SECTION_README).expect("write readme");
+        let scanned: HashSet<String> = HashSet::from(["README.md".to_string()]);
+
+        let ranker = FileRanker::new(tmp.path(), scanned);
+        let sections = &ranker.get_manifest_info()["readme_sections"];
+
+        assert_eq!(sections["api"], JsonValue::String("Call `demo::run()`.".to_string()));
+        assert!(sections["usage"].as_str().is_some_and(|s| s.starts_with("Run `demo --help`.")));
+    }
 }
@@ -97,6 +97,21 @@ pub static DEFAULT_RULES: Lazy<Vec<RedactionRule>> = Lazy::new(|| {
             pattern: Regex::new(r"\bSK[0-9a-fA-F]{32}\b").expect("valid regex"),
             replacement: "[TWILIO_KEY_REDACTED]",
         },
+        RedactionRule {
+            name: "twilio_account_sid",
+            pattern: Regex::new(r"\bAC[0-9a-fA-F]{32}\b").expect("valid regex"),
+            replacement: "[TWILIO_ACCOUNT_SID_REDACTED]",
+        },
+        // Auth tokens are bare 32-char hex, indistinguishable from an MD5 hash,
+        // so only values assigned to a Twilio token key are redacted.
+        RedactionRule {
+            name: "twilio_auth_token",
+            pattern: Regex::new(
+                r#"(?i)(twilio[_\-.]?(?:auth[_\-]?)?token['"]?\s*[:=]\s*['"]?)([0-9a-f]{32})\b"#,
+            )
+            .expect("valid regex"),
+            replacement: "${1}[TWILIO_AUTH_TOKEN_REDACTED]",
+        },
         // ── SendGrid ─────────────────────────────────────────────────────────────
         RedactionRule {
             name: "sendgrid_key",
@@ -158,6 +173,91 @@ pub static DEFAULT_RULES: Lazy<Vec<RedactionRule>> = Lazy::new(|| {
             pattern: Regex::new(r"\bsk-[A-Za-z0-9]{20,}\b").expect("valid regex"),
             replacement: "[REDACTED_OPENAI_KEY]",
         },
+        // ── HashiCorp Vault ──────────────────────────────────────────────────────
+        // hvs./hvb./hvr. tokens anywhere; legacy s./b./r. tokens only when
+        // assigned to a Vault token key, since `s.` + 24 letters is also valid code.
+        RedactionRule {
+            name: "vault_token",
+            pattern: Regex::new(
+                r#"\bhv[sbr]\.[A-Za-z0-9_\-]{24,}\b|(?i:((?:vault[_\-]?token|x-vault-token)['"]?\s*[:=]\s*['"]?))[sbr]\.[A-Za-z0-9]{24}\b"#,
+            )
+            .expect("valid regex"),
+            replacement: "${1}[VAULT_TOKEN_REDACTED]",
+        },
+        RedactionRule {
+            name: "vault_uri",
+            pattern: Regex::new(r#"\b(vault://)[^\s'"<>`]+"#).expect("valid regex"),
+            replacement: "${1}[VAULT_URI_REDACTED]",
+        },
+        // ── Doppler ──────────────────────────────────────────────────────────────
+        RedactionRule {
+            name: "doppler_token",
+            pattern: Regex::new(
+                r"\bdp\.(?:st(?:\.[A-Za-z0-9_\-]+)?|pt|ct|sa|scim|audit)\.[A-Za-z0-9]{40,44}\b",
+            )
+            .expect("valid regex"),
+            replacement: "[DOPPLER_TOKEN_REDACTED]",
+        },
+        // ── PagerDuty ────────────────────────────────────────────────────────────
+        // Integration keys are plain 32-char alphanumerics; match them by key name.
+        RedactionRule {
+            name: "pagerduty_routing_key",
+            pattern: Regex::new(
+                r#"(?i)((?:x-routing-key|routing[_\-]?key|pagerduty[_\-]?(?:integration[_\-]?|service[_\-]?)?key)['"]?\s*[:=]\s*['"]?)([A-Za-z0-9]{32})\b"#,
+            )
+            .expect("valid regex"),
+            replacement: "${1}[PAGERDUTY_KEY_REDACTED]",
+        },
+        // ── LaunchDarkly ─────────────────────────────────────────────────────────
+        RedactionRule {
+            name: "launchdarkly_sdk_key",
+            pattern: Regex::new(
+                r"\bsdk-(?:[0-9a-f]{40}|[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12})\b",
+            )
+            .expect("valid regex"),
+            replacement: "[LAUNCHDARKLY_SDK_KEY_REDACTED]",
+        },
+        RedactionRule {
+            name: "launchdarkly_client_key",
+            pattern: Regex::new(r"\b[0-9a-f]{40}-[0-9a-f]{12}-[0-9a-f]{12}\b")
+                .expect("valid regex"),
+            replacement: "[LAUNCHDARKLY_CLIENT_KEY_REDACTED]",
+        },
+        // ── Okta ─────────────────────────────────────────────────────────────────
+        RedactionRule {
+            name: "okta_api_token",
+            pattern: Regex::new(
+                r#"(?i:(SSWS\s+|okta[_\-]?(?:api[_\-]?)?token['"]?\s*[:=]\s*['"]?))[A-Za-z0-9_\-]{40,64}\b"#,
+            )
+            .expect("valid regex"),
+            replacement: "${1}[OKTA_TOKEN_REDACTED]",
+        },
+        // ── Cloudflare ───────────────────────────────────────────────────────────
+        // None of these values has a prefix, so each is matched by key name.
+        RedactionRule {
+            name: "cloudflare_api_token",
+            pattern: Regex::new(
+                r#"(?i:((?:cf|cloudflare)[_\-]?api[_\-]?token['"]?\s*[:=]\s*['"]?|x-auth-key:\s*))[A-Za-z0-9_\-]{40}\b"#,
+            )
+            .expect("valid regex"),
+            replacement: "${1}[CLOUDFLARE_API_TOKEN_REDACTED]",
+        },
+        RedactionRule {
+            name: "cloudflare_global_key",
+            pattern: Regex::new(
+                r#"(?i:((?:cf|cloudflare)[_\-]?api[_\-]?key['"]?\s*[:=]\s*['"]?))[0-9a-fA-F]{37}\b"#,
+            )
+            .expect("valid regex"),
+            replacement: "${1}[CLOUDFLARE_GLOBAL_KEY_REDACTED]",
+        },
+        RedactionRule {
+            name: "cloudflare_zone_id",
+            pattern: Regex::new(
+                r#"(?i:((?:cf[_\-]?|cloudflare[_\-]?)?zone[_\-]?id['"]?\s*[:=]\s*['"]?))[0-9a-fA-F]{32}\b"#,
+            )
+            .expect("valid regex"),
+            replacement: "${1}[CLOUDFLARE_ZONE_ID_REDACTED]",
+        },
         // ── Private keys (PEM blocks) ─────────────────────────────────────────────
         RedactionRule {
             name: "private_key_header",
@@ -419,4 +519,128 @@ mod tests {
         assert!(out.contains("[GITHUB_TOKEN_REDACTED]"), "got: {out}");
         assert!(!out.contains("[SECRET_REDACTED]"));
     }
+
+    #[test]
+    fn redacts_vault_tokens() {
+        let token = "hvs.CAESIJlU1xN2a8sVq0Zb3kXg7YtRmP4cWdEoHfLiAunB9QeGh";
+        let out = redact(&format!("VAULT_TOKEN={token}"));
+        assert_eq!(out, "VAULT_TOKEN=[VAULT_TOKEN_REDACTED]");
+
+        let out = redact(r#"vault_token: "s.Qf1s5zigZ4OX6akYjQXJC1jY""#);
+        assert_eq!(out, r#"vault_token: "[VAULT_TOKEN_REDACTED]""#);
+        // A legacy-looking token outside a Vault key is left to other rules.
+        assert_eq!(redact("s.abcdefghijklmnopqrstuvwx"), "s.abcdefghijklmnopqrstuvwx");
+    }
+
+    #[test]
+    fn redacts_vault_secret_uri() {
+        let out = redact("password_ref = \"vault://secret/data/payments#db_password\"");
+        assert_eq!(out, "password_ref = \"vault://[VAULT_URI_REDACTED]\"");
+    }
+
+    #[test]
+    fn redacts_doppler_service_token() {
+        let token = "dp.st.prd.Xk3mQ9vLp2RtW8sYz4NcB7hJdF6gA1eUoKiT5qMn0wE";
+        let out = redact(&format!("DOPPLER_TOKEN={token}"));
+        assert_eq!(out, "DOPPLER_TOKEN=[DOPPLER_TOKEN_REDACTED]");
+    }
+
+    #[test]
+    fn random_string_is_not_a_vault_or_doppler_token() {
+        let rules: Vec<_> = DEFAULT_RULES
+            .iter()
+            .filter(|rule| matches!(rule.name, "vault_token" | "doppler_token"))
+            .collect();
+        assert_eq!(rules.len(), 2);
+        for text in ["q7Zr2LmX9wKp4TbN8cVd", "token q7Zr2LmX9wKp4TbN8cVd", "hvs.short"] {
+            assert!(rules.iter().all(|rule| !rule.pattern.is_match(text)), "{text}");
+        }
+    }
+
+    #[test]
+    fn redacts_twilio_account_sid_and_auth_token_separately() {
+        let env = "TWILIO_ACCOUNT_SID=AC0123456789abcdef0123456789abcdef\n\
+                   TWILIO_AUTH_TOKEN=9f86d081884c7d659a2feaa0c55ad015\n";
+        assert_eq!(
+            redact(env),
+            "TWILIO_ACCOUNT_SID=[TWILIO_ACCOUNT_SID_REDACTED]\n\
+             TWILIO_AUTH_TOKEN=[TWILIO_AUTH_TOKEN_REDACTED]\n"
+        );
+        // The same hex outside a Twilio key is an ordinary hash.
+        let hash = "md5 = \"9f86d081884c7d659a2feaa0c55ad015\"";
+        assert_eq!(redact(hash), hash);
+    }
+
+    #[test]
+    fn redacts_pagerduty_routing_key() {
+        let out = redact("x-routing-key: R0ut1ngK3yAbCdEfGhIjKlMnOpQrStUv");
+        assert_eq!(out, "x-routing-key: [PAGERDUTY_KEY_REDACTED]");
+        let out = redact(r#"{"routing_key": "R0ut1ngK3yAbCdEfGhIjKlMnOpQrStUv"}"#);
+        assert_eq!(out, r#"{"routing_key": "[PAGERDUTY_KEY_REDACTED]"}"#);
+    }
+
+    #[test]
+    fn redacts_launchdarkly_keys() {
+        let sdk = "sdk-0123456789abcdef0123456789abcdef01234567";
+        assert_eq!(
+            redact(&format!("LD_SDK_KEY={sdk}")),
+            "LD_SDK_KEY=[LAUNCHDARKLY_SDK_KEY_REDACTED]"
+        );
+        let client = "0123456789abcdef0123456789abcdef01234567-0123456789ab-cdef01234567";
+        assert_eq!(
+            redact(&format!("const clientKey = '{client}';")),
+            "const clientKey = '[LAUNCHDARKLY_CLIENT_KEY_REDACTED]';"
+        );
+    }
+
+    #[test]
+    fn redacts_okta_api_token() {
+        let token = "00aBcDeFgHiJkLmNoPqRsTuVwXyZ0123456789-_Ab";
+        let out = redact(&format!("Authorization: SSWS {token}"));
+        assert_eq!(out, "Authorization: SSWS [OKTA_TOKEN_REDACTED]");
+        let out = redact(&format!("okta_token = \"{token}\""));
+        assert_eq!(out, "okta_token = \"[OKTA_TOKEN_REDACTED]\"");
+    }
+
+    const CF_TOKEN: &str = "Xq7vB2mK9pLr4sT8wN3cY6hJ1dF5gA0eUoZiRtWy";
+    const CF_GLOBAL_KEY: &str = "1e8b5c9f0a3d4e7b2c6f9a1d3e5b7c9f0a2d4";
+    const HEX32: &str = "d41d8cd98f00b204e9800998ecf8427e";
+
+    #[test]
+    fn redacts_cloudflare_api_token() {
+        assert_eq!(
+            redact(&format!("CF_API_TOKEN={CF_TOKEN}")),
+            "CF_API_TOKEN=[CLOUDFLARE_API_TOKEN_REDACTED]"
+        );
+        assert_eq!(
+            redact(&format!("X-Auth-Key: {CF_TOKEN}")),
+            "X-Auth-Key: [CLOUDFLARE_API_TOKEN_REDACTED]"
+        );
+        // Same shape under an unrelated key, or one character longer.
+        assert!(!redact(&format!("BUILD_ID={CF_TOKEN}")).contains("CLOUDFLARE"));
+        assert!(!redact(&format!("CF_API_TOKEN={CF_TOKEN}x")).contains("CLOUDFLARE"));
+    }
+
+    #[test]
+    fn redacts_cloudflare_global_key() {
+        assert_eq!(
+            redact(&format!("CF_API_KEY={CF_GLOBAL_KEY}")),
+            "CF_API_KEY=[CLOUDFLARE_GLOBAL_KEY_REDACTED]"
+        );
+        assert!(!redact(&format!("checksum = {CF_GLOBAL_KEY}")).contains("CLOUDFLARE"));
+    }
+
+    #[test]
+    fn redacts_cloudflare_zone_id_only_in_zone_context() {
+        assert_eq!(
+            redact(&format!("CF_ZONE_ID={HEX32}")),
+            "CF_ZONE_ID=[CLOUDFLARE_ZONE_ID_REDACTED]"
+        );
+        assert_eq!(
+            redact(&format!("zone_id: \"{HEX32}\"")),
+            "zone_id: \"[CLOUDFLARE_ZONE_ID_REDACTED]\""
+        );
+        let hash = format!("etag: \"{HEX32}\"");
+        assert_eq!(redact(&hash), hash);
+    }
 }
@@ -1,12 +1,18 @@
 //! Context pack Markdown rendering
 
+use crate::analysis::extract_openapi_routes;
 use crate::domain::{Chunk, FileDisposition, FileInfo, ScanStats};
-use crate::utils::format_with_commas;
+use crate::render::reader::{parse_context_pack, ContextPackChunk, ContextPackFile};
+use crate::utils::{format_with_commas, stable_hash, Tokenizer};
+use anyhow::{Context, Result};
 use chrono::Utc;
 use serde_json::Value as JsonValue;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
+/// Routes listed in the overview before the rest are summarized as a count.
+const MAX_LISTED_ROUTES: usize = 20;
+
 /// Arguments for `render_context_pack`.
 #[allow(missing_docs)]
 pub struct ContextPackCtx<'a> {
@@ -63,6 +69,22 @@ pub fn render_context_pack(
     full_inventory: bool,
     include_timestamp: bool,
 ) -> String {
+    // Group chunks by file path, sorted by file priority then path.
+    let file_priorities: HashMap<&str, f64> =
+        files.iter().map(|f| (f.relative_path.as_str(), f.priority)).collect();
+    let mut chunks_by_file: HashMap<&str, Vec<&Chunk>> = HashMap::new();
+    for chunk in chunks {
+        chunks_by_file.entry(chunk.path.as_str()).or_default().push(chunk);
+    }
+
+    let mut sorted_paths: Vec<&str> = chunks_by_file.keys().copied().collect();
+    sorted_paths.sort_by(|a, b| {
+        let pa = file_priorities.get(a).copied().unwrap_or(0.0);
+        let pb = file_priorities.get(b).copied().unwrap_or(0.0);
+        pb.partial_cmp(&pa).unwrap_or(std::cmp::Ordering::Equal).then_with(|| a.cmp(b))
+    });
+    let anchors = section_anchors(&sorted_paths);
+
     let mut out = String::new();
 
     // ── Header ──────────────────────────────────────────────────────────────
@@ -87,6 +109,8 @@ pub fn render_context_pack(
     ));
     out.push_str("\n---\n\n");
 
+    render_table_of_contents(&mut out, &sorted_paths, &anchors);
+
     render_inventory(&mut out, stats, files, dispositions, full_inventory);
 
     // ── Repository Overview ──────────────────────────────────────────────────
@@ -100,6 +124,17 @@ pub fn render_context_pack(
         out.push_str(&format!("**Description:** {}\n\n", description));
     }
 
+    // Usage/API sections lifted from the README by the ranker.
+    if let Some(JsonValue::Object(sections)) = manifest_info.get("readme_sections") {
+        for (key, label) in [("usage", "Usage"), ("api", "API")] {
+            if let Some(body) = sections.get(key).and_then(JsonValue::as_str) {
+                if !body.is_empty() {
+                    out.push_str(&format!("**{}:**\n\n{}\n\n", label, body));
+                }
+            }
+        }
+    }
+
     // Language summary (top 5, bold paragraph — matches Python renderer.py lines 144-149)
     let mut lang_counts: Vec<_> = stats.languages_detected.iter().collect();
     lang_counts.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
@@ -127,6 +162,28 @@ pub fn render_context_pack(
         }
     }
 
+    // HTTP routes declared in the included chunks (actix/axum, Flask/FastAPI, NestJS).
+    let routes = extract_openapi_routes(chunks);
+    if !routes.is_empty() {
+        let chunk_paths: HashMap<&str, &str> =
+            chunks.iter().map(|c| (c.id.as_str(), c.path.as_str())).collect();
+        out.push_str("\n**HTTP Routes:**\n");
+        for route in routes.iter().take(MAX_LISTED_ROUTES) {
+            let path = chunk_paths.get(route.chunk_id.as_str()).copied().unwrap_or("");
+            if route.handler_name.is_empty() {
+                out.push_str(&format!("- `{} {}` (`{}`)\n", route.method, route.path, path));
+            } else {
+                out.push_str(&format!(
+                    "- `{} {}` → `{}` (`{}`)\n",
+                    route.method, route.path, route.handler_name, path
+                ));
+            }
+        }
+        if routes.len() > MAX_LISTED_ROUTES {
+            out.push_str(&format!("- … {} more\n", routes.len() - MAX_LISTED_ROUTES));
+        }
+    }
+
     // Available Commands from package.json scripts (matches Python _render_overview lines 159-168)
     if let Some(JsonValue::Object(scripts)) = manifest_info.get("scripts") {
         let known_cmds = ["build", "test", "start", "dev", "lint"];
@@ -148,37 +205,8 @@ pub fn render_context_pack(
 
     // README excerpt — find the highest-priority readme file and show first 15 meaningful lines.
     // Matches Python renderer.py lines 170-190.
-    // Uses already-processed (redacted) chunks to avoid re-reading raw source from disk.
-    if let Some(readme) = files.iter().find(|f| f.is_readme) {
-        let readme_chunks: Vec<&Chunk> =
-            chunks.iter().filter(|c| c.path == readme.relative_path).collect();
-        if let Some(first_chunk) = readme_chunks.first() {
-            let content = &first_chunk.content;
-            let readme_lines: Vec<&str> = content.lines().collect();
-            let mut excerpt_lines: Vec<&str> = Vec::new();
-            let mut in_content = false;
-            for line in readme_lines.iter().take(50) {
-                let trimmed = line.trim();
-                // Skip badges only before content begins (Python: badge check before in_content=True)
-                if !in_content {
-                    if trimmed.starts_with("![") || trimmed.starts_with("[![") {
-                        continue;
-                    }
-                    if trimmed.is_empty() {
-                        continue;
-                    }
-                    in_content = true;
-                }
-                excerpt_lines.push(line);
-                if excerpt_lines.len() >= 15 {
-                    break;
-                }
-            }
-            if !excerpt_lines.is_empty() {
-                let excerpt = excerpt_lines.join("\n");
-                out.push_str(&format!("\n**README Excerpt:**\n\n{}\n", excerpt));
-            }
-        }
+    if let Some(excerpt) = readme_excerpt(files, chunks) {
+        out.push_str(&format!("\n**README Excerpt:**\n\n{}\n", excerpt));
     }
     out.push('\n');
 
@@ -273,31 +301,16 @@ pub fn render_context_pack(
     // ── File Contents ────────────────────────────────────────────────────────
     out.push_str("## 📄 File Contents\n\n");
 
-    // Group chunks by file path, sorted by file priority then path.
-    let file_priorities: HashMap<&str, f64> =
-        files.iter().map(|f| (f.relative_path.as_str(), f.priority)).collect();
-    let mut chunks_by_file: HashMap<&str, Vec<&Chunk>> = HashMap::new();
-    for chunk in chunks {
-        chunks_by_file.entry(chunk.path.as_str()).or_default().push(chunk);
-    }
-
-    let mut sorted_paths: Vec<&&str> = chunks_by_file.keys().collect();
-    sorted_paths.sort_by(|a, b| {
-        let pa = file_priorities.get(**a).copied().unwrap_or(0.0);
-        let pb = file_priorities.get(**b).copied().unwrap_or(0.0);
-        pb.partial_cmp(&pa).unwrap_or(std::cmp::Ordering::Equal).then_with(|| a.cmp(b))
-    });
-
-    for path in sorted_paths {
+    for (path, anchor) in sorted_paths.iter().zip(&anchors) {
         let file_chunks = chunks_by_file.get(path).unwrap();
         let mut sorted_chunks: Vec<&&Chunk> = file_chunks.iter().collect();
         sorted_chunks.sort_by(|a, b| a.start_line.cmp(&b.start_line).then_with(|| a.id.cmp(&b.id)));
 
         let lang = sorted_chunks.first().map(|c| c.language.as_str()).unwrap_or("text");
-        let priority = file_priorities.get(*path).copied().unwrap_or(0.5);
+        let priority = file_priorities.get(path).copied().unwrap_or(0.5);
 
         // Per-file header with metadata
-        out.push_str(&format!("### `{}`\n\n", path));
+        out.push_str(&format!("<a id=\"{}\"></a>\n\n### `{}`\n\n", anchor, path));
         out.push_str(&format!(
             "*Priority: {:.0}% | Language: {} | Chunks: {}*\n\n",
             priority * 100.0,
@@ -306,21 +319,7 @@ pub fn render_context_pack(
         ));
 
         for chunk in dedupe_overlapping_chunks(sorted_chunks.into_iter().copied().collect()) {
-            let mut notes: Vec<String> = chunk
-                .tags
-                .iter()
-                .filter_map(|tag| {
-                    if let Some(reason) = tag.strip_prefix("reason:") {
-                        return Some(reason.to_string());
-                    }
-                    if let Some(stitch) = tag.strip_prefix("stitch:") {
-                        return Some(format!("stitched:{stitch}"));
-                    }
-                    None
-                })
-                .collect();
-            notes.sort();
-            notes.dedup();
+            let notes = chunk_notes(&chunk);
             if notes.is_empty() {
                 out.push_str(&format!("**Lines {}-{}:**\n\n", chunk.start_line, chunk.end_line));
             } else {
@@ -343,6 +342,211 @@ pub fn render_context_pack(
     out
 }
 
+/// Merges rendered context packs from several repositories into one pack.
+///
+/// Chunks keep their pack order (and each pack's file priority order). A
+/// chunk is dropped when one with the same ID (path, line range, and content)
+/// was already taken, or when it would push the total past `max_tokens`
+/// counted with `tokenizer`. File sections are prefixed with their repository
+/// name, each tree is labelled `# <repo>` in the combined tree, and a Sources
+/// section lists what each pack contributed.
+///
+/// # Errors
+/// Returns an error if any input is not a context pack
+pub fn merge_context_packs(
+    packs: Vec<&str>,
+    max_tokens: usize,
+    tokenizer: Tokenizer,
+) -> Result<String> {
+    let docs = packs
+        .iter()
+        .enumerate()
+        .map(|(idx, pack)| {
+            parse_context_pack(pack).with_context(|| format!("Invalid context pack #{}", idx + 1))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut tokens = 0usize;
+    let mut sections: Vec<(String, &ContextPackFile, Vec<&ContextPackChunk>)> = Vec::new();
+    let mut sources: Vec<(&str, usize, usize)> = Vec::new();
+    for doc in &docs {
+        let (mut source_files, mut source_chunks) = (0, 0);
+        for file in &doc.files {
+            let mut kept = Vec::new();
+            for chunk in &file.chunks {
+                let chunk_tokens = tokenizer.count(&chunk.content);
+                if tokens + chunk_tokens > max_tokens {
+                    continue;
+                }
+                let id = stable_hash(&chunk.content, &file.path, chunk.start_line, chunk.end_line);
+                if seen.insert(id) {
+                    tokens += chunk_tokens;
+                    kept.push(chunk);
+                }
+            }
+            if !kept.is_empty() {
+                source_files += 1;
+                source_chunks += kept.len();
+                sections.push((format!("{}/{}", doc.header.repo_name, file.path), file, kept));
+            }
+        }
+        sources.push((doc.header.repo_name.as_str(), source_files, source_chunks));
+    }
+
+    let chunk_count: usize = sections.iter().map(|(_, _, chunks)| chunks.len()).sum();
+    let size: usize =
+        sections.iter().flat_map(|(_, _, chunks)| chunks).map(|c| c.content.len()).sum();
+    let names: Vec<&str> = sources.iter().map(|(name, _, _)| *name).collect();
+
+    let mut out = format!("# Repository Context Pack: {}\n\n", names.join(" + "));
+    out.push_str("> Generated by repo-context\n");
+    out.push_str(&format!(
+        "> Files: {} | Chunks: {} | Size: {} bytes\n",
+        sections.len(),
+        chunk_count,
+        format_with_commas(size as u64)
+    ));
+    out.push_str("\n---\n\n");
+
+    out.push_str("## Sources\n\n");
+    for (name, files, chunks) in &sources {
+        out.push_str(&format!("- `{}` (files: {}, chunks: {})\n", name, files, chunks));
+    }
+    out.push('\n');
+
+    out.push_str("## 📁 Directory Structure\n\n```\n");
+    let trees: Vec<String> =
+        docs.iter().map(|doc| format!("# {}\n{}", doc.header.repo_name, doc.file_tree)).collect();
+    out.push_str(&trees.join("\n\n"));
+    out.push_str("\n```\n\n");
+
+    out.push_str("## 📄 File Contents\n\n");
+    let paths: Vec<&str> = sections.iter().map(|(path, _, _)| path.as_str()).collect();
+    for ((path, file, chunks), anchor) in sections.iter().zip(section_anchors(&paths)) {
+        out.push_str(&format!("<a id=\"{}\"></a>\n\n### `{}`\n\n", anchor, path));
+        out.push_str(&format!(
+            "*Priority: {:.0}% | Language: {} | Chunks: {}*\n\n",
+            file.priority * 100.0,
+            file.language,
+            chunks.len()
+        ));
+        for chunk in chunks {
+            if chunk.notes.is_empty() {
+                out.push_str(&format!("**Lines {}-{}:**\n\n", chunk.start_line, chunk.end_line));
+            } else {
+                out.push_str(&format!(
+                    "**Lines {}-{}:** `[{}]`\n\n",
+                    chunk.start_line,
+                    chunk.end_line,
+                    chunk.notes.join(" | ")
+                ));
+            }
+            out.push_str(&format!(
+                "```{}\n{}\n```\n\n",
+                escape_fence_lang(&chunk.language),
+                chunk.content.trim_end()
+            ));
+        }
+    }
+
+    Ok(out)
+}
+
+/// Collapsible list of links to each file section.
+fn render_table_of_contents(out: &mut String, paths: &[&str], anchors: &[String]) {
+    if paths.is_empty() {
+        return;
+    }
+    out.push_str("<details>\n<summary>Table of Contents</summary>\n\n");
+    for (path, anchor) in paths.iter().zip(anchors) {
+        out.push_str(&format!("- [`{}`](#{})\n", path, anchor));
+    }
+    out.push_str("\n</details>\n\n");
+}
+
+/// Link target IDs for file sections, one per path, in the same order.
+///
+/// IDs are `file-` plus the lowercased path with every run of
+/// non-alphanumerics collapsed to `-` (`src/auth.rs` → `file-src-auth-rs`).
+/// Paths that collapse to the same ID get `-2`, `-3`, ... suffixes.
+pub(crate) fn section_anchors(paths: &[&str]) -> Vec<String> {
+    let mut used: HashSet<String> = HashSet::new();
+    paths
+        .iter()
+        .map(|path| {
+            let mut slug = String::from("file-");
+            for c in path.chars() {
+                if c.is_ascii_alphanumeric() {
+                    slug.push(c.to_ascii_lowercase());
+                } else if !slug.ends_with('-') {
+                    slug.push('-');
+                }
+            }
+            let slug = slug.trim_end_matches('-').to_string();
+            let mut anchor = slug.clone();
+            let mut n = 1;
+            while !used.insert(anchor.clone()) {
+                n += 1;
+                anchor = format!("{slug}-{n}");
+            }
+            anchor
+        })
+        .collect()
+}
+
+/// First 15 meaningful lines of the highest-priority README, skipping leading badges.
+///
+/// Uses already-processed (redacted) chunks to avoid re-reading raw source from disk.
+pub(crate) fn readme_excerpt(files: &[FileInfo], chunks: &[Chunk]) -> Option<String> {
+    let readme = files.iter().find(|f| f.is_readme)?;
+    let first_chunk = chunks.iter().find(|c| c.path == readme.relative_path)?;
+    let mut excerpt_lines: Vec<&str> = Vec::new();
+    let mut in_content = false;
+    for line in first_chunk.content.lines().take(50) {
+        let trimmed = line.trim();
+        // Skip badges only before content begins (Python: badge check before in_content=True)
+        if !in_content {
+            if trimmed.starts_with("![") || trimmed.starts_with("[![") {
+                continue;
+            }
+            if trimmed.is_empty() {
+                continue;
+            }
+            in_content = true;
+        }
+        excerpt_lines.push(line);
+        if excerpt_lines.len() >= 15 {
+            break;
+        }
+    }
+    if excerpt_lines.is_empty() {
+        None
+    } else {
+        Some(excerpt_lines.join("\n"))
+    }
+}
+
+/// Annotations shown next to a chunk's line range (`reason:` and `stitch:` tags).
+pub(crate) fn chunk_notes(chunk: &Chunk) -> Vec<String> {
+    let mut notes: Vec<String> = chunk
+        .tags
+        .iter()
+        .filter_map(|tag| {
+            if let Some(reason) = tag.strip_prefix("reason:") {
+                return Some(reason.to_string());
+            }
+            if let Some(stitch) = tag.strip_prefix("stitch:") {
+                return Some(format!("stitched:{stitch}"));
+            }
+            None
+        })
+        .collect();
+    notes.sort();
+    notes.dedup();
+    notes
+}
+
 fn capitalize(s: &str) -> String {
     let mut chars = s.chars();
     match chars.next() {
@@ -357,7 +561,7 @@ fn escape_table_cell(s: &str) -> String {
 }
 
 /// Sanitize a language identifier for use in a Markdown code fence.
-fn escape_fence_lang(lang: &str) -> String {
+pub(crate) fn escape_fence_lang(lang: &str) -> String {
     lang.chars()
         .filter(|c| c.is_alphanumeric() || *c == '_' || *c == '-' || *c == '+')
         .take(20)
@@ -420,7 +624,7 @@ fn render_inventory(
     out.push('\n');
 }
 
-fn dedupe_overlapping_chunks(chunks: Vec<&Chunk>) -> Vec<Chunk> {
+pub(crate) fn dedupe_overlapping_chunks(chunks: Vec<&Chunk>) -> Vec<Chunk> {
     let mut rendered = Vec::new();
     let mut next_line = 1usize;
     for chunk in chunks {
@@ -447,9 +651,14 @@ fn dedupe_overlapping_chunks(chunks: Vec<&Chunk>) -> Vec<Chunk> {
 
 #[cfg(test)]
 mod tests {
-    use super::dedupe_overlapping_chunks;
-    use crate::domain::Chunk;
-    use std::collections::BTreeSet;
+    use super::{
+        dedupe_overlapping_chunks, merge_context_packs, render_context_pack, section_anchors,
+    };
+    use crate::domain::{Chunk, ScanStats};
+    use crate::render::parse_context_pack;
+    use crate::utils::Tokenizer;
+    use std::collections::{BTreeSet, HashMap, HashSet};
+    use std::path::Path;
 
     fn chunk(id: &str, start: usize, end: usize, content: &str) -> Chunk {
         Chunk {
@@ -483,4 +692,182 @@ mod tests {
         assert_eq!(deduped[1].start_line, 4);
         assert_eq!(deduped[1].content, "line4\nline5\n");
     }
+
+    #[test]
+    fn section_anchors_are_slugged_and_unique() {
+        let anchors = section_anchors(&["src/auth.rs", "src/auth_rs", "src/Auth.rs", "README.md"]);
+
+        assert_eq!(
+            anchors,
+            ["file-src-auth-rs", "file-src-auth-rs-2", "file-src-auth-rs-3", "file-readme-md"]
+        );
+        assert_eq!(anchors.iter().collect::<HashSet<_>>().len(), anchors.len());
+    }
+
+    #[test]
+    fn table_of_contents_links_match_section_anchors() {
+        let mut auth = chunk("a", 1, 2, "fn verify() {}\n");
+        auth.path = "src/auth.rs".to_string();
+        let mut auth_alt = chunk("b", 1, 2, "fn other() {}\n");
+        auth_alt.path = "src/auth_rs".to_string();
+        let stats = ScanStats::default();
+
+        let out = render_context_pack(
+            Path::new("/tmp/demo"),
+            &[],
+            &[auth, auth_alt],
+            &stats,
+            "",
+            &HashMap::new(),
+            &[],
+            false,
+            false,
+        );
+
+        let toc_start = out.find("<details>\n<summary>Table of Contents</summary>").expect("toc");
+        let toc_end = toc_start + out[toc_start..].find("</details>").expect("toc end");
+        let targets: Vec<&str> = out[toc_start..toc_end]
+            .lines()
+            .filter_map(|line| line.split("](#").nth(1))
+            .map(|rest| rest.trim_end_matches(')'))
+            .collect();
+
+        assert_eq!(targets, ["file-src-auth-rs", "file-src-auth-rs-2"]);
+        for target in targets {
+            assert_eq!(out.matches(&format!("<a id=\"{target}\"></a>")).count(), 1);
+        }
+        assert!(out.contains("<a id=\"file-src-auth-rs\"></a>\n\n### `src/auth.rs`"));
+    }
+
+    #[test]
+    fn readme_usage_and_api_sections_are_surfaced() {
+        let manifest = HashMap::from([(
+            "readme_sections".to_string(),
+            serde_json::json!({"usage": "Run `demo`.", "api": "Call `run()`.", "license": "MIT"}),
+        )]);
+        let stats = ScanStats::default();
+
+        let out = render_context_pack(
+            Path::new("/tmp/demo"),
+            &[],
+            &[],
+            &stats,
+            "",
+            &manifest,
+            &[],
+            false,
+            false,
+        );
+
+        assert!(out.contains("**Usage:**\n\nRun `demo`.\n\n"));
+        assert!(out.contains("**API:**\n\nCall `run()`.\n\n"));
+        assert!(!out.contains("MIT"));
+    }
+
+    #[test]
+    fn http_routes_are_listed_in_overview() {
+        let mut api = chunk(
+            "a",
+            1,
+            3,
+            "#[get(\"/users/{id}\")]\nasync fn get_user() {}\n#[post(\"/users\")]\n",
+        );
+        api.path = "src/api.rs".to_string();
+        let stats = ScanStats::default();
+
+        let out = render_context_pack(
+            Path::new("/tmp/demo"),
+            &[],
+            &[api],
+            &stats,
+            "",
+            &HashMap::new(),
+            &[],
+            false,
+            false,
+        );
+
+        assert!(out.contains(
+            "**HTTP Routes:**\n- `POST /users` (`src/api.rs`)\n- `GET /users/{id}` → `get_user` (`src/api.rs`)\n"
+        ));
+    }
+
+    fn pack(root: &str, chunks: &[Chunk]) -> String {
+        let stats = ScanStats::default();
+        render_context_pack(
+            Path::new(root),
+            &[],
+            chunks,
+            &stats,
+            &format!("{}/\n└── src/", root.rsplit('/').next().unwrap_or(root)),
+            &HashMap::new(),
+            &[],
+            false,
+            false,
+        )
+    }
+
+    #[test]
+    fn merged_pack_dedupes_identical_chunks_and_lists_sources() {
+        let shared = chunk("a", 1, 2, "pub fn shared() {}\n");
+        let mut client = chunk("b", 1, 2, "fn call_service_b() {}\n");
+        client.path = "src/client.rs".to_string();
+        let mut handler = chunk("c", 1, 2, "fn handle() {}\n");
+        handler.path = "src/handler.rs".to_string();
+        let a = pack("/tmp/service-a", &[shared.clone(), client]);
+        let b = pack("/tmp/service-b", &[shared, handler]);
+
+        let merged =
+            merge_context_packs(vec![&a, &b], usize::MAX, Tokenizer::Heuristic).expect("merge");
+        let doc = parse_context_pack(&merged).expect("parse merged");
+
+        assert_eq!(doc.header.repo_name, "service-a + service-b");
+        assert_eq!(doc.header.chunks, 3);
+        let paths: Vec<&str> = doc.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            ["service-a/src/client.rs", "service-a/src/lib.rs", "service-b/src/handler.rs"]
+        );
+        assert!(merged.contains(
+            "## Sources\n\n- `service-a` (files: 2, chunks: 2)\n- `service-b` (files: 1, chunks: 1)\n"
+        ));
+        assert!(doc.file_tree.starts_with("# service-a\nservice-a/\n"));
+        assert!(doc.file_tree.contains("\n\n# service-b\nservice-b/\n"));
+    }
+
+    #[test]
+    fn merged_pack_respects_the_token_budget() {
+        let big = chunk("a", 1, 40, &"let value = compute_something();\n".repeat(40));
+        let mut small = chunk("b", 1, 2, "fn tiny() {}\n");
+        small.path = "src/tiny.rs".to_string();
+        let a = pack("/tmp/service-a", &[big]);
+        let b = pack("/tmp/service-b", &[small]);
+
+        // The budget is counted with the configured tokenizer.
+        let tokenizer = Tokenizer::Cl100k;
+        let merged = merge_context_packs(vec![&a, &b], 50, tokenizer).expect("merge");
+        let doc = parse_context_pack(&merged).expect("parse merged");
+
+        let tokens: usize =
+            doc.files.iter().flat_map(|f| &f.chunks).map(|c| tokenizer.count(&c.content)).sum();
+        assert!(tokens <= 50);
+        assert_eq!(doc.files.len(), 1);
+        assert_eq!(doc.files[0].path, "service-b/src/tiny.rs");
+    }
+
+    #[test]
+    fn merged_pack_escapes_fence_languages_and_keeps_backtick_content() {
+        let content = "let doc = r#\"\n```\nexample\n```\n\"#;\n";
+        let a = pack("/tmp/service-a", &[chunk("a", 1, 5, content)]);
+        // A pack edited by hand (or from an older version) with an unsafe fence label.
+        let a = a.replace("```rust\n", "```rust `x\n");
+
+        let merged =
+            merge_context_packs(vec![&a], usize::MAX, Tokenizer::Heuristic).expect("merge");
+        assert!(merged.contains("```rustx\n"));
+        let doc = parse_context_pack(&merged).expect("parse merged");
+        let merged_chunk = &doc.files[0].chunks[0];
+        assert_eq!(merged_chunk.language, "rustx");
+        assert_eq!(merged_chunk.content, content.trim_end());
+    }
 }
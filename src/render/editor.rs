@@ -0,0 +1,162 @@
+//! Editor snippet rendering (Monaco / VS Code)
+//!
+//! Serializes chunks as JSON Lines for editor integrations: a header object
+//! with the `workspaceRoot` URI, then one snippet per chunk with its `file://`
+//! URI, 1-based line range, and a Monaco `IRange`.
+
+use crate::domain::Chunk;
+use serde_json::json;
+use std::path::Path;
+
+/// Renders chunks as editor snippet JSON Lines.
+///
+/// The first line is `{"type":"header","workspaceRoot":...,"snippetCount":N}`.
+/// Each snippet carries `startLine` / `endLine` (1-based, inclusive) and a
+/// `range` object in Monaco `IRange` form; subtract one from the line
+/// numbers for a `vscode.Range`. `language` is the Monaco language id.
+///
+/// # Arguments
+/// * `root_path` - Repository root; chunk paths are resolved against it
+/// * `chunks` - Chunks to render
+///
+/// # Returns
+/// JSON Lines formatted string (header plus one snippet per line)
+pub fn render_editor_snippets(root_path: &Path, chunks: &[Chunk]) -> String {
+    let workspace_root = file_uri(&root_path.to_string_lossy());
+
+    let header = json!({
+        "type": "header",
+        "workspaceRoot": workspace_root,
+        "snippetCount": chunks.len(),
+    });
+    let mut out = header.to_string();
+    out.push('\n');
+
+    for chunk in chunks {
+        let end_column = chunk.content.lines().last().map_or(1, |line| line.chars().count() + 1);
+        let snippet = json!({
+            "id": chunk.id,
+            "language": monaco_language(&chunk.language),
+            "uri": format!("{}/{}", workspace_root.trim_end_matches('/'), encode_path(&chunk.path)),
+            "path": chunk.path,
+            "startLine": chunk.start_line,
+            "endLine": chunk.end_line,
+            "range": {
+                "startLineNumber": chunk.start_line,
+                "startColumn": 1,
+                "endLineNumber": chunk.end_line,
+                "endColumn": end_column,
+            },
+            "content": chunk.content,
+        });
+        out.push_str(&snippet.to_string());
+        out.push('\n');
+    }
+    out
+}
+
+/// `file://` URI for an absolute path (Windows drive paths get a leading `/`).
+fn file_uri(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let path = if path.starts_with('/') { path } else { format!("/{path}") };
+    format!("file://{}", encode_path(&path))
+}
+
+/// Percent-encode a `/`-separated path for use in a URI.
+fn encode_path(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-._~:".contains(&byte) {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    out
+}
+
+/// Monaco language id for a repo-context language name.
+fn monaco_language(language: &str) -> &str {
+    match language {
+        "bash" | "zsh" | "shell" => "shell",
+        "protobuf" => "proto",
+        "text" | "makefile" => "plaintext",
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_editor_snippets;
+    use crate::domain::Chunk;
+    use serde_json::Value;
+    use std::collections::BTreeSet;
+    use std::path::Path;
+
+    fn mk_chunk(id: &str, path: &str, language: &str, content: &str) -> Chunk {
+        Chunk {
+            id: id.to_string(),
+            path: path.to_string(),
+            language: language.to_string(),
+            start_line: 10,
+            end_line: 9 + content.lines().count(),
+            content: content.to_string(),
+            priority: 0.5,
+            tags: BTreeSet::new(),
+            token_estimate: 3,
+            file_id: "f".to_string(),
+            chunk_index: 0,
+            chunks_in_file: 1,
+            byte_start: None,
+            byte_end: None,
+            content_sha256: String::new(),
+            file_sha256: String::new(),
+        }
+    }
+
+    fn render(chunks: &[Chunk]) -> Vec<Value> {
+        render_editor_snippets(Path::new("/home/dev/my repo"), chunks)
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn header_and_uris_are_file_uris_under_the_workspace_root() {
+        let chunks = [
+            mk_chunk("a", "src/main.rs", "rust", "fn main() {\n    run();\n}\n"),
+            mk_chunk("b", "scripts/deploy #2.sh", "bash", "echo hi\n"),
+        ];
+        let lines = render(&chunks);
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["type"], "header");
+        assert_eq!(lines[0]["workspaceRoot"], "file:///home/dev/my%20repo");
+        assert_eq!(lines[0]["snippetCount"], 2);
+        assert_eq!(lines[1]["uri"], "file:///home/dev/my%20repo/src/main.rs");
+        assert_eq!(lines[2]["uri"], "file:///home/dev/my%20repo/scripts/deploy%20%232.sh");
+        assert_eq!(lines[2]["language"], "shell");
+    }
+
+    #[test]
+    fn snippets_carry_all_monaco_range_fields() {
+        let lines = render(&[mk_chunk("a", "src/lib.rs", "rust", "fn a() {\n}\n")]);
+        let snippet = &lines[1];
+
+        for field in ["language", "uri", "startLine", "endLine", "content"] {
+            assert!(snippet.get(field).is_some(), "missing {field}");
+        }
+        assert_eq!(snippet["startLine"], 10);
+        assert_eq!(snippet["endLine"], 11);
+        assert_eq!(
+            snippet["range"],
+            serde_json::json!({
+                "startLineNumber": 10,
+                "startColumn": 1,
+                "endLineNumber": 11,
+                "endColumn": 2,
+            })
+        );
+        assert_eq!(snippet["content"], "fn a() {\n}\n");
+    }
+}
@@ -0,0 +1,85 @@
+//! GitHub Actions workflow-command annotations
+//!
+//! Formats export findings as `::warning ...::` / `::error ...::` lines, which
+//! GitHub Actions turns into annotations on the run and pull request when
+//! they are printed to stdout.
+
+use std::collections::BTreeMap;
+
+/// Returns true when `GITHUB_ACTIONS=true` is set in the environment.
+pub fn running_in_github_actions() -> bool {
+    std::env::var("GITHUB_ACTIONS").is_ok_and(|value| value.eq_ignore_ascii_case("true"))
+}
+
+/// One `::warning` per redaction rule that matched in each file.
+///
+/// `redactions` maps relative file paths to per-rule match counts. Match
+/// locations are not tracked, so every annotation points at line 1.
+pub fn redaction_annotations(
+    redactions: &BTreeMap<String, BTreeMap<String, usize>>,
+) -> Vec<String> {
+    redactions
+        .iter()
+        .flat_map(|(path, counts)| {
+            counts.keys().map(move |rule| {
+                format!(
+                    "::warning file={},line=1::{}",
+                    escape_property(path),
+                    escape_data(&format!("[REDACTED] {rule} pattern found"))
+                )
+            })
+        })
+        .collect()
+}
+
+/// A file-less `::error` annotation.
+pub fn error_annotation(message: &str) -> String {
+    format!("::error::{}", escape_data(message))
+}
+
+/// Escape an annotation message (`%`, CR, LF).
+fn escape_data(value: &str) -> String {
+    value.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Escape an annotation property value; `:` and `,` delimit properties too.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{error_annotation, redaction_annotations};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn redaction_annotations_follow_workflow_command_syntax() {
+        let redactions = BTreeMap::from([
+            (
+                "config/settings.py".to_string(),
+                BTreeMap::from([
+                    ("aws_access_key".to_string(), 2),
+                    ("github_token".to_string(), 1),
+                ]),
+            ),
+            ("src/a,b:c.rs".to_string(), BTreeMap::from([("jwt".to_string(), 1)])),
+        ]);
+
+        assert_eq!(
+            redaction_annotations(&redactions),
+            [
+                "::warning file=config/settings.py,line=1::[REDACTED] aws_access_key pattern found",
+                "::warning file=config/settings.py,line=1::[REDACTED] github_token pattern found",
+                "::warning file=src/a%2Cb%3Ac.rs,line=1::[REDACTED] jwt pattern found",
+            ]
+        );
+    }
+
+    #[test]
+    fn error_annotation_escapes_newlines_and_percent() {
+        assert_eq!(
+            error_annotation("budget 100% used\nsecond line"),
+            "::error::budget 100%25 used%0Asecond line"
+        );
+    }
+}
@@ -0,0 +1,423 @@
+//! HTML rendering for human browsing
+//!
+//! Produces a single self-contained HTML page (inline CSS, no external assets)
+//! with a file tree sidebar, collapsible per-chunk sections, and syntax
+//! highlighting via `syntect`.
+
+use crate::domain::Chunk;
+use crate::utils::format_with_commas;
+use once_cell::sync::Lazy;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::ThemeSet;
+use syntect::html::{styled_line_to_highlighted_html, IncludeBackground};
+use syntect::parsing::{SyntaxReference, SyntaxSet};
+use syntect::util::LinesWithEndings;
+
+pub(crate) static SYNTAX_SET: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines);
+pub(crate) static THEME_SET: Lazy<ThemeSet> = Lazy::new(ThemeSet::load_defaults);
+
+const HIGHLIGHT_THEME: &str = "InspiredGitHub";
+
+const STYLE: &str = r#"
+* { box-sizing: border-box; }
+body { margin: 0; font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; color: #1f2328; display: flex; }
+nav { width: 300px; height: 100vh; overflow-y: auto; position: sticky; top: 0; padding: 1rem; border-right: 1px solid #d0d7de; background: #f6f8fa; font-size: 13px; }
+nav ul { list-style: none; padding-left: 1rem; margin: 0; }
+nav > ul { padding-left: 0; }
+nav a { color: #0969da; text-decoration: none; }
+nav .dir { font-weight: 600; }
+main { flex: 1; min-width: 0; padding: 1rem 2rem; }
+h1 { font-size: 1.5rem; }
+h2 { font-size: 1.1rem; font-family: ui-monospace, SFMono-Regular, Menlo, monospace; border-bottom: 1px solid #d0d7de; padding-bottom: .3rem; }
+details { margin: .5rem 0; border: 1px solid #d0d7de; border-radius: 6px; }
+summary { cursor: pointer; padding: .4rem .6rem; background: #f6f8fa; font-size: 13px; }
+pre { margin: 0; padding: .75rem; overflow-x: auto; font-size: 12px; line-height: 1.45; }
+code { font-family: ui-monospace, SFMono-Regular, Menlo, monospace; }
+.budget { height: 10px; background: #eaeef2; border-radius: 5px; overflow: hidden; max-width: 480px; }
+.budget-fill { height: 100%; background: #2da44e; }
+.budget-fill.over { background: #cf222e; }
+.budget-label { font-size: 13px; color: #57606a; }
+pre a { color: inherit; text-decoration: underline dotted; }
+"#;
+
+/// Renders chunks as a self-contained HTML page.
+///
+/// Chunks are grouped by file in the order they appear (priority order), and
+/// each file gets an anchor referenced from the sidebar tree. Identifiers
+/// naming a symbol defined elsewhere in the export link to its chunk (see
+/// [`resolve_cross_references`]).
+///
+/// # Arguments
+/// * `root_path` - Repository root (used for the page title)
+/// * `chunks` - Chunks to render
+/// * `max_tokens` - Token budget for the budget bar (`None` = unbounded)
+///
+/// # Returns
+/// The complete HTML document
+pub fn render_html(root_path: &Path, chunks: &[Chunk], max_tokens: Option<usize>) -> String {
+    let repo_name = root_path.file_name().and_then(|n| n.to_str()).unwrap_or("repository");
+
+    // Group chunks by file, preserving first-appearance order.
+    let mut file_order: Vec<&str> = Vec::new();
+    let mut by_file: BTreeMap<&str, Vec<&Chunk>> = BTreeMap::new();
+    for chunk in chunks {
+        let entry = by_file.entry(chunk.path.as_str()).or_default();
+        if entry.is_empty() {
+            file_order.push(chunk.path.as_str());
+        }
+        entry.push(chunk);
+    }
+    let cross_refs = resolve_cross_references(chunks);
+
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str(&format!("<title>{} — context pack</title>\n", escape_html(repo_name)));
+    out.push_str(&format!("<style>{STYLE}</style>\n</head>\n<body>\n"));
+
+    out.push_str("<nav>\n");
+    out.push_str(&render_file_tree(&file_order));
+    out.push_str("</nav>\n<main>\n");
+
+    out.push_str(&format!("<h1>{}</h1>\n", escape_html(repo_name)));
+    out.push_str(&render_budget_bar(chunks, max_tokens));
+
+    for path in &file_order {
+        out.push_str(&format!(
+            "<section class=\"file\" id=\"{}\">\n<h2>{}</h2>\n",
+            file_anchor(path),
+            escape_html(path)
+        ));
+        for chunk in &by_file[path] {
+            let anchor = chunk_anchor(chunk);
+            out.push_str(&format!(
+                "<details open id=\"{}\">\n<summary>Lines {}-{} · {} tokens</summary>\n",
+                anchor,
+                chunk.start_line,
+                chunk.end_line,
+                format_with_commas(chunk.token_estimate as u64)
+            ));
+            out.push_str(&format!(
+                "<pre><code class=\"language-{}\">{}</code></pre>\n</details>\n",
+                escape_html(&chunk.language),
+                link_identifiers(
+                    &highlight_code(&chunk.content, &chunk.language),
+                    &cross_refs,
+                    &anchor
+                )
+            ));
+        }
+        out.push_str("</section>\n");
+    }
+
+    out.push_str("</main>\n</body>\n</html>\n");
+    out
+}
+
+/// Anchor id used for a file's section.
+pub(crate) fn file_anchor(path: &str) -> String {
+    let slug: String =
+        path.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '-' }).collect();
+    format!("file-{slug}")
+}
+
+/// Anchor id used for a chunk's `<details>` block.
+fn chunk_anchor(chunk: &Chunk) -> String {
+    let slug: String =
+        chunk.id.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '-' }).collect();
+    format!("chunk-{slug}")
+}
+
+/// Maps every symbol carrying a `def:<symbol>` tag to the anchor of the
+/// chunk that defines it. When several chunks define the same name, the
+/// first (highest-priority) one wins.
+pub fn resolve_cross_references(chunks: &[Chunk]) -> HashMap<String, String> {
+    let mut refs = HashMap::new();
+    for chunk in chunks {
+        for symbol in chunk.tags.iter().filter_map(|tag| tag.strip_prefix("def:")) {
+            if !symbol.is_empty() {
+                refs.entry(symbol.to_string()).or_insert_with(|| chunk_anchor(chunk));
+            }
+        }
+    }
+    refs
+}
+
+/// Wraps whole-word identifiers found in `refs` with links to their
+/// definitions. Operates on highlighted HTML: tags and character entities
+/// are copied through untouched, and links back to `self_anchor` are skipped.
+fn link_identifiers(html: &str, refs: &HashMap<String, String>, self_anchor: &str) -> String {
+    if refs.is_empty() {
+        return html.to_string();
+    }
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(first) = rest.chars().next() {
+        let end = match first {
+            '<' => rest.find('>').map_or(rest.len(), |i| i + 1),
+            '&' => rest.find(';').map_or(rest.len(), |i| i + 1),
+            c if is_ident(c) => rest.find(|c: char| !is_ident(c)).unwrap_or(rest.len()),
+            c => c.len_utf8(),
+        };
+        let (token, tail) = rest.split_at(end);
+        match refs.get(token) {
+            Some(anchor) if anchor != self_anchor => {
+                out.push_str(&format!("<a href=\"#{anchor}\">{token}</a>"));
+            }
+            _ => out.push_str(token),
+        }
+        rest = tail;
+    }
+    out
+}
+
+#[derive(Default)]
+struct TreeNode<'a> {
+    dirs: BTreeMap<&'a str, TreeNode<'a>>,
+    files: BTreeMap<&'a str, &'a str>,
+}
+
+fn render_file_tree(paths: &[&str]) -> String {
+    let mut root = TreeNode::default();
+    for &path in paths {
+        let mut node = &mut root;
+        let mut parts = path.split('/').peekable();
+        while let Some(part) = parts.next() {
+            if parts.peek().is_none() {
+                node.files.insert(part, path);
+            } else {
+                node = node.dirs.entry(part).or_default();
+            }
+        }
+    }
+
+    let mut out = String::new();
+    write_tree_node(&root, &mut out);
+    out
+}
+
+fn write_tree_node(node: &TreeNode<'_>, out: &mut String) {
+    out.push_str("<ul>\n");
+    for (name, child) in &node.dirs {
+        out.push_str(&format!("<li><span class=\"dir\">{}/</span>\n", escape_html(name)));
+        write_tree_node(child, out);
+        out.push_str("</li>\n");
+    }
+    for (name, path) in &node.files {
+        out.push_str(&format!(
+            "<li><a href=\"#{}\">{}</a></li>\n",
+            file_anchor(path),
+            escape_html(name)
+        ));
+    }
+    out.push_str("</ul>\n");
+}
+
+fn render_budget_bar(chunks: &[Chunk], max_tokens: Option<usize>) -> String {
+    let used: usize = chunks.iter().map(|c| c.token_estimate).sum();
+    let budget = max_tokens.unwrap_or(used).max(1);
+    let ratio = used as f64 / budget as f64;
+    let width = (ratio * 100.0).min(100.0);
+    let class = if ratio > 1.0 { "budget-fill over" } else { "budget-fill" };
+    let label = match max_tokens {
+        Some(limit) => format!(
+            "{} / {} tokens",
+            format_with_commas(used as u64),
+            format_with_commas(limit as u64)
+        ),
+        None => format!("{} tokens (no budget)", format_with_commas(used as u64)),
+    };
+    format!(
+        "<div class=\"budget\"><div class=\"{class}\" style=\"width: {width:.1}%\"></div></div>\n\
+         <p class=\"budget-label\">{label}</p>\n"
+    )
+}
+
+pub(crate) fn find_syntax(language: &str) -> &'static SyntaxReference {
+    // syntect's bundled grammars are keyed by extension; map our language
+    // names onto those where they differ.
+    let token = match language {
+        "python" => "py",
+        "rust" => "rs",
+        "javascript" | "typescript" | "vue" | "svelte" => "js",
+        "bash" | "zsh" | "shell" => "sh",
+        "csharp" => "cs",
+        "ruby" => "rb",
+        "markdown" => "md",
+        "restructuredtext" => "rst",
+        "scss" | "less" => "css",
+        other => other,
+    };
+    SYNTAX_SET.find_syntax_by_token(token).unwrap_or_else(|| SYNTAX_SET.find_syntax_plain_text())
+}
+
+fn highlight_code(content: &str, language: &str) -> String {
+    let Some(theme) =
+        THEME_SET.themes.get(HIGHLIGHT_THEME).or_else(|| THEME_SET.themes.values().next())
+    else {
+        return escape_html(content);
+    };
+
+    let mut highlighter = HighlightLines::new(find_syntax(language), theme);
+    let mut out = String::with_capacity(content.len() * 2);
+    for line in LinesWithEndings::from(content) {
+        let html = highlighter.highlight_line(line, &SYNTAX_SET).ok().and_then(|regions| {
+            styled_line_to_highlighted_html(&regions, IncludeBackground::No).ok()
+        });
+        match html {
+            Some(html) => out.push_str(&html),
+            None => out.push_str(&escape_html(line)),
+        }
+    }
+    out
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn mk_chunk(path: &str, language: &str, content: &str) -> Chunk {
+        Chunk {
+            id: format!("{path}-chunk"),
+            path: path.to_string(),
+            language: language.to_string(),
+            start_line: 1,
+            end_line: content.lines().count().max(1),
+            content: content.to_string(),
+            priority: 0.5,
+            tags: BTreeSet::new(),
+            token_estimate: content.len() / 4,
+            file_id: "f".to_string(),
+            chunk_index: 0,
+            chunks_in_file: 1,
+            byte_start: None,
+            byte_end: None,
+            content_sha256: String::new(),
+            file_sha256: String::new(),
+        }
+    }
+
+    #[test]
+    fn html_contains_one_code_block_per_chunk() {
+        let chunks = vec![
+            mk_chunk("src/main.rs", "rust", "fn main() {\n    println!(\"hi\");\n}\n"),
+            mk_chunk("src/lib.rs", "rust", "pub fn lib() {}\n"),
+            mk_chunk("app.py", "python", "def run():\n    return 1 < 2\n"),
+        ];
+
+        let html = render_html(Path::new("/tmp/demo"), &chunks, Some(10_000));
+
+        assert!(std::str::from_utf8(html.as_bytes()).is_ok());
+        assert_eq!(html.matches("<code").count(), chunks.len());
+        assert!(html.contains("href=\"#file-src-main-rs\""));
+        assert!(html.contains("id=\"file-src-main-rs\""));
+        assert!(!html.contains("1 < 2"), "chunk content must be escaped");
+    }
+
+    #[test]
+    fn highlighting_handles_all_supported_languages() {
+        let languages = [
+            "python",
+            "javascript",
+            "typescript",
+            "go",
+            "java",
+            "kotlin",
+            "rust",
+            "c",
+            "cpp",
+            "csharp",
+            "ruby",
+            "php",
+            "swift",
+            "scala",
+            "bash",
+            "zsh",
+            "markdown",
+            "restructuredtext",
+            "asciidoc",
+            "text",
+            "yaml",
+            "toml",
+            "json",
+            "ini",
+            "html",
+            "css",
+            "scss",
+            "less",
+            "vue",
+            "svelte",
+            "sql",
+            "dockerfile",
+            "graphql",
+            "protobuf",
+            "makefile",
+            "shell",
+        ];
+        for language in languages {
+            let html = highlight_code("let x = \"<tag>\";\n// comment\n", language);
+            assert!(!html.contains("<tag>"), "unescaped content for {language}");
+        }
+    }
+
+    #[test]
+    fn budget_bar_marks_overrun() {
+        let chunks = vec![mk_chunk("a.txt", "text", &"x".repeat(400))];
+        let html = render_budget_bar(&chunks, Some(50));
+        assert!(html.contains("budget-fill over"));
+        assert!(html.contains("100 / 50 tokens"));
+    }
+
+    #[test]
+    fn calls_to_defined_symbols_link_to_their_chunk() {
+        let mut def =
+            mk_chunk("src/auth.rs", "rust", "pub fn verify_token() -> bool {\n    true\n}\n");
+        def.tags.insert("def:verify_token".to_string());
+        let caller = mk_chunk("src/main.rs", "rust", "fn main() {\n    verify_token();\n}\n");
+        let chunks = vec![def, caller];
+
+        let refs = resolve_cross_references(&chunks);
+        assert_eq!(refs.get("verify_token").map(String::as_str), Some("chunk-src-auth-rs-chunk"));
+
+        let html = render_html(Path::new("/tmp/demo"), &chunks, None);
+        let link = "<a href=\"#chunk-src-auth-rs-chunk\">verify_token</a>";
+        assert_eq!(html.matches(link).count(), 1, "only the call site links, not the definition");
+        assert!(html.contains("id=\"chunk-src-auth-rs-chunk\""));
+    }
+
+    #[test]
+    fn linking_respects_word_boundaries_tags_and_entities() {
+        let refs = HashMap::from([
+            ("run".to_string(), "chunk-a".to_string()),
+            ("lt".to_string(), "chunk-b".to_string()),
+            ("span".to_string(), "chunk-c".to_string()),
+        ]);
+        let html = "<span style=\"x\">run(rerun, run_all) &lt; run</span>";
+
+        let linked = link_identifiers(html, &refs, "chunk-self");
+
+        assert_eq!(
+            linked,
+            "<span style=\"x\"><a href=\"#chunk-a\">run</a>(rerun, run_all) &lt; \
+             <a href=\"#chunk-a\">run</a></span>"
+        );
+    }
+}
@@ -6,15 +6,30 @@ use crate::domain::Chunk;
 use serde_json::Value;
 use std::collections::BTreeMap;
 
+/// Version of the chunks.jsonl record layout.
+///
+/// Bump the minor version for additive changes (new optional fields, or a
+/// change to how `tags` are formatted) and the major version for anything
+/// that removes or retypes an existing field.
+pub const CHUNKS_SCHEMA_VERSION: &str = "1.0";
+
+/// Discriminator written on every chunk record.
+pub const CHUNKS_FORMAT: &str = "repo-context-chunks";
+
 /// Renders chunks as JSON Lines format.
 ///
-/// Each chunk is serialized as a JSON object with fields:
+/// The first line is a header record:
+/// `{"chunk_count":N,"schema_version":"1.0","type":"header"}`.
+///
+/// Each chunk is then serialized as a JSON object with fields:
 /// - `content`: The chunk content
 /// - `end_line`: Ending line number
+/// - `format`: Always `repo-context-chunks`
 /// - `id`: Unique chunk ID
 /// - `lang`: Programming language
 /// - `path`: File path
 /// - `priority`: Priority score (rounded to 3 decimals)
+/// - `schema_version`: [`CHUNKS_SCHEMA_VERSION`]
 /// - `start_line`: Starting line number
 /// - `tags`: Array of tags
 ///
@@ -24,7 +39,13 @@ use std::collections::BTreeMap;
 /// # Returns
 /// JSON Lines formatted string (one JSON object per line)
 pub fn render_jsonl(chunks: &[Chunk]) -> String {
-    let mut lines = Vec::with_capacity(chunks.len());
+    let mut lines = Vec::with_capacity(chunks.len() + 1);
+    let header = serde_json::json!({
+        "chunk_count": chunks.len(),
+        "schema_version": CHUNKS_SCHEMA_VERSION,
+        "type": "header",
+    });
+    lines.push(header.to_string());
     for chunk in chunks {
         let mut tags: Vec<&str> = chunk.tags.iter().map(String::as_str).collect();
         tags.sort();
@@ -46,6 +67,7 @@ pub fn render_jsonl(chunks: &[Chunk]) -> String {
         entry.insert("end_line", Value::Number(chunk.end_line.into()));
         entry.insert("file_id", Value::String(chunk.file_id.clone()));
         entry.insert("file_sha256", Value::String(chunk.file_sha256.clone()));
+        entry.insert("format", Value::String(CHUNKS_FORMAT.to_string()));
         entry.insert("generated", Value::Bool(chunk.tags.contains("generated")));
         entry.insert("id", Value::String(chunk.id.clone()));
         entry.insert("lang", Value::String(chunk.language.clone()));
@@ -70,6 +92,7 @@ pub fn render_jsonl(chunks: &[Chunk]) -> String {
                 .to_string(),
             ),
         );
+        entry.insert("schema_version", Value::String(CHUNKS_SCHEMA_VERSION.to_string()));
         entry.insert("start_line", Value::Number(chunk.start_line.into()));
         entry.insert(
             "tags",
@@ -82,22 +105,18 @@ pub fn render_jsonl(chunks: &[Chunk]) -> String {
             lines.push(line);
         }
     }
-    if lines.is_empty() {
-        String::new()
-    } else {
-        format!("{}\n", lines.join("\n"))
-    }
+    format!("{}\n", lines.join("\n"))
 }
 
 #[cfg(test)]
 mod tests {
-    use super::render_jsonl;
+    use super::{render_jsonl, CHUNKS_FORMAT, CHUNKS_SCHEMA_VERSION};
     use crate::domain::Chunk;
+    use serde::Deserialize;
     use std::collections::BTreeSet;
 
-    #[test]
-    fn jsonl_includes_rich_metadata_fields() {
-        let chunk = Chunk {
+    fn sample_chunk() -> Chunk {
+        Chunk {
             id: "c1".to_string(),
             path: "src/lib.rs".to_string(),
             language: "rust".to_string(),
@@ -114,10 +133,31 @@ mod tests {
             byte_end: Some(13),
             content_sha256: "abc".to_string(),
             file_sha256: "def".to_string(),
-        };
+        }
+    }
+
+    /// A consumer written against schema 1.0.
+    #[derive(Deserialize)]
+    struct ChunkV1_0 {
+        schema_version: String,
+        path: String,
+        content: String,
+    }
+
+    /// A consumer written against a hypothetical 1.1 that adds `summary`.
+    #[derive(Deserialize)]
+    struct ChunkV1_1 {
+        schema_version: String,
+        path: String,
+        #[serde(default)]
+        summary: Option<String>,
+    }
 
-        let jsonl = render_jsonl(&[chunk]);
-        let value: serde_json::Value = serde_json::from_str(jsonl.trim()).unwrap();
+    #[test]
+    fn jsonl_includes_rich_metadata_fields() {
+        let jsonl = render_jsonl(&[sample_chunk()]);
+        let record = jsonl.lines().nth(1).expect("chunk record");
+        let value: serde_json::Value = serde_json::from_str(record).unwrap();
 
         assert_eq!(value["token_estimate"], 4);
         assert_eq!(value["file_id"], "file1");
@@ -126,4 +166,49 @@ mod tests {
         assert_eq!(value["byte_start"], 0);
         assert_eq!(value["content_sha256"], "abc");
     }
+
+    #[test]
+    fn jsonl_starts_with_header_and_versions_records() {
+        let jsonl = render_jsonl(&[sample_chunk(), sample_chunk()]);
+        let mut lines = jsonl.lines();
+
+        let header: serde_json::Value = serde_json::from_str(lines.next().unwrap()).unwrap();
+        assert_eq!(header["type"], "header");
+        assert_eq!(header["schema_version"], CHUNKS_SCHEMA_VERSION);
+        assert_eq!(header["chunk_count"], 2);
+
+        for line in lines {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(value["format"], CHUNKS_FORMAT);
+            assert_eq!(value["schema_version"], CHUNKS_SCHEMA_VERSION);
+        }
+    }
+
+    #[test]
+    fn empty_export_still_writes_header() {
+        let header: serde_json::Value = serde_json::from_str(render_jsonl(&[]).trim()).unwrap();
+        assert_eq!(header["chunk_count"], 0);
+    }
+
+    #[test]
+    fn v1_1_parser_reads_v1_0_output() {
+        let jsonl = render_jsonl(&[sample_chunk()]);
+        let record: ChunkV1_1 = serde_json::from_str(jsonl.lines().nth(1).unwrap()).unwrap();
+        assert_eq!(record.schema_version, "1.0");
+        assert_eq!(record.path, "src/lib.rs");
+        assert_eq!(record.summary, None);
+    }
+
+    #[test]
+    fn v1_0_parser_ignores_fields_added_in_v1_1() {
+        let mut value: serde_json::Value =
+            serde_json::from_str(render_jsonl(&[sample_chunk()]).lines().nth(1).unwrap()).unwrap();
+        value["schema_version"] = "1.1".into();
+        value["summary"] = "Program entrypoint".into();
+
+        let record: ChunkV1_0 = serde_json::from_value(value).unwrap();
+        assert_eq!(record.schema_version, "1.1");
+        assert_eq!(record.path, "src/lib.rs");
+        assert_eq!(record.content, "fn main() {}\n");
+    }
 }
@@ -0,0 +1,94 @@
+//! LangChain document rendering
+//!
+//! Serializes chunks as LangChain `Document` objects (`page_content` +
+//! `metadata`), one JSON object per line, ready for vectorstore loaders.
+
+use crate::domain::Chunk;
+use serde_json::json;
+
+/// Renders chunks as LangChain `Document` JSON Lines.
+///
+/// Each line has the shape:
+/// `{"page_content": "...", "metadata": {"source", "start_line", "end_line",
+/// "language", "priority", "tags"}}`.
+///
+/// # Arguments
+/// * `chunks` - Chunks to render
+///
+/// # Returns
+/// JSON Lines formatted string (one document per line)
+pub fn render_langchain_docs(chunks: &[Chunk]) -> String {
+    let mut out = String::new();
+    for chunk in chunks {
+        let doc = json!({
+            "page_content": chunk.content,
+            "metadata": {
+                "source": chunk.path,
+                "start_line": chunk.start_line,
+                "end_line": chunk.end_line,
+                "language": chunk.language,
+                "priority": (chunk.priority * 1000.0).round() / 1000.0,
+                "tags": chunk.tags,
+            },
+        });
+        if let Ok(line) = serde_json::to_string(&doc) {
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_langchain_docs;
+    use crate::domain::Chunk;
+    use std::collections::BTreeSet;
+
+    fn mk_chunk(id: &str, content: &str) -> Chunk {
+        Chunk {
+            id: id.to_string(),
+            path: "src/lib.rs".to_string(),
+            language: "rust".to_string(),
+            start_line: 3,
+            end_line: 7,
+            content: content.to_string(),
+            priority: 0.8765,
+            tags: BTreeSet::from(["def:run".to_string()]),
+            token_estimate: 4,
+            file_id: "file1".to_string(),
+            chunk_index: 0,
+            chunks_in_file: 1,
+            byte_start: None,
+            byte_end: None,
+            content_sha256: String::new(),
+            file_sha256: String::new(),
+        }
+    }
+
+    #[test]
+    fn langchain_docs_have_expected_schema() {
+        let chunks = vec![mk_chunk("a", "fn run() {}\n"), mk_chunk("b", "struct \"Quoted\";\n")];
+
+        let out = render_langchain_docs(&chunks);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+
+        for (line, chunk) in lines.iter().zip(&chunks) {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(value["page_content"], chunk.content.as_str());
+            let metadata = &value["metadata"];
+            assert_eq!(metadata["source"], "src/lib.rs");
+            assert_eq!(metadata["start_line"], 3);
+            assert_eq!(metadata["end_line"], 7);
+            assert_eq!(metadata["language"], "rust");
+            assert_eq!(metadata["priority"], 0.877);
+            assert_eq!(metadata["tags"], serde_json::json!(["def:run"]));
+        }
+    }
+
+    #[test]
+    fn langchain_docs_empty_input_is_empty() {
+        assert_eq!(render_langchain_docs(&[]), "");
+    }
+}
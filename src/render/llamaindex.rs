@@ -0,0 +1,134 @@
+//! LlamaIndex document rendering
+//!
+//! Serializes chunks as LlamaIndex `TextNode` objects (`id_`, `text`,
+//! `metadata`, `relationships`), one JSON object per line.
+
+use crate::domain::Chunk;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+/// Renders chunks as LlamaIndex `TextNode` JSON Lines.
+///
+/// `relationships.SOURCE` points at the file path; `PREVIOUS` / `NEXT` link
+/// to neighbouring chunks of the same file, ordered by start line.
+///
+/// # Arguments
+/// * `chunks` - Chunks to render
+///
+/// # Returns
+/// JSON Lines formatted string (one node per line)
+pub fn render_llamaindex_docs(chunks: &[Chunk]) -> String {
+    let neighbours = file_neighbours(chunks);
+
+    let mut out = String::new();
+    for chunk in chunks {
+        let mut relationships = Map::new();
+        relationships.insert("SOURCE".to_string(), json!({ "node_id": chunk.path }));
+        if let Some((prev, next)) = neighbours.get(chunk.id.as_str()) {
+            if let Some(prev) = prev {
+                relationships.insert("PREVIOUS".to_string(), json!({ "node_id": prev }));
+            }
+            if let Some(next) = next {
+                relationships.insert("NEXT".to_string(), json!({ "node_id": next }));
+            }
+        }
+
+        let node = json!({
+            "id_": chunk.id,
+            "text": chunk.content,
+            "metadata": {
+                "file_path": chunk.path,
+                "start_line": chunk.start_line,
+                "end_line": chunk.end_line,
+                "language": chunk.language,
+                "priority": (chunk.priority * 1000.0).round() / 1000.0,
+                "token_estimate": chunk.token_estimate,
+                "keywords": chunk.tags,
+            },
+            "relationships": Value::Object(relationships),
+        });
+        if let Ok(line) = serde_json::to_string(&node) {
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Map each chunk id to its previous/next chunk ids within the same file.
+fn file_neighbours(chunks: &[Chunk]) -> HashMap<&str, (Option<&str>, Option<&str>)> {
+    let mut by_file: HashMap<&str, Vec<&Chunk>> = HashMap::new();
+    for chunk in chunks {
+        by_file.entry(chunk.path.as_str()).or_default().push(chunk);
+    }
+
+    let mut neighbours = HashMap::new();
+    for file_chunks in by_file.values_mut() {
+        file_chunks.sort_by(|a, b| a.start_line.cmp(&b.start_line).then_with(|| a.id.cmp(&b.id)));
+        for (i, chunk) in file_chunks.iter().enumerate() {
+            let prev = i.checked_sub(1).map(|p| file_chunks[p].id.as_str());
+            let next = file_chunks.get(i + 1).map(|n| n.id.as_str());
+            neighbours.insert(chunk.id.as_str(), (prev, next));
+        }
+    }
+    neighbours
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_llamaindex_docs;
+    use crate::domain::Chunk;
+    use std::collections::BTreeSet;
+
+    fn mk_chunk(id: &str, path: &str, start: usize) -> Chunk {
+        Chunk {
+            id: id.to_string(),
+            path: path.to_string(),
+            language: "rust".to_string(),
+            start_line: start,
+            end_line: start + 9,
+            content: format!("// chunk {id}\n"),
+            priority: 0.5,
+            tags: BTreeSet::from(["def:run".to_string(), "entrypoint".to_string()]),
+            token_estimate: 3,
+            file_id: "f".to_string(),
+            chunk_index: 0,
+            chunks_in_file: 2,
+            byte_start: None,
+            byte_end: None,
+            content_sha256: String::new(),
+            file_sha256: String::new(),
+        }
+    }
+
+    #[test]
+    fn llamaindex_docs_round_trip_and_link_neighbours() {
+        // Chunks arrive in priority order, not line order.
+        let chunks = vec![
+            mk_chunk("b", "src/lib.rs", 11),
+            mk_chunk("a", "src/lib.rs", 1),
+            mk_chunk("c", "src/main.rs", 1),
+        ];
+
+        let out = render_llamaindex_docs(&chunks);
+        let nodes: Vec<serde_json::Value> =
+            out.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(nodes.len(), 3);
+
+        let b = &nodes[0];
+        assert_eq!(b["id_"], "b");
+        assert_eq!(b["text"], "// chunk b\n");
+        assert_eq!(b["metadata"]["keywords"], serde_json::json!(["def:run", "entrypoint"]));
+        assert_eq!(b["relationships"]["SOURCE"]["node_id"], "src/lib.rs");
+        assert_eq!(b["relationships"]["PREVIOUS"]["node_id"], "a");
+        assert!(b["relationships"].get("NEXT").is_none());
+
+        let a = &nodes[1];
+        assert_eq!(a["relationships"]["NEXT"]["node_id"], "b");
+        assert!(a["relationships"].get("PREVIOUS").is_none());
+
+        let c = &nodes[2];
+        assert!(c["relationships"].get("PREVIOUS").is_none());
+        assert!(c["relationships"].get("NEXT").is_none());
+    }
+}
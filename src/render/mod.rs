@@ -1,9 +1,26 @@
-//! Output rendering (Markdown, JSONL, reports)
+//! Output rendering (Markdown, JSONL, HTML, templates, reports)
 
 pub mod context_pack;
+pub mod editor;
+pub mod github_annotations;
+pub mod html;
 pub mod jsonl;
+pub mod langchain;
+pub mod llamaindex;
+pub mod openai;
+pub mod reader;
 pub mod report;
+pub mod template;
 
-pub use context_pack::{render_context_pack, ContextPackCtx};
+pub use context_pack::{merge_context_packs, render_context_pack, ContextPackCtx};
+pub use editor::render_editor_snippets;
+pub use html::render_html;
 pub use jsonl::render_jsonl;
-pub use report::{write_report, ReportOptions};
+pub use langchain::render_langchain_docs;
+pub use llamaindex::render_llamaindex_docs;
+pub use openai::render_openai_batch;
+pub use reader::{
+    parse_context_pack, ContextPackChunk, ContextPackDocument, ContextPackFile, ContextPackHeader,
+};
+pub use report::{write_manifest, write_report, ReportOptions};
+pub use template::{render_template, TemplateContext};
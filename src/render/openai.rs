@@ -0,0 +1,102 @@
+//! OpenAI Batch API rendering
+//!
+//! Wraps each chunk in a `/v1/chat/completions` request object so the output
+//! file can be uploaded directly to the Batch API.
+
+use crate::domain::Chunk;
+use serde_json::json;
+
+/// Default model for batch requests when `--model` is not given.
+pub const DEFAULT_BATCH_MODEL: &str = "gpt-4o-mini";
+
+/// Default system prompt for batch requests when `--system-prompt` is not given.
+pub const DEFAULT_BATCH_SYSTEM_PROMPT: &str =
+    "You are reviewing a chunk of a source repository. Summarize what it does.";
+
+/// Renders chunks as OpenAI Batch API requests (NDJSON).
+///
+/// Each line is
+/// `{"custom_id", "method": "POST", "url": "/v1/chat/completions", "body": {...}}`
+/// with the chunk id as `custom_id` and the chunk content as the user message.
+///
+/// # Arguments
+/// * `chunks` - Chunks to render
+/// * `system_prompt` - System message sent with every request
+/// * `model` - Model name for every request
+///
+/// # Returns
+/// NDJSON formatted string (one request per line)
+pub fn render_openai_batch(chunks: &[Chunk], system_prompt: &str, model: &str) -> String {
+    let mut out = String::new();
+    for chunk in chunks {
+        let request = json!({
+            "custom_id": chunk.id,
+            "method": "POST",
+            "url": "/v1/chat/completions",
+            "body": {
+                "model": model,
+                "messages": [
+                    { "role": "system", "content": system_prompt },
+                    { "role": "user", "content": chunk.content },
+                ],
+            },
+        });
+        if let Ok(line) = serde_json::to_string(&request) {
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_openai_batch;
+    use crate::domain::Chunk;
+    use std::collections::BTreeSet;
+
+    fn mk_chunk(id: &str, content: &str) -> Chunk {
+        Chunk {
+            id: id.to_string(),
+            path: "src/lib.rs".to_string(),
+            language: "rust".to_string(),
+            start_line: 1,
+            end_line: 1,
+            content: content.to_string(),
+            priority: 0.5,
+            tags: BTreeSet::new(),
+            token_estimate: 3,
+            file_id: "f".to_string(),
+            chunk_index: 0,
+            chunks_in_file: 1,
+            byte_start: None,
+            byte_end: None,
+            content_sha256: String::new(),
+            file_sha256: String::new(),
+        }
+    }
+
+    #[test]
+    fn openai_batch_is_ndjson_with_request_structure() {
+        let chunks = vec![mk_chunk("c1", "fn a() {}\n"), mk_chunk("c2", "fn b() {}\n")];
+
+        let out = render_openai_batch(&chunks, "Summarize.", "gpt-4o");
+
+        assert!(out.ends_with('\n'));
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+        for (line, chunk) in lines.iter().zip(&chunks) {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(value["custom_id"], chunk.id.as_str());
+            assert_eq!(value["method"], "POST");
+            assert_eq!(value["url"], "/v1/chat/completions");
+            assert_eq!(value["body"]["model"], "gpt-4o");
+            let messages = value["body"]["messages"].as_array().unwrap();
+            assert_eq!(messages.len(), 2);
+            assert_eq!(messages[0]["role"], "system");
+            assert_eq!(messages[0]["content"], "Summarize.");
+            assert_eq!(messages[1]["role"], "user");
+            assert_eq!(messages[1]["content"], chunk.content.as_str());
+        }
+    }
+}
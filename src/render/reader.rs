@@ -0,0 +1,320 @@
+//! Context pack Markdown reader
+//!
+//! Parses a context pack written by [`render_context_pack`] back into its
+//! header, directory tree, and per-file chunks, so two packs can be compared
+//! file by file instead of as raw Markdown.
+//!
+//! [`render_context_pack`]: crate::render::render_context_pack
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+
+/// A parsed context pack.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContextPackDocument {
+    /// Title and summary lines.
+    pub header: ContextPackHeader,
+    /// Directory tree exactly as rendered, without the code fence.
+    pub file_tree: String,
+    /// File sections in the order they appear in the pack.
+    pub files: Vec<ContextPackFile>,
+}
+
+/// Summary lines at the top of a context pack.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContextPackHeader {
+    /// Repository name from the title.
+    pub repo_name: String,
+    /// `YYYY-MM-DD HH:MM:SS UTC`, absent for `--no-timestamp` packs.
+    pub generated_at: Option<String>,
+    /// Number of files included in the pack.
+    pub files: usize,
+    /// Number of chunks included in the pack.
+    pub chunks: usize,
+    /// Total size of the included files.
+    pub size_bytes: u64,
+}
+
+/// One `### \`path\`` section of the File Contents part.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContextPackFile {
+    /// Path relative to the repository root.
+    pub path: String,
+    /// Language from the file metadata line.
+    pub language: String,
+    /// File priority, rounded to a whole percent by the renderer.
+    pub priority: f64,
+    /// Chunks of the file in the order they appear.
+    pub chunks: Vec<ContextPackChunk>,
+}
+
+/// One `**Lines a-b:**` block of a file section.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContextPackChunk {
+    /// First line of the chunk (1-based).
+    pub start_line: usize,
+    /// Last line of the chunk (inclusive).
+    pub end_line: usize,
+    /// Code fence language.
+    pub language: String,
+    /// Annotations rendered next to the line range (`reason:` / `stitch:` tags).
+    pub notes: Vec<String>,
+    /// Chunk content; trailing whitespace is trimmed by the renderer.
+    pub content: String,
+}
+
+const TITLE_PREFIX: &str = "# Repository Context Pack: ";
+const TREE_HEADING: &str = "## 📁 Directory Structure";
+const CONTENTS_HEADING: &str = "## 📄 File Contents";
+
+/// Parses a context pack Markdown document.
+///
+/// # Errors
+/// Returns an error if `content` does not start with the context pack title
+/// or a file section is malformed
+pub fn parse_context_pack(content: &str) -> Result<ContextPackDocument> {
+    let lines: Vec<&str> = content.lines().collect();
+    let header = parse_header(&lines)?;
+    let tree_start = lines.iter().position(|line| *line == TREE_HEADING);
+    let file_tree = tree_start.map(|idx| parse_file_tree(&lines[idx + 1..])).unwrap_or_default();
+    // Search after the tree so a README excerpt cannot shadow the heading.
+    let contents_from = tree_start.unwrap_or(0);
+    let files = match lines[contents_from..].iter().position(|line| *line == CONTENTS_HEADING) {
+        Some(idx) => parse_files(&lines[contents_from + idx + 1..])?,
+        None => Vec::new(),
+    };
+    Ok(ContextPackDocument { header, file_tree, files })
+}
+
+fn parse_header(lines: &[&str]) -> Result<ContextPackHeader> {
+    let Some(repo_name) = lines.first().and_then(|line| line.strip_prefix(TITLE_PREFIX)) else {
+        bail!("Not a repo-context context pack: missing '{}' title", TITLE_PREFIX.trim_end());
+    };
+    let mut header = ContextPackHeader {
+        repo_name: repo_name.to_string(),
+        generated_at: None,
+        files: 0,
+        chunks: 0,
+        size_bytes: 0,
+    };
+    let summary = lines.iter().skip(1).skip_while(|line| line.is_empty());
+    for line in summary.map_while(|line| line.strip_prefix("> ")) {
+        if let Some(generated) = line.strip_prefix("Generated by repo-context") {
+            header.generated_at = generated.strip_prefix(" on ").map(str::to_string);
+            continue;
+        }
+        for field in line.split(" | ") {
+            let Some((key, value)) = field.split_once(": ") else {
+                continue;
+            };
+            let number = value.trim_end_matches(" bytes").replace(',', "");
+            match key {
+                "Files" => header.files = number.parse().context("Invalid file count")?,
+                "Chunks" => header.chunks = number.parse().context("Invalid chunk count")?,
+                "Size" => header.size_bytes = number.parse().context("Invalid size")?,
+                _ => {}
+            }
+        }
+    }
+    Ok(header)
+}
+
+/// Lines of the first code fence after the Directory Structure heading.
+fn parse_file_tree(lines: &[&str]) -> String {
+    let fenced = lines.iter().skip_while(|line| !line.starts_with("```")).skip(1);
+    fenced.take_while(|line| **line != "```").copied().collect::<Vec<_>>().join("\n")
+}
+
+fn parse_files(lines: &[&str]) -> Result<Vec<ContextPackFile>> {
+    let mut files: Vec<ContextPackFile> = Vec::new();
+    let mut idx = 0;
+    while idx < lines.len() {
+        let line = lines[idx];
+        idx += 1;
+        if let Some(path) = line.strip_prefix("### `").and_then(|rest| rest.strip_suffix('`')) {
+            files.push(ContextPackFile {
+                path: path.to_string(),
+                language: String::new(),
+                priority: 0.0,
+                chunks: Vec::new(),
+            });
+        } else if let Some(meta) =
+            line.strip_prefix("*Priority: ").and_then(|m| m.strip_suffix('*'))
+        {
+            let file = files.last_mut().context("File metadata before any file heading")?;
+            for (n, field) in meta.split(" | ").enumerate() {
+                if n == 0 {
+                    let pct: f64 =
+                        field.trim_end_matches('%').parse().context("Invalid priority")?;
+                    file.priority = pct / 100.0;
+                } else if let Some(language) = field.strip_prefix("Language: ") {
+                    file.language = language.to_string();
+                }
+            }
+        } else if let Some(range) = line.strip_prefix("**Lines ") {
+            let file = files.last_mut().context("Chunk before any file heading")?;
+            let (chunk, consumed) = parse_chunk(range, &lines[idx..])
+                .with_context(|| format!("Malformed chunk in {}", file.path))?;
+            file.chunks.push(chunk);
+            idx += consumed;
+        }
+    }
+    Ok(files)
+}
+
+/// Parses one chunk from the text after `**Lines ` and the lines after it.
+///
+/// Chunk content is not escaped, so a fence line only closes the chunk when
+/// it is followed by a blank line and then the next chunk, the next file
+/// section, or the end of the document. Returns the chunk and the number of
+/// lines consumed.
+fn parse_chunk(range: &str, lines: &[&str]) -> Result<(ContextPackChunk, usize)> {
+    let (range, notes) = range.split_once(":**").context("Missing ':**' after line range")?;
+    let (start, end) = range.split_once('-').context("Missing '-' in line range")?;
+    let notes = notes
+        .trim()
+        .strip_prefix("`[")
+        .and_then(|n| n.strip_suffix("]`"))
+        .map(|n| n.split(" | ").map(str::to_string).collect())
+        .unwrap_or_default();
+
+    let fence = lines.iter().position(|line| line.starts_with("```")).context("Missing fence")?;
+    let language = lines[fence].trim_start_matches('`').to_string();
+    let body = &lines[fence + 1..];
+    let close = (0..body.len())
+        .find(|&i| {
+            body[i] == "```"
+                && body.get(i + 1).is_none_or(|next| next.is_empty())
+                && body
+                    .get(i + 2)
+                    .is_none_or(|next| next.starts_with("**Lines ") || next.starts_with("<a id=\""))
+        })
+        .context("Unterminated code fence")?;
+
+    let chunk = ContextPackChunk {
+        start_line: start.trim().parse().context("Invalid start line")?,
+        end_line: end.trim().parse().context("Invalid end line")?,
+        language,
+        notes,
+        content: body[..close].join("\n"),
+    };
+    Ok((chunk, fence + 1 + close + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_context_pack;
+    use crate::domain::{Chunk, FileInfo, ScanStats};
+    use crate::render::render_context_pack;
+    use std::collections::{BTreeSet, HashMap};
+    use std::path::Path;
+
+    fn chunk(path: &str, start: usize, content: &str) -> Chunk {
+        Chunk {
+            id: format!("{path}:{start}"),
+            path: path.to_string(),
+            language: "rust".to_string(),
+            start_line: start,
+            end_line: start + content.lines().count() - 1,
+            content: content.to_string(),
+            priority: 0.8,
+            tags: BTreeSet::new(),
+            token_estimate: 10,
+            file_id: "f".to_string(),
+            chunk_index: 0,
+            chunks_in_file: 1,
+            byte_start: None,
+            byte_end: None,
+            content_sha256: String::new(),
+            file_sha256: String::new(),
+        }
+    }
+
+    fn file(path: &str, priority: f64) -> FileInfo {
+        FileInfo {
+            path: Path::new("/tmp/demo").join(path),
+            relative_path: path.to_string(),
+            size_bytes: 100,
+            extension: ".rs".to_string(),
+            language: "rust".to_string(),
+            id: path.to_string(),
+            priority,
+            token_estimate: 10,
+            tags: BTreeSet::new(),
+            is_readme: false,
+            is_config: false,
+            is_doc: false,
+            is_env_file: false,
+            is_symlink: false,
+            symlink_target: None,
+            complexity_estimate: 0,
+            priority_explanation: None,
+        }
+    }
+
+    fn render(files: &[FileInfo], chunks: &[Chunk], include_timestamp: bool) -> String {
+        let stats = ScanStats {
+            files_included: files.len(),
+            total_bytes_included: 1234,
+            ..ScanStats::default()
+        };
+        render_context_pack(
+            Path::new("/tmp/demo"),
+            files,
+            chunks,
+            &stats,
+            "demo/\n├── src/\n│   ├── lib.rs ⭐\n│   └── main.rs",
+            &HashMap::new(),
+            &[],
+            false,
+            include_timestamp,
+        )
+    }
+
+    #[test]
+    fn round_trips_rendered_header_tree_and_chunks() {
+        let files = [file("src/lib.rs", 0.9), file("src/main.rs", 0.75)];
+        let mut tagged = chunk("src/lib.rs", 10, "fn b() {}\n");
+        tagged.tags.insert("reason:entrypoint".to_string());
+        let chunks = [
+            chunk("src/lib.rs", 1, "pub fn a() {\n    b();\n}\n"),
+            tagged,
+            chunk("src/main.rs", 1, "fn main() {\n    demo::a();\n}\n\n"),
+        ];
+
+        let doc = parse_context_pack(&render(&files, &chunks, true)).expect("parse");
+
+        assert_eq!(doc.header.repo_name, "demo");
+        assert!(doc.header.generated_at.as_deref().is_some_and(|ts| ts.ends_with(" UTC")));
+        assert_eq!((doc.header.files, doc.header.chunks, doc.header.size_bytes), (2, 3, 1234));
+        assert_eq!(doc.file_tree, "demo/\n├── src/\n│   ├── lib.rs ⭐\n│   └── main.rs");
+
+        let paths: Vec<&str> = doc.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, ["src/lib.rs", "src/main.rs"]);
+        let lib = &doc.files[0];
+        assert_eq!((lib.language.as_str(), lib.priority), ("rust", 0.9));
+        assert_eq!(lib.chunks.len(), 2);
+        assert_eq!((lib.chunks[0].start_line, lib.chunks[0].end_line), (1, 3));
+        assert_eq!(lib.chunks[0].content, chunks[0].content.trim_end());
+        assert_eq!(lib.chunks[1].notes, ["entrypoint"]);
+        assert_eq!(lib.chunks[1].language, "rust");
+        assert_eq!(doc.files[1].chunks[0].content, chunks[2].content.trim_end());
+    }
+
+    #[test]
+    fn fences_inside_chunk_content_do_not_end_the_chunk() {
+        let content = "/// ```\n/// demo::a();\n/// ```\n```\n\nfn a() {}\n";
+        let files = [file("src/lib.rs", 0.5)];
+        let doc = parse_context_pack(&render(&files, &[chunk("src/lib.rs", 1, content)], false))
+            .expect("parse");
+
+        assert_eq!(doc.header.generated_at, None);
+        assert_eq!(doc.files[0].chunks.len(), 1);
+        assert_eq!(doc.files[0].chunks[0].content, content.trim_end());
+    }
+
+    #[test]
+    fn rejects_documents_without_the_pack_title() {
+        assert!(parse_context_pack("# Some other document\n").is_err());
+    }
+}
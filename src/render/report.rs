@@ -1,10 +1,11 @@
 //! Report JSON generation.
 
 use crate::domain::{FileDisposition, FileInfo, ScanStats, REPORT_SCHEMA_VERSION};
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::Utc;
 use serde_json::{json, Map, Value};
-use std::path::Path;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
 
 /// Options for report generation.
 #[derive(Debug, Default, Clone)]
@@ -15,6 +16,8 @@ pub struct ReportOptions<'a> {
     pub provenance: Option<&'a Value>,
     /// Optional focus metadata (focused export scope info)
     pub focus: Option<&'a Value>,
+    /// Original and compressed sizes of gzip-compressed outputs
+    pub compressed_outputs: &'a [Value],
 }
 
 /// Writes a JSON report to the specified path.
@@ -52,12 +55,20 @@ pub fn write_report(
     let file_manifest = sorted_files
         .iter()
         .map(|f| {
-            json!({
+            let mut entry = json!({
                 "id": f.id,
                 "path": f.relative_path,
                 "priority": round_priority(f.priority),
                 "tokens": f.token_estimate,
-            })
+            });
+            if f.is_symlink {
+                entry["is_symlink"] = Value::Bool(true);
+                entry["symlink_target"] = json!(f.symlink_target);
+            }
+            if let Some(explanation) = &f.priority_explanation {
+                entry["priority_explanation"] = json!(explanation);
+            }
+            entry
         })
         .collect::<Vec<_>>();
 
@@ -78,6 +89,12 @@ pub fn write_report(
         report.insert("focus".to_string(), focus.clone());
     }
     report.insert("output_files".to_string(), serde_json::to_value(sorted_output_files)?);
+    if !options.compressed_outputs.is_empty() {
+        report.insert(
+            "compressed_outputs".to_string(),
+            Value::Array(options.compressed_outputs.to_vec()),
+        );
+    }
     if !file_manifest.is_empty() {
         report.insert("files".to_string(), serde_json::to_value(file_manifest)?);
     }
@@ -92,6 +109,45 @@ pub fn write_report(
     Ok(())
 }
 
+/// Writes the run manifest: every artifact of the run with its size and
+/// SHA-256, plus the fingerprint of the inputs that produced them.
+///
+/// Files are listed sorted by path, so a re-run with the same inputs and
+/// outputs writes a byte-identical manifest.
+pub fn write_manifest(
+    manifest_path: &Path,
+    output_files: &[PathBuf],
+    provenance_fingerprint: &str,
+) -> Result<()> {
+    let mut sorted_files = output_files.to_vec();
+    sorted_files.sort();
+    sorted_files.dedup();
+
+    let files = sorted_files
+        .iter()
+        .map(|path| {
+            let bytes = std::fs::read(path)
+                .with_context(|| format!("Failed to read {} for manifest", path.display()))?;
+            Ok(json!({
+                "path": path.display().to_string(),
+                "bytes": bytes.len(),
+                "sha256": format!("{:x}", Sha256::digest(&bytes)),
+            }))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let manifest = json!({
+        "schema_version": REPORT_SCHEMA_VERSION,
+        "provenance_fingerprint": provenance_fingerprint,
+        "files": files,
+    });
+    if let Some(parent) = manifest_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+    Ok(())
+}
+
 /// Rounds priority to 3 decimal places for cleaner output.
 fn round_priority(priority: f64) -> f64 {
     (priority * 1000.0).round() / 1000.0
@@ -99,7 +155,7 @@ fn round_priority(priority: f64) -> f64 {
 
 #[cfg(test)]
 mod tests {
-    use super::{write_report, ReportOptions};
+    use super::{write_manifest, write_report, ReportOptions};
     use crate::domain::{FileInfo, ScanStats};
     use serde_json::json;
     use std::collections::BTreeSet;
@@ -153,6 +209,11 @@ mod tests {
             is_readme: false,
             is_config: false,
             is_doc: false,
+            is_env_file: false,
+            is_symlink: false,
+            symlink_target: None,
+            complexity_estimate: 0,
+            priority_explanation: None,
         };
 
         write_report(
@@ -162,7 +223,12 @@ mod tests {
             &["out/chunks.jsonl".to_string()],
             &json!({"mode":"rag"}),
             &[],
-            ReportOptions { include_timestamp: false, provenance: None, focus: None },
+            ReportOptions {
+                include_timestamp: false,
+                provenance: None,
+                focus: None,
+                compressed_outputs: &[],
+            },
         )
         .expect("write report");
 
@@ -171,4 +237,40 @@ mod tests {
         assert!(parsed.get("generated_at").is_none());
         assert_eq!(parsed["files"][0]["priority"], json!(0.812));
     }
+
+    #[test]
+    fn manifest_lists_every_artifact_with_its_checksum() {
+        use sha2::{Digest, Sha256};
+
+        let tmp = TempDir::new().expect("tmp");
+        let chunks = tmp.path().join("repo_chunks.jsonl");
+        let report = tmp.path().join("repo_report.json");
+        fs::write(&chunks, "{\"type\":\"header\"}\n").expect("write chunks");
+        fs::write(&report, "{}").expect("write report");
+        let manifest_path = tmp.path().join("repo_manifest.json");
+
+        write_manifest(&manifest_path, &[report.clone(), chunks.clone()], "abc123")
+            .expect("write manifest");
+
+        let content = fs::read_to_string(&manifest_path).expect("read manifest");
+        let parsed: serde_json::Value = serde_json::from_str(&content).expect("json");
+        assert_eq!(parsed["provenance_fingerprint"], json!("abc123"));
+        let files = parsed["files"].as_array().expect("files");
+        assert_eq!(files.len(), 2);
+        for (entry, path) in files.iter().zip([&chunks, &report]) {
+            let bytes = fs::read(path).expect("read artifact");
+            assert_eq!(entry["path"], json!(path.display().to_string()));
+            assert_eq!(entry["bytes"], json!(bytes.len()));
+            assert_eq!(entry["sha256"], json!(format!("{:x}", Sha256::digest(&bytes))));
+        }
+    }
+
+    #[test]
+    fn manifest_fails_for_missing_artifact() {
+        let tmp = TempDir::new().expect("tmp");
+        let missing = tmp.path().join("gone.md");
+        let err = write_manifest(&tmp.path().join("m.json"), &[missing], "abc")
+            .expect_err("missing artifact");
+        assert!(err.to_string().contains("gone.md"));
+    }
 }
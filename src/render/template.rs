@@ -0,0 +1,375 @@
+//! Custom template rendering (Tera)
+//!
+//! Lets users replace the built-in Markdown layout with their own Tera
+//! template. The bundled `context_pack.md.tera` follows the default context
+//! pack layout and is a starting point for customization.
+
+use super::context_pack::{
+    chunk_notes, dedupe_overlapping_chunks, escape_fence_lang, readme_excerpt, section_anchors,
+    ContextPackCtx,
+};
+use crate::domain::{Chunk, FileInfo, ScanStats};
+use crate::utils::format_with_commas;
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use tera::Tera;
+
+/// Built-in template following the default context pack layout.
+pub const DEFAULT_CONTEXT_PACK_TEMPLATE: &str =
+    include_str!("../../templates/context_pack.md.tera");
+
+/// Data exposed to context pack templates.
+///
+/// Mirrors the inputs of `render_context_pack`, plus a few pre-computed views
+/// (`file_sections`, `key_files`, ...) so templates don't need to re-implement
+/// grouping and ordering logic.
+#[derive(Debug, Serialize)]
+pub struct TemplateContext<'a> {
+    /// Repository directory name.
+    pub repo_name: String,
+    /// Repository root path.
+    pub root_path: String,
+    /// Generation timestamp (`None` with `--no-timestamp`).
+    pub generated_at: Option<String>,
+    /// Included files, in ranked order.
+    pub files: &'a [FileInfo],
+    /// Included chunks, in budget order.
+    pub chunks: &'a [Chunk],
+    /// Scan statistics.
+    pub stats: &'a ScanStats,
+    /// Rendered directory tree.
+    pub tree: &'a str,
+    /// Manifest information (project name, description, scripts).
+    pub manifest: &'a HashMap<String, JsonValue>,
+    /// Total included bytes, formatted with thousands separators.
+    pub total_bytes_included: String,
+    /// Top five detected languages by file count.
+    pub languages: Vec<LanguageCount>,
+    /// Entrypoint paths, sorted.
+    pub entrypoints: Vec<&'a str>,
+    /// README excerpt, if a README was included.
+    pub readme_excerpt: Option<String>,
+    /// Documentation / Configuration / Entrypoints groups (five files each).
+    pub key_files: Vec<KeyFileGroup<'a>>,
+    /// Chunks grouped per file, ordered by file priority then path.
+    pub file_sections: Vec<FileSection>,
+}
+
+/// Language name with its detected file count.
+#[derive(Debug, Serialize)]
+#[allow(missing_docs)]
+pub struct LanguageCount {
+    pub name: String,
+    pub count: usize,
+}
+
+/// A named group of key files.
+#[derive(Debug, Serialize)]
+#[allow(missing_docs)]
+pub struct KeyFileGroup<'a> {
+    pub category: &'static str,
+    pub files: Vec<KeyFile<'a>>,
+}
+
+/// A key file with its priority as a percentage.
+#[derive(Debug, Serialize)]
+#[allow(missing_docs)]
+pub struct KeyFile<'a> {
+    pub path: &'a str,
+    pub priority_pct: u64,
+}
+
+/// Key file category name and the predicate selecting its files.
+type KeyFileCategory = (&'static str, fn(&FileInfo) -> bool);
+
+/// All rendered chunks of one file.
+#[derive(Debug, Serialize)]
+#[allow(missing_docs)]
+pub struct FileSection {
+    pub path: String,
+    pub anchor: String,
+    pub language: String,
+    pub priority_pct: u64,
+    pub chunk_count: usize,
+    pub chunks: Vec<SectionChunk>,
+}
+
+/// A chunk prepared for rendering (overlap removed, trailing whitespace trimmed).
+#[derive(Debug, Serialize)]
+#[allow(missing_docs)]
+pub struct SectionChunk {
+    pub start_line: usize,
+    pub end_line: usize,
+    pub notes: Vec<String>,
+    pub fence_lang: String,
+    pub content: String,
+}
+
+impl<'a> TemplateContext<'a> {
+    /// Build the template context from the same inputs as the Markdown renderer.
+    pub fn from_context_pack(ctx: &ContextPackCtx<'a>) -> Self {
+        let files = ctx.files;
+        let chunks = ctx.chunks;
+
+        let mut lang_counts: Vec<_> = ctx.stats.languages_detected.iter().collect();
+        lang_counts.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        let languages = lang_counts
+            .into_iter()
+            .take(5)
+            .map(|(name, count)| LanguageCount { name: name.clone(), count: *count })
+            .collect();
+
+        let mut entrypoints: Vec<&str> = files
+            .iter()
+            .filter(|f| f.tags.contains("entrypoint"))
+            .map(|f| f.relative_path.as_str())
+            .collect();
+        entrypoints.sort();
+        entrypoints.truncate(10);
+
+        let categories: [KeyFileCategory; 3] = [
+            ("Documentation", |f| f.is_doc),
+            ("Configuration", |f| f.is_config),
+            ("Entrypoints", |f| f.tags.contains("entrypoint")),
+        ];
+        let key_files = categories
+            .iter()
+            .map(|&(category, predicate)| KeyFileGroup {
+                category,
+                files: files
+                    .iter()
+                    .filter(|f| predicate(f))
+                    .take(5)
+                    .map(|f| KeyFile {
+                        path: f.relative_path.as_str(),
+                        priority_pct: (f.priority * 100.0).round() as u64,
+                    })
+                    .collect(),
+            })
+            .filter(|group| !group.files.is_empty())
+            .collect();
+
+        Self {
+            repo_name: ctx
+                .root_path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("repo")
+                .to_string(),
+            root_path: ctx.root_path.display().to_string(),
+            generated_at: ctx
+                .include_timestamp
+                .then(|| Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string()),
+            files,
+            chunks,
+            stats: ctx.stats,
+            tree: ctx.tree,
+            manifest: ctx.manifest_info,
+            total_bytes_included: format_with_commas(ctx.stats.total_bytes_included),
+            languages,
+            entrypoints,
+            readme_excerpt: readme_excerpt(files, chunks),
+            key_files,
+            file_sections: build_file_sections(files, chunks),
+        }
+    }
+}
+
+fn build_file_sections(files: &[FileInfo], chunks: &[Chunk]) -> Vec<FileSection> {
+    let file_priorities: HashMap<&str, f64> =
+        files.iter().map(|f| (f.relative_path.as_str(), f.priority)).collect();
+    let mut chunks_by_file: HashMap<&str, Vec<&Chunk>> = HashMap::new();
+    for chunk in chunks {
+        chunks_by_file.entry(chunk.path.as_str()).or_default().push(chunk);
+    }
+
+    let mut sorted_paths: Vec<&str> = chunks_by_file.keys().copied().collect();
+    sorted_paths.sort_by(|a, b| {
+        let pa = file_priorities.get(a).copied().unwrap_or(0.0);
+        let pb = file_priorities.get(b).copied().unwrap_or(0.0);
+        pb.partial_cmp(&pa).unwrap_or(std::cmp::Ordering::Equal).then_with(|| a.cmp(b))
+    });
+
+    let anchors = section_anchors(&sorted_paths);
+    sorted_paths
+        .into_iter()
+        .zip(anchors)
+        .map(|(path, anchor)| {
+            let mut file_chunks = chunks_by_file.remove(path).unwrap_or_default();
+            file_chunks
+                .sort_by(|a, b| a.start_line.cmp(&b.start_line).then_with(|| a.id.cmp(&b.id)));
+            let language =
+                file_chunks.first().map(|c| c.language.clone()).unwrap_or_else(|| "text".into());
+            let priority = file_priorities.get(path).copied().unwrap_or(0.5);
+            let chunk_count = file_chunks.len();
+            let chunks = dedupe_overlapping_chunks(file_chunks)
+                .into_iter()
+                .map(|chunk| SectionChunk {
+                    start_line: chunk.start_line,
+                    end_line: chunk.end_line,
+                    notes: chunk_notes(&chunk),
+                    fence_lang: escape_fence_lang(&chunk.language),
+                    content: chunk.content.trim_end().to_string(),
+                })
+                .collect();
+            FileSection {
+                path: path.to_string(),
+                anchor,
+                language,
+                priority_pct: (priority * 100.0).round() as u64,
+                chunk_count,
+                chunks,
+            }
+        })
+        .collect()
+}
+
+/// Render a context pack with a user-supplied Tera template.
+///
+/// # Errors
+/// Returns an error if the template cannot be read, fails to parse, or
+/// references data missing from the context.
+pub fn render_template(template_path: &Path, context: &TemplateContext<'_>) -> Result<String> {
+    let source = fs::read_to_string(template_path)
+        .with_context(|| format!("Failed reading template: {}", template_path.display()))?;
+    let name = template_path.file_name().and_then(|n| n.to_str()).unwrap_or("template");
+    render_template_str(name, &source, context)
+        .with_context(|| format!("Failed rendering template: {}", template_path.display()))
+}
+
+/// Render a context pack with the bundled default template.
+///
+/// # Errors
+/// Returns an error if the template fails to render.
+pub fn render_default_template(context: &TemplateContext<'_>) -> Result<String> {
+    render_template_str("context_pack.md.tera", DEFAULT_CONTEXT_PACK_TEMPLATE, context)
+}
+
+fn render_template_str(name: &str, source: &str, context: &TemplateContext<'_>) -> Result<String> {
+    let mut tera = Tera::default();
+    tera.add_raw_template(name, source).context("Invalid template syntax")?;
+    let ctx = tera::Context::from_serialize(context).context("Failed building template context")?;
+    tera.render(name, &ctx).context("Template rendering failed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn mk_file(path: &str) -> FileInfo {
+        FileInfo {
+            path: PathBuf::from(path),
+            relative_path: path.to_string(),
+            size_bytes: 20,
+            extension: ".rs".to_string(),
+            language: "rust".to_string(),
+            id: "f1".to_string(),
+            priority: 0.9,
+            token_estimate: 5,
+            tags: BTreeSet::from(["entrypoint".to_string()]),
+            is_readme: false,
+            is_config: false,
+            is_doc: false,
+            is_env_file: false,
+            is_symlink: false,
+            symlink_target: None,
+            complexity_estimate: 0,
+            priority_explanation: None,
+        }
+    }
+
+    fn mk_chunk(path: &str) -> Chunk {
+        Chunk {
+            id: "c1".to_string(),
+            path: path.to_string(),
+            language: "rust".to_string(),
+            start_line: 1,
+            end_line: 1,
+            content: "fn main() {}\n".to_string(),
+            priority: 0.9,
+            tags: BTreeSet::new(),
+            token_estimate: 3,
+            file_id: "f1".to_string(),
+            chunk_index: 0,
+            chunks_in_file: 1,
+            byte_start: None,
+            byte_end: None,
+            content_sha256: String::new(),
+            file_sha256: String::new(),
+        }
+    }
+
+    fn render_with<F: FnOnce(&TemplateContext<'_>) -> Result<String>>(render: F) -> String {
+        let files = vec![mk_file("src/main.rs")];
+        let chunks = vec![mk_chunk("src/main.rs")];
+        let stats = ScanStats { files_included: 1, ..ScanStats::default() };
+        let manifest = HashMap::new();
+        let ctx = ContextPackCtx {
+            root_path: Path::new("/tmp/demo"),
+            files: &files,
+            chunks: &chunks,
+            stats: &stats,
+            tree: "demo/\n└── src/",
+            manifest_info: &manifest,
+            dispositions: &[],
+            full_inventory: false,
+            include_timestamp: false,
+        };
+        render(&TemplateContext::from_context_pack(&ctx)).expect("render")
+    }
+
+    #[test]
+    fn default_template_renders_key_sections() {
+        let out = render_with(render_default_template);
+
+        assert!(out.starts_with("# Repository Context Pack: demo\n"));
+        assert!(out.contains("> Generated by repo-context\n"));
+        assert!(out.contains("## 📋 Repository Overview"));
+        assert!(out.contains("## 📁 Directory Structure"));
+        assert!(out.contains("## 🔑 Key Files"));
+        assert!(out.contains("### `src/main.rs`"));
+        assert!(out.contains("- [`src/main.rs`](#file-src-main-rs)\n"));
+        assert!(out.contains("<a id=\"file-src-main-rs\"></a>\n\n### `src/main.rs`"));
+        assert!(out.contains("```rust\nfn main() {}\n```"));
+    }
+
+    #[test]
+    fn custom_template_file_is_used() {
+        let tmp = TempDir::new().expect("tmp");
+        let path = tmp.path().join("custom.md.tera");
+        fs::write(&path, "{{ repo_name }}:{% for s in file_sections %} {{ s.path }}{% endfor %}")
+            .expect("write");
+
+        let out = render_with(|ctx| render_template(&path, ctx));
+
+        assert_eq!(out, "demo: src/main.rs");
+    }
+
+    #[test]
+    fn missing_template_file_is_error() {
+        let files: Vec<FileInfo> = Vec::new();
+        let stats = ScanStats::default();
+        let manifest = HashMap::new();
+        let ctx = ContextPackCtx {
+            root_path: Path::new("/tmp/demo"),
+            files: &files,
+            chunks: &[],
+            stats: &stats,
+            tree: "",
+            manifest_info: &manifest,
+            dispositions: &[],
+            full_inventory: false,
+            include_timestamp: false,
+        };
+        let template_ctx = TemplateContext::from_context_pack(&ctx);
+        assert!(render_template(Path::new("/nonexistent/x.tera"), &template_ctx).is_err());
+    }
+}
@@ -1,15 +1,88 @@
 //! File scanner implementation with gitignore support
 
 use crate::domain::{FileDisposition, FileDispositionReason, FileInfo, ScanStats};
-use crate::utils::{is_binary_file, is_likely_minified, normalize_path};
+use crate::utils::{is_binary_file, is_likely_minified, normalize_path, sniff_content_type};
 use anyhow::Result;
 use globset::{Glob, GlobSet, GlobSetBuilder};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 const DEFAULT_SAMPLE_SIZE: usize = 8192;
+/// Bytes read from a file to sniff its language.
+const SNIFF_SAMPLE_SIZE: u64 = 512;
+
+/// Outcome of inspecting a file's bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ContentCheck {
+    Binary,
+    Minified,
+    Text,
+}
+
+/// Inspects `(path, skip_minified, max_line_length)`; swappable in tests.
+type ContentProbe = fn(&Path, bool, usize) -> ContentCheck;
+
+/// Directory, relative to the user cache directory, holding one scan cache
+/// per repository root. Keeping it outside the repository leaves the working
+/// tree untouched.
+const SCAN_CACHE_DIR: &str = "repo-context/scan";
+
+/// Contents of a scan cache file.
+#[derive(Debug, Serialize, Deserialize)]
+struct ScanCache {
+    /// [`FileScanner::config_hash`] of the scanner that wrote the cache.
+    config_hash: String,
+    /// Unix timestamp (seconds) of the scan.
+    scanned_at: u64,
+    /// Every file and directory the scan walked, keyed by path relative to
+    /// the root (`""` is the root itself).
+    stamps: BTreeMap<String, PathStamp>,
+    files: Vec<FileInfo>,
+    stats: ScanStats,
+    dispositions: Vec<FileDisposition>,
+}
+
+/// Size and modification time of a walked path. A directory's stamp changes
+/// when entries are added, removed, or renamed, a file's when it is edited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct PathStamp {
+    size: u64,
+    modified_ns: u64,
+}
+
+impl PathStamp {
+    fn of(metadata: &std::fs::Metadata) -> Self {
+        let modified = metadata.modified().ok().and_then(|t| t.duration_since(UNIX_EPOCH).ok());
+        Self {
+            size: metadata.len(),
+            modified_ns: modified.map(|d| d.as_nanos() as u64).unwrap_or(0),
+        }
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// The user's cache directory: `$XDG_CACHE_HOME`, else `%LOCALAPPDATA%` on
+/// Windows or `~/.cache` elsewhere.
+fn user_cache_dir() -> Option<PathBuf> {
+    let var = |name| std::env::var_os(name).filter(|v| !v.is_empty()).map(PathBuf::from);
+    var("XDG_CACHE_HOME").or_else(|| {
+        if cfg!(windows) {
+            var("LOCALAPPDATA")
+        } else {
+            var("HOME").map(|home| home.join(".cache"))
+        }
+    })
+}
 
 /// File scanner that discovers files in a repository while respecting gitignore rules.
 pub struct FileScanner {
@@ -21,6 +94,13 @@ pub struct FileScanner {
     follow_symlinks: bool,
     skip_minified: bool,
     max_line_length: usize,
+    file_scan_timeout_ms: Option<u64>,
+    max_depth: Option<usize>,
+    sniff_content: bool,
+    include_env_files: bool,
+    content_probe: ContentProbe,
+    scan_cache_dir: Option<PathBuf>,
+    stamps: BTreeMap<String, PathStamp>,
     stats: ScanStats,
     dispositions: Vec<FileDisposition>,
 }
@@ -37,6 +117,13 @@ impl FileScanner {
             follow_symlinks: config.follow_symlinks,
             skip_minified: config.skip_minified,
             max_line_length: 5000,
+            file_scan_timeout_ms: config.file_scan_timeout_ms,
+            max_depth: config.max_depth,
+            sniff_content: config.sniff_content,
+            include_env_files: config.include_env_files,
+            content_probe: probe_content,
+            scan_cache_dir: user_cache_dir().map(|dir| dir.join(SCAN_CACHE_DIR)),
+            stamps: BTreeMap::new(),
             stats: ScanStats::default(),
             dispositions: Vec::new(),
         }
@@ -59,6 +146,13 @@ impl FileScanner {
             follow_symlinks: false,
             skip_minified: true,
             max_line_length: 5000,
+            file_scan_timeout_ms: None,
+            max_depth: None,
+            sniff_content: false,
+            include_env_files: false,
+            content_probe: probe_content,
+            scan_cache_dir: user_cache_dir().map(|dir| dir.join(SCAN_CACHE_DIR)),
+            stamps: BTreeMap::new(),
             stats: ScanStats::default(),
             dispositions: Vec::new(),
         }
@@ -71,7 +165,8 @@ impl FileScanner {
         self
     }
 
-    /// Set glob patterns to exclude
+    /// Set glob patterns to exclude; a `!` prefix re-includes paths that an
+    /// exclude pattern would otherwise drop
     #[must_use]
     pub fn exclude_globs(mut self, globs: Vec<String>) -> Self {
         self.exclude_globs = globs;
@@ -106,24 +201,59 @@ impl FileScanner {
         self
     }
 
-    fn build_exclude_globset(&self) -> Result<GlobSet> {
-        let mut builder = GlobSetBuilder::new();
-        for pattern in &self.exclude_globs {
-            match Glob::new(pattern) {
-                Ok(glob) => {
-                    builder.add(glob);
-                }
-                Err(e) => {
-                    tracing::warn!("Invalid exclude glob pattern '{}': {}", pattern, e);
-                }
-            }
-        }
-        Ok(builder.build()?)
+    /// Set how long a single file's binary/minified check may run before the
+    /// file is skipped (`None` waits indefinitely)
+    #[must_use]
+    pub fn file_scan_timeout_ms(mut self, timeout_ms: Option<u64>) -> Self {
+        self.file_scan_timeout_ms = timeout_ms;
+        self
+    }
+
+    /// Set how many directory levels below the root to descend (1 = files in
+    /// the root only; `None` is unlimited)
+    #[must_use]
+    pub fn max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Set whether files rejected by extension are kept when their content
+    /// sniffs as an included language
+    #[must_use]
+    pub fn sniff_content(mut self, sniff: bool) -> Self {
+        self.sniff_content = sniff;
+        self
+    }
+
+    /// Set whether `.env` / `.env.*` files are scanned (off by default)
+    #[must_use]
+    pub fn include_env_files(mut self, include: bool) -> Self {
+        self.include_env_files = include;
+        self
+    }
+
+    /// Set the directory scan caches are read from and written to (defaults
+    /// to `repo-context/scan` under the user cache directory)
+    #[must_use]
+    pub fn scan_cache_dir(mut self, dir: PathBuf) -> Self {
+        self.scan_cache_dir = Some(dir);
+        self
+    }
+
+    /// Exclude patterns, skipping `!`-prefixed include overrides.
+    fn build_exclude_set(&self) -> Result<GlobSet> {
+        build_globset(self.exclude_globs.iter().filter(|p| !p.starts_with('!')).map(String::as_str))
+    }
+
+    /// `!`-prefixed patterns with the prefix stripped; a path matching one of
+    /// these is kept even when an exclude pattern matches it.
+    fn build_include_override_set(&self) -> Result<GlobSet> {
+        build_globset(self.exclude_globs.iter().filter_map(|p| p.strip_prefix('!')))
     }
 
     /// Check if a file extension should be included
     fn should_include_extension(&self, path: &Path) -> bool {
-        if is_special_repo_file(path) {
+        if is_special_repo_file(path) || (self.include_env_files && is_env_file(path)) {
             return true;
         }
         let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
@@ -149,33 +279,106 @@ impl FileScanner {
         self.include_extensions.contains(&ext_with_dot)
     }
 
+    /// Sniff the extension of a file rejected by [`Self::should_include_extension`],
+    /// returning it only when it is an included extension.
+    fn sniff_included_extension(&self, path: &Path, size: u64) -> Option<&'static str> {
+        if !self.sniff_content
+            || size > self.max_file_bytes
+            || (is_env_file(path) && !self.include_env_files)
+        {
+            return None;
+        }
+        let mut first_bytes = Vec::with_capacity(SNIFF_SAMPLE_SIZE as usize);
+        std::fs::File::open(path)
+            .ok()?
+            .take(SNIFF_SAMPLE_SIZE)
+            .read_to_end(&mut first_bytes)
+            .ok()?;
+        sniff_content_type(path, &first_bytes)
+            .filter(|ext| self.include_extensions.iter().any(|included| included == ext))
+    }
+
+    /// Whether [`Self::scan`] would consider `path` (absolute, under the root)
+    /// from its location, name, and ignore rules alone.
+    ///
+    /// Size, binary, and minified checks need the file's current contents, so
+    /// a path passing here may still be skipped by the scan. When sniffing is
+    /// on, a file rejected by extension is sniffed if it still exists.
+    pub fn would_include(&self, path: &Path) -> bool {
+        let Ok(rel) = path.strip_prefix(&self.root_path) else {
+            return false;
+        };
+        let mut dirs: Vec<&str> = rel.iter().filter_map(|part| part.to_str()).collect();
+        dirs.pop();
+        if dirs.iter().any(|dir| is_skipped_dir(dir)) {
+            return false;
+        }
+        if self.max_depth.is_some_and(|depth| dirs.len() >= depth) {
+            return false;
+        }
+        if self.is_gitignored(path) {
+            return false;
+        }
+
+        let rel_path = normalize_path(rel.to_str().unwrap_or(""));
+        let excluded = self.build_exclude_set().is_ok_and(|set| set.is_match(&rel_path));
+        let reincluded = self.build_include_override_set().is_ok_and(|set| set.is_match(&rel_path));
+        if excluded && !reincluded {
+            return false;
+        }
+
+        self.should_include_extension(path)
+            || (self.sniff_content
+                && (self.include_env_files || !is_env_file(path))
+                && std::fs::metadata(path)
+                    .map_or(true, |m| self.sniff_included_extension(path, m.len()).is_some()))
+    }
+
+    /// Whether a `.gitignore` between the root and `path` ignores it, or
+    /// `.git/info/exclude` does. Like the scan's walker, ignore files only
+    /// apply inside a git repository.
+    fn is_gitignored(&self, path: &Path) -> bool {
+        if !self.respect_gitignore
+            || !self.root_path.ancestors().any(|dir| dir.join(".git").exists())
+        {
+            return false;
+        }
+        let is_dir = path.is_dir();
+        // The deepest ignore file with a matching rule decides, as in git.
+        for dir in path.ancestors().skip(1).take_while(|dir| dir.starts_with(&self.root_path)) {
+            let (gitignore, _) = Gitignore::new(dir.join(".gitignore"));
+            let matched = gitignore.matched_path_or_any_parents(path, is_dir);
+            if !matched.is_none() {
+                return matched.is_ignore();
+            }
+        }
+        let mut exclude = GitignoreBuilder::new(&self.root_path);
+        exclude.add(self.root_path.join(".git/info/exclude"));
+        exclude
+            .build()
+            .is_ok_and(|gitignore| gitignore.matched_path_or_any_parents(path, is_dir).is_ignore())
+    }
+
     /// Scan the repository and return list of FileInfo objects.
     ///
     /// Files are returned in deterministic sorted order by relative path.
     pub fn scan(&mut self) -> Result<Vec<FileInfo>> {
         self.stats = ScanStats::default();
         self.dispositions.clear();
+        self.stamps.clear();
 
         // Pre-allocate with reasonable capacity to avoid reallocations during growth
-        let mut files: Vec<(PathBuf, String)> = Vec::with_capacity(1024);
-        let exclude_globset = self.build_exclude_globset()?;
+        let mut files: Vec<(PathBuf, String, bool, Option<&'static str>)> =
+            Vec::with_capacity(1024);
+        let exclude_set = self.build_exclude_set()?;
+        let include_override_set = self.build_include_override_set()?;
 
         // Directory filter function matching Python's _walk_files behavior
         let dir_filter = |entry: &ignore::DirEntry| -> bool {
             if let Some(file_type) = entry.file_type() {
                 if file_type.is_dir() {
                     if let Some(name) = entry.file_name().to_str() {
-                        // Skip known large directories unconditionally (Python lines 880-887)
-                        if matches!(
-                            name,
-                            "node_modules" | "__pycache__" | ".git" | ".venv" | "venv"
-                        ) {
-                            return false;
-                        }
-                        // Skip hidden directories except .github (Python lines 875-877)
-                        if name.starts_with('.') && name != ".github" {
-                            return false;
-                        }
+                        return !is_skipped_dir(name);
                     }
                 }
             }
@@ -189,6 +392,7 @@ impl FileScanner {
             .git_global(self.respect_gitignore)
             .git_exclude(self.respect_gitignore)
             .follow_links(self.follow_symlinks)
+            .max_depth(self.max_depth)
             .hidden(false) // Don't automatically skip hidden files
             .parents(true) // Read .gitignore files from parent directories
             .filter_entry(dir_filter);
@@ -204,8 +408,14 @@ impl FileScanner {
 
             let path = entry.path();
 
-            // Skip directories
+            // Skip directories, stamping them for the scan cache
             if path.is_dir() {
+                if let (Ok(rel), Ok(metadata)) =
+                    (path.strip_prefix(&self.root_path), path.metadata())
+                {
+                    let rel_path = normalize_path(rel.to_str().unwrap_or(""));
+                    self.stamps.insert(rel_path, PathStamp::of(&metadata));
+                }
                 continue;
             }
 
@@ -233,6 +443,7 @@ impl FileScanner {
             };
 
             let size = metadata.len();
+            self.stamps.insert(rel_path.clone(), PathStamp::of(&metadata));
             self.stats.total_bytes_scanned += size;
             self.stats.total_bytes_discovered += size;
 
@@ -245,15 +456,21 @@ impl FileScanner {
                 continue;
             }
 
-            // Check explicit exclude globs
-            if exclude_globset.is_match(&rel_path) {
+            // Check explicit exclude globs, then `!` overrides that re-include
+            if exclude_set.is_match(&rel_path) && !include_override_set.is_match(&rel_path) {
                 self.stats.files_skipped_glob += 1;
                 self.record_path(path, rel_path, FileDispositionReason::SkippedGlob, Some(size));
                 continue;
             }
 
-            // Check extension
-            if !self.should_include_extension(path) {
+            // Check extension, falling back to the content when sniffing is on
+            let included_by_extension = self.should_include_extension(path);
+            let sniffed_extension = if included_by_extension {
+                None
+            } else {
+                self.sniff_included_extension(path, size)
+            };
+            if !included_by_extension && sniffed_extension.is_none() {
                 self.stats.files_skipped_extension += 1;
                 self.record_path(
                     path,
@@ -270,26 +487,47 @@ impl FileScanner {
                 continue;
             }
 
-            // Check if binary
-            if is_binary_file(path, DEFAULT_SAMPLE_SIZE) {
-                self.stats.files_skipped_binary += 1;
-                self.record_path(path, rel_path, FileDispositionReason::SkippedBinary, Some(size));
-                continue;
-            }
-
-            // Check if minified
-            if self.skip_minified && is_likely_minified(path, self.max_line_length) {
-                self.stats.files_skipped_minified += 1;
-                self.record_path(
-                    path,
-                    rel_path,
-                    FileDispositionReason::SkippedMinified,
-                    Some(size),
-                );
-                continue;
+            // Check if binary or minified
+            match self.check_content(path) {
+                Some(ContentCheck::Text) => {}
+                Some(ContentCheck::Binary) => {
+                    self.stats.files_skipped_binary += 1;
+                    self.record_path(
+                        path,
+                        rel_path,
+                        FileDispositionReason::SkippedBinary,
+                        Some(size),
+                    );
+                    continue;
+                }
+                Some(ContentCheck::Minified) => {
+                    self.stats.files_skipped_minified += 1;
+                    self.record_path(
+                        path,
+                        rel_path,
+                        FileDispositionReason::SkippedMinified,
+                        Some(size),
+                    );
+                    continue;
+                }
+                None => {
+                    tracing::debug!(
+                        "Skipping {} after exceeding the {}ms scan timeout",
+                        rel_path,
+                        self.file_scan_timeout_ms.unwrap_or_default()
+                    );
+                    self.stats.files_skipped_timeout += 1;
+                    self.record_path(
+                        path,
+                        rel_path,
+                        FileDispositionReason::SkippedTimeout,
+                        Some(size),
+                    );
+                    continue;
+                }
             }
 
-            files.push((path.to_path_buf(), rel_path));
+            files.push((path.to_path_buf(), rel_path, entry.path_is_symlink(), sniffed_extension));
         }
 
         // Sort by relative path for deterministic ordering
@@ -298,7 +536,39 @@ impl FileScanner {
         // Convert to FileInfo objects
         // Pre-allocate result with known capacity to avoid reallocations
         let mut result = Vec::with_capacity(files.len());
-        for (path, rel_path) in files {
+        // Regular files claim their real paths first, so a symlink to a file
+        // that is already being scanned (or to another symlink's target) is
+        // skipped instead of exporting the same content twice.
+        let mut seen_targets: HashSet<PathBuf> = files
+            .iter()
+            .filter(|(_, _, is_symlink, _)| !is_symlink)
+            .filter_map(|(path, _, _, _)| path.canonicalize().ok())
+            .collect();
+        for (path, rel_path, is_symlink, sniffed_extension) in files {
+            let symlink_target = if is_symlink {
+                if let Ok(resolved) = path.canonicalize() {
+                    if !seen_targets.insert(resolved.clone()) {
+                        tracing::warn!(
+                            "Skipping symlink {} -> {}: target is already scanned",
+                            rel_path,
+                            resolved.display()
+                        );
+                        self.record_path(
+                            &path,
+                            rel_path,
+                            FileDispositionReason::SkippedDuplicateSymlink,
+                            None,
+                        );
+                        continue;
+                    }
+                }
+                std::fs::read_link(&path)
+                    .ok()
+                    .map(|target| normalize_path(&target.to_string_lossy()))
+            } else {
+                None
+            };
+
             let metadata = match path.metadata() {
                 Ok(m) => m,
                 Err(_) => continue,
@@ -310,7 +580,10 @@ impl FileScanner {
                 if !ext.is_empty() && !ext.starts_with('.') { format!(".{}", ext) } else { ext };
 
             let filename = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-            let language = crate::domain::get_language(&ext_with_dot, filename);
+            let language = crate::domain::get_language(
+                sniffed_extension.unwrap_or(ext_with_dot.as_str()),
+                filename,
+            );
 
             // Generate stable ID: SHA-256 of relative path, first 16 hex chars (matches Python)
             let id = {
@@ -334,6 +607,11 @@ impl FileScanner {
                 is_readme: false,      // Will be detected by ranker
                 is_config: false,      // Will be detected by ranker
                 is_doc: false,         // Will be detected by ranker
+                is_env_file: is_env_file(&path),
+                is_symlink,
+                symlink_target,
+                complexity_estimate: 0, // Will be estimated by ranker
+                priority_explanation: None,
             };
 
             self.stats.files_included += 1;
@@ -353,11 +631,31 @@ impl FileScanner {
             + self.stats.files_skipped_binary
             + self.stats.files_skipped_extension
             + self.stats.files_skipped_gitignore
-            + self.stats.files_skipped_glob;
+            + self.stats.files_skipped_glob
+            + self.stats.files_skipped_timeout
+            + self.stats.files_skipped_depth;
 
         Ok(result)
     }
 
+    /// Run the content probe on `path`, or `None` if it outlives the scan
+    /// timeout. With a timeout the probe runs on a worker thread that is left
+    /// to finish (or hang) on its own once abandoned.
+    fn check_content(&self, path: &Path) -> Option<ContentCheck> {
+        let (probe, skip_minified, max_line_length) =
+            (self.content_probe, self.skip_minified, self.max_line_length);
+        let Some(timeout_ms) = self.file_scan_timeout_ms else {
+            return Some(probe(path, skip_minified, max_line_length));
+        };
+
+        let (tx, rx) = mpsc::channel();
+        let path = path.to_path_buf();
+        std::thread::spawn(move || {
+            let _ = tx.send(probe(&path, skip_minified, max_line_length));
+        });
+        rx.recv_timeout(Duration::from_millis(timeout_ms)).ok()
+    }
+
     /// Get scanning statistics
     pub fn stats(&self) -> &ScanStats {
         &self.stats
@@ -368,6 +666,101 @@ impl FileScanner {
         &self.dispositions
     }
 
+    /// Path of the scan cache written by [`Self::write_scan_cache`]: a file
+    /// named by a hash of the root path, or `None` without a cache directory.
+    pub fn scan_cache_path(&self) -> Option<PathBuf> {
+        let root = normalize_path(&self.root_path.to_string_lossy());
+        let hash = format!("{:x}", Sha256::digest(root.as_bytes()));
+        Some(self.scan_cache_dir.as_ref()?.join(format!("{}.json", &hash[..16])))
+    }
+
+    /// Hash of every setting that affects scan results, so a cache written
+    /// with different settings (or by another version) is never reused.
+    fn config_hash(&self) -> String {
+        let mut extensions = self.include_extensions.clone();
+        extensions.sort();
+        let mut globs = self.exclude_globs.clone();
+        globs.sort();
+        let settings = serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "root": normalize_path(&self.root_path.to_string_lossy()),
+            "include_extensions": extensions,
+            "exclude_globs": globs,
+            "max_file_bytes": self.max_file_bytes,
+            "respect_gitignore": self.respect_gitignore,
+            "follow_symlinks": self.follow_symlinks,
+            "skip_minified": self.skip_minified,
+            "max_line_length": self.max_line_length,
+            "file_scan_timeout_ms": self.file_scan_timeout_ms,
+            "max_depth": self.max_depth,
+            "sniff_content": self.sniff_content,
+            "include_env_files": self.include_env_files,
+        });
+        format!("{:x}", Sha256::digest(settings.to_string().as_bytes()))
+    }
+
+    /// Load the results of a previous scan with the same settings, written
+    /// less than `ttl` ago, while every walked file and directory keeps the
+    /// size and modification time it had then.
+    ///
+    /// On a hit the cached stats and dispositions replace the scanner's own,
+    /// exactly as if [`Self::scan`] had run. A missing, unreadable, stale, or
+    /// mismatched cache returns `None`.
+    pub fn try_load_scan_cache(&mut self, ttl: Duration) -> Option<Vec<FileInfo>> {
+        let content = std::fs::read_to_string(self.scan_cache_path()?).ok()?;
+        let cache: ScanCache = match serde_json::from_str(&content) {
+            Ok(cache) => cache,
+            Err(e) => {
+                tracing::debug!("Ignoring unreadable scan cache: {}", e);
+                return None;
+            }
+        };
+        if cache.config_hash != self.config_hash() {
+            tracing::debug!("Scan cache settings changed; rescanning");
+            return None;
+        }
+        // A timestamp in the future (clock changes) counts as stale.
+        let age = unix_now().checked_sub(cache.scanned_at)?;
+        if age >= ttl.as_secs() {
+            tracing::debug!("Scan cache is {}s old; rescanning", age);
+            return None;
+        }
+        let changed = cache.stamps.iter().find(|(rel, stamp)| {
+            let metadata = std::fs::metadata(self.root_path.join(rel)).ok();
+            metadata.map(|m| PathStamp::of(&m)) != Some(**stamp)
+        });
+        if let Some((rel, _)) = changed {
+            tracing::debug!("Scan cache is stale ('{}' changed); rescanning", rel);
+            return None;
+        }
+        tracing::debug!("Using scan cache from {}s ago", age);
+        self.stats = cache.stats;
+        self.dispositions = cache.dispositions;
+        Some(cache.files)
+    }
+
+    /// Record `files` and the current stats and dispositions for
+    /// [`Self::try_load_scan_cache`], creating the cache directory if needed.
+    /// Does nothing without a cache directory.
+    pub fn write_scan_cache(&self, files: &[FileInfo]) -> Result<()> {
+        let Some(path) = self.scan_cache_path() else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let cache = ScanCache {
+            config_hash: self.config_hash(),
+            scanned_at: unix_now(),
+            stamps: self.stamps.clone(),
+            files: files.to_vec(),
+            stats: self.stats.clone(),
+            dispositions: self.dispositions.clone(),
+        };
+        std::fs::write(&path, serde_json::to_string(&cache)?)?;
+        Ok(())
+    }
+
     fn record_path(
         &mut self,
         path: &Path,
@@ -421,7 +814,17 @@ impl FileScanner {
             if let Some(size) = size {
                 self.stats.total_bytes_discovered += size;
             }
-            if is_excluded_noise_path(&rel_path) {
+            // The raw walk is unlimited; files past `max_depth` were never
+            // reached by the scan walk and must not count as gitignored.
+            if self.max_depth.is_some_and(|max| rel_path.split('/').count() > max) {
+                self.stats.files_skipped_depth += 1;
+                self.record_path(
+                    &path,
+                    rel_path.clone(),
+                    FileDispositionReason::SkippedDepth,
+                    size,
+                );
+            } else if is_excluded_noise_path(&rel_path) {
                 self.record_path(
                     &path,
                     rel_path.clone(),
@@ -449,6 +852,29 @@ impl FileScanner {
     }
 }
 
+/// Directories [`FileScanner::scan`] never descends into: known large
+/// directories (Python lines 880-887) and hidden ones except `.github`
+/// (Python lines 875-877).
+fn is_skipped_dir(name: &str) -> bool {
+    matches!(name, "node_modules" | "__pycache__" | ".git" | ".venv" | "venv")
+        || (name.starts_with('.') && name != ".github")
+}
+
+fn build_globset<'a>(patterns: impl Iterator<Item = &'a str>) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        match Glob::new(pattern) {
+            Ok(glob) => {
+                builder.add(glob);
+            }
+            Err(e) => {
+                tracing::warn!("Invalid exclude glob pattern '{}': {}", pattern, e);
+            }
+        }
+    }
+    Ok(builder.build()?)
+}
+
 fn collect_regular_files(root: &Path) -> Vec<(String, PathBuf)> {
     let dir_filter = |entry: &ignore::DirEntry| -> bool {
         if let Some(file_type) = entry.file_type() {
@@ -498,6 +924,16 @@ fn collect_regular_files(root: &Path) -> Vec<(String, PathBuf)> {
     files
 }
 
+fn probe_content(path: &Path, skip_minified: bool, max_line_length: usize) -> ContentCheck {
+    if is_binary_file(path, DEFAULT_SAMPLE_SIZE) {
+        ContentCheck::Binary
+    } else if skip_minified && is_likely_minified(path, max_line_length) {
+        ContentCheck::Minified
+    } else {
+        ContentCheck::Text
+    }
+}
+
 fn is_excluded_noise_path(rel_path: &str) -> bool {
     rel_path.split('/').any(|part| {
         matches!(
@@ -539,6 +975,21 @@ fn is_symlink_target_safe(path: &Path, repo_root: &Path) -> bool {
     }
 }
 
+/// Returns true for dotenv files (`.env`, `.env.local`, `.env.production`, ...).
+///
+/// Committed templates (`.env.example`, `.env.sample`, `.env.template`) hold
+/// placeholder values and are not treated as env files.
+pub fn is_env_file(path: &Path) -> bool {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("").to_lowercase();
+    let Some(suffix) = name.strip_prefix(".env") else {
+        return false;
+    };
+    match suffix.strip_prefix('.') {
+        None => suffix.is_empty(),
+        Some(variant) => !matches!(variant, "" | "example" | "sample" | "template"),
+    }
+}
+
 /// Returns true when a repository metadata/config file should bypass extension filtering.
 pub fn is_special_repo_file(path: &Path) -> bool {
     let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("").to_lowercase();
@@ -765,4 +1216,239 @@ mod tests {
             .iter()
             .any(|d| d.path == "image.bin" && d.reason == FileDispositionReason::SkippedExtension));
     }
+
+    #[test]
+    fn test_sniff_content_includes_extensionless_scripts() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir(root.join("scripts")).unwrap();
+        fs::write(root.join("scripts/manage"), "#!/usr/bin/env python3\nimport sys\n").unwrap();
+        fs::write(
+            root.join("generated"),
+            "//! Generated bindings\npub fn answer() -> u32 {\n    42\n}\n",
+        )
+        .unwrap();
+        fs::write(root.join("notes"), "remember the milk\n").unwrap();
+
+        let scan = |sniff: bool| {
+            let mut scanner = FileScanner::new(root.to_path_buf())
+                .include_extensions(vec![".py".to_string(), ".rs".to_string()])
+                .respect_gitignore(false)
+                .sniff_content(sniff);
+            let files = scanner.scan().unwrap();
+            let mut found: Vec<_> =
+                files.into_iter().map(|f| (f.relative_path, f.language)).collect();
+            found.sort();
+            found
+        };
+
+        assert!(scan(false).is_empty());
+        assert_eq!(
+            scan(true),
+            [
+                ("generated".to_string(), "rust".to_string()),
+                ("scripts/manage".to_string(), "python".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sniff_content_respects_include_extensions_and_size() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("tool"), "#!/usr/bin/env python\nprint('hi')\n").unwrap();
+        fs::write(root.join("big"), format!("fn big() {{}}\n{}", "// pad\n".repeat(100))).unwrap();
+
+        let mut scanner = FileScanner::new(root.to_path_buf())
+            .include_extensions(vec![".rs".to_string()])
+            .max_file_bytes(64)
+            .respect_gitignore(false)
+            .sniff_content(true);
+        assert!(scanner.scan().unwrap().is_empty());
+        assert_eq!(scanner.stats().files_skipped_extension, 2);
+    }
+
+    #[test]
+    fn test_env_files_are_detected_and_flagged() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for name in [".env", ".env.local", ".env.production", ".env.example", ".envrc"] {
+            fs::write(root.join(name), "API_KEY=secret\n").unwrap();
+        }
+
+        let scan = |include_env_files: bool| {
+            FileScanner::new(root.to_path_buf())
+                .include_extensions(vec![".rs".to_string()])
+                .respect_gitignore(false)
+                .sniff_content(true)
+                .include_env_files(include_env_files)
+                .scan()
+                .unwrap()
+        };
+        let default_files = scan(false);
+        assert!(default_files.iter().all(|f| !f.is_env_file), "env files need an opt-in");
+        assert!(default_files.iter().any(|f| f.relative_path == ".env.example"));
+
+        let files = scan(true);
+        let env_files: Vec<&str> =
+            files.iter().filter(|f| f.is_env_file).map(|f| f.relative_path.as_str()).collect();
+
+        assert_eq!(env_files, [".env", ".env.local", ".env.production"]);
+        assert!(files.iter().any(|f| f.relative_path == ".env.example" && !f.is_env_file));
+        assert!(!files.iter().any(|f| f.relative_path == ".envrc"));
+    }
+
+    #[test]
+    fn test_file_scan_timeout_skips_hanging_file() {
+        fn hang_on_slow_files(path: &Path, skip_minified: bool, max_line: usize) -> ContentCheck {
+            if path.file_name().is_some_and(|name| name == "slow.rs") {
+                std::thread::sleep(Duration::from_secs(10));
+            }
+            probe_content(path, skip_minified, max_line)
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("fast.rs"), "fn fast() {}").unwrap();
+        fs::write(root.join("slow.rs"), "fn slow() {}").unwrap();
+
+        let mut scanner = FileScanner::new(root.to_path_buf())
+            .respect_gitignore(false)
+            .file_scan_timeout_ms(Some(100));
+        scanner.content_probe = hang_on_slow_files;
+        let started = std::time::Instant::now();
+        let files = scanner.scan().unwrap();
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        let paths: Vec<&str> = files.iter().map(|f| f.relative_path.as_str()).collect();
+        assert_eq!(paths, ["fast.rs"]);
+        assert_eq!(scanner.stats().files_skipped_timeout, 1);
+        assert!(scanner
+            .dispositions()
+            .iter()
+            .any(|d| d.path == "slow.rs" && d.reason == FileDispositionReason::SkippedTimeout));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinks_are_tracked_and_duplicates_skipped() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join(".data")).unwrap();
+        fs::write(root.join("main.rs"), "fn main() {}").unwrap();
+        fs::write(root.join(".data/real.rs"), "fn real() {}").unwrap();
+        std::os::unix::fs::symlink("main.rs", root.join("alias.rs")).unwrap();
+        std::os::unix::fs::symlink(".data/real.rs", root.join("linked.rs")).unwrap();
+
+        let mut scanner =
+            FileScanner::new(root.to_path_buf()).respect_gitignore(false).follow_symlinks(true);
+        let files = scanner.scan().unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.relative_path.as_str()).collect();
+        assert_eq!(paths, ["linked.rs", "main.rs"]);
+
+        assert!(files[0].is_symlink);
+        assert_eq!(files[0].symlink_target.as_deref(), Some(".data/real.rs"));
+        assert!(!files[1].is_symlink);
+        assert_eq!(files[1].symlink_target, None);
+        assert!(scanner
+            .dispositions()
+            .iter()
+            .any(|d| d.path == "alias.rs"
+                && d.reason == FileDispositionReason::SkippedDuplicateSymlink));
+    }
+
+    #[test]
+    fn test_max_depth_excludes_deeper_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("one/two")).unwrap();
+        fs::write(root.join("top.rs"), "fn top() {}").unwrap();
+        fs::write(root.join("one/mid.rs"), "fn mid() {}").unwrap();
+        fs::write(root.join("one/two/deep.rs"), "fn deep() {}").unwrap();
+
+        let mut scanner =
+            FileScanner::new(root.to_path_buf()).respect_gitignore(true).max_depth(Some(2));
+        let files = scanner.scan().unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.relative_path.as_str()).collect();
+        assert_eq!(paths, ["one/mid.rs", "top.rs"]);
+
+        let stats = scanner.stats();
+        assert_eq!(stats.files_skipped_depth, 1);
+        assert_eq!(stats.files_skipped_gitignore, 0);
+        assert!(scanner.dispositions().iter().any(
+            |d| d.path == "one/two/deep.rs" && d.reason == FileDispositionReason::SkippedDepth
+        ));
+    }
+
+    #[test]
+    fn test_negated_exclude_glob_re_includes_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("vendor/important")).unwrap();
+        fs::write(root.join("main.rs"), "fn main() {}").unwrap();
+        fs::write(root.join("vendor/lib.rs"), "fn lib() {}").unwrap();
+        fs::write(root.join("vendor/important/keep.rs"), "fn keep() {}").unwrap();
+
+        let mut scanner = FileScanner::new(root.to_path_buf())
+            .respect_gitignore(false)
+            .exclude_globs(vec!["vendor/**".to_string(), "!vendor/important/**".to_string()]);
+        let files = scanner.scan().unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.relative_path.as_str()).collect();
+        assert_eq!(paths, ["main.rs", "vendor/important/keep.rs"]);
+
+        assert_eq!(scanner.stats().files_skipped_glob, 1);
+        assert!(scanner
+            .dispositions()
+            .iter()
+            .any(|d| d.path == "vendor/lib.rs" && d.reason == FileDispositionReason::SkippedGlob));
+    }
+
+    #[test]
+    fn test_scan_cache_round_trip_and_invalidation() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("main.rs"), "fn main() {}").unwrap();
+        fs::write(root.join("notes.md"), "# Notes").unwrap();
+        let scanner_with = |extensions: &[&str]| {
+            FileScanner::new(root.to_path_buf())
+                .respect_gitignore(false)
+                .include_extensions(extensions.iter().map(|e| e.to_string()).collect())
+                .scan_cache_dir(cache_dir.path().to_path_buf())
+        };
+        let ttl = Duration::from_secs(60);
+
+        let mut scanner = scanner_with(&[".rs"]);
+        assert!(scanner.try_load_scan_cache(ttl).is_none());
+        let files = scanner.scan().unwrap();
+        scanner.write_scan_cache(&files).unwrap();
+        assert!(scanner.scan_cache_path().unwrap().starts_with(cache_dir.path()));
+        // The cache lives outside the repository.
+        assert_eq!(fs::read_dir(root).unwrap().count(), 2);
+
+        let mut cached = scanner_with(&[".rs"]);
+        let restored = cached.try_load_scan_cache(ttl).unwrap();
+        let paths: Vec<&str> = restored.iter().map(|f| f.relative_path.as_str()).collect();
+        assert_eq!(paths, ["main.rs"]);
+        assert_eq!(cached.stats().files_included, scanner.stats().files_included);
+        assert_eq!(cached.dispositions().len(), scanner.dispositions().len());
+
+        // Expired, and written with different settings.
+        assert!(cached.try_load_scan_cache(Duration::ZERO).is_none());
+        assert!(scanner_with(&[".rs", ".md"]).try_load_scan_cache(ttl).is_none());
+
+        // Editing, adding, or removing a file invalidates the cache within the TTL.
+        fs::write(root.join("main.rs"), "fn main() { run(); }").unwrap();
+        assert!(scanner_with(&[".rs"]).try_load_scan_cache(ttl).is_none());
+        let files = scanner.scan().unwrap();
+        scanner.write_scan_cache(&files).unwrap();
+        assert!(scanner_with(&[".rs"]).try_load_scan_cache(ttl).is_some());
+
+        fs::write(root.join("added.rs"), "fn added() {}").unwrap();
+        assert!(scanner_with(&[".rs"]).try_load_scan_cache(ttl).is_none());
+        let files = scanner.scan().unwrap();
+        scanner.write_scan_cache(&files).unwrap();
+        fs::remove_file(root.join("notes.md")).unwrap();
+        assert!(scanner_with(&[".rs"]).try_load_scan_cache(ttl).is_none());
+    }
 }
@@ -29,6 +29,8 @@ const SKIPPED_DIRS: &[&str] = &[
 /// # Arguments
 /// * `root_path` - Root path to start from
 /// * `max_depth` - Maximum depth to traverse
+/// * `collapsed_depth` - Directories deeper than this are shown as a single
+///   `dir/ (N files)` line instead of being expanded (None expands all)
 /// * `include_files` - Whether to include files (not just directories)
 /// * `files_to_highlight` - Set of file paths to highlight in output
 ///
@@ -40,6 +42,7 @@ const SKIPPED_DIRS: &[&str] = &[
 pub fn generate_tree(
     root_path: &Path,
     max_depth: usize,
+    collapsed_depth: Option<usize>,
     include_files: bool,
     files_to_highlight: &HashSet<String>,
 ) -> Result<String> {
@@ -51,6 +54,7 @@ pub fn generate_tree(
         "",
         1,
         max_depth,
+        collapsed_depth,
         include_files,
         files_to_highlight,
         &mut lines,
@@ -65,6 +69,7 @@ fn walk_tree(
     prefix: &str,
     depth: usize,
     max_depth: usize,
+    collapsed_depth: Option<usize>,
     include_files: bool,
     files_to_highlight: &HashSet<String>,
     lines: &mut Vec<String>,
@@ -111,7 +116,11 @@ fn walk_tree(
 
         let marker = if files_to_highlight.contains(&rel_path) { " ⭐" } else { "" };
 
-        if is_dir {
+        if is_dir && collapsed_depth.is_some_and(|collapsed| depth > collapsed) {
+            let count = count_files(&path);
+            let noun = if count == 1 { "file" } else { "files" };
+            lines.push(format!("{}{}{}/ ({} {}){}", prefix, connector, name, count, noun, marker));
+        } else if is_dir {
             lines.push(format!("{}{}{}/{}", prefix, connector, name, marker));
             let extension = if is_last { "    " } else { "│   " };
             walk_tree(
@@ -120,6 +129,7 @@ fn walk_tree(
                 &format!("{}{}", prefix, extension),
                 depth + 1,
                 max_depth,
+                collapsed_depth,
                 include_files,
                 files_to_highlight,
                 lines,
@@ -132,6 +142,22 @@ fn walk_tree(
     Ok(())
 }
 
+/// Files below `dir` at any depth, skipping the entries the tree hides.
+fn count_files(dir: &Path) -> usize {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let is_dir = entry.file_type().ok()?.is_dir();
+            let name = entry.file_name().to_string_lossy().to_string();
+            (!should_skip_render_entry(&name, is_dir)).then(|| (is_dir, entry.path()))
+        })
+        .map(|(is_dir, path)| if is_dir { count_files(&path) } else { 1 })
+        .sum()
+}
+
 fn should_skip_render_entry(name: &str, is_dir: bool) -> bool {
     if name.starts_with('.') && name != ".github" && name != ".env.example" {
         return true;
@@ -158,7 +184,7 @@ mod tests {
         fs::write(root.join("src/main.rs"), "fn main() {}\n").expect("write main");
         fs::write(root.join("README.md"), "# Demo\n").expect("write readme");
 
-        let tree = generate_tree(root, 4, true, &HashSet::new()).expect("tree");
+        let tree = generate_tree(root, 4, None, true, &HashSet::new()).expect("tree");
         assert!(tree.contains("src/"));
         assert!(tree.contains("main.rs"));
         assert!(tree.contains("README.md"));
@@ -173,8 +199,37 @@ mod tests {
         fs::create_dir(root.join("src")).expect("mkdir src");
         fs::write(root.join("src/lib.rs"), "pub fn x() {}\n").expect("write lib");
 
-        let tree = generate_tree(root, 4, true, &HashSet::new()).expect("tree");
+        let tree = generate_tree(root, 4, None, true, &HashSet::new()).expect("tree");
         assert!(!tree.contains("target/"));
         assert!(tree.contains("src/"));
     }
+
+    #[test]
+    fn test_generate_tree_collapses_directories_below_collapsed_depth() {
+        let tmp = TempDir::new().expect("tmp dir");
+        let root = tmp.path();
+        fs::create_dir_all(root.join("l1/l2/l3/l4/l5")).expect("mkdir levels");
+        fs::create_dir_all(root.join("l1/l2/other")).expect("mkdir other");
+        for dir in ["l1", "l1/l2", "l1/l2/l3", "l1/l2/l3/l4", "l1/l2/l3/l4/l5"] {
+            fs::write(root.join(dir).join("mod.rs"), "\n").expect("write mod");
+        }
+        fs::write(root.join("l1/l2/other/a.rs"), "\n").expect("write a");
+
+        let tree = generate_tree(root, 10, Some(2), true, &HashSet::new()).expect("tree");
+        let lines: Vec<&str> = tree.lines().skip(1).collect();
+        assert_eq!(
+            lines,
+            [
+                "└── l1/",
+                "    ├── l2/",
+                "    │   ├── l3/ (3 files)",
+                "    │   ├── other/ (1 file)",
+                "    │   └── mod.rs",
+                "    └── mod.rs",
+            ]
+        );
+
+        let expanded = generate_tree(root, 10, None, true, &HashSet::new()).expect("tree");
+        assert!(expanded.contains("l5/"));
+    }
 }
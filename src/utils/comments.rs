@@ -0,0 +1,237 @@
+//! Comment stripping
+//!
+//! A small lexer that removes comments while copying string literals through
+//! untouched, so `"http://example.com"` or `'-- not a comment'` survive.
+//! Newlines inside removed comments are kept, leaving the line count (and so
+//! chunk line ranges) unchanged.
+
+/// Comment and string syntax for one language family.
+struct CommentSyntax {
+    line: &'static [&'static str],
+    block: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+    /// Python: a triple-quoted string opening a line is a docstring.
+    docstrings: bool,
+    /// Rust: `r#"..."#` raw strings and `'"'` char literals (but not lifetimes).
+    rust_literals: bool,
+}
+
+const C_LIKE: CommentSyntax = CommentSyntax {
+    line: &["//"],
+    block: Some(("/*", "*/")),
+    quotes: &['"', '\''],
+    docstrings: false,
+    rust_literals: false,
+};
+
+fn syntax_for(language: &str) -> Option<CommentSyntax> {
+    let syntax = match language {
+        // `'` also starts lifetimes in Rust, so char literals are lexed separately.
+        "rust" => CommentSyntax { quotes: &['"'], rust_literals: true, ..C_LIKE },
+        "javascript" | "typescript" | "go" | "vue" | "svelte" => {
+            CommentSyntax { quotes: &['"', '\'', '`'], ..C_LIKE }
+        }
+        "c" | "cpp" | "java" | "csharp" | "kotlin" | "swift" | "scala" | "protobuf" | "scss"
+        | "less" => C_LIKE,
+        "css" => CommentSyntax { line: &[], ..C_LIKE },
+        "php" => CommentSyntax { line: &["//", "#"], ..C_LIKE },
+        "python" => CommentSyntax { line: &["#"], block: None, docstrings: true, ..C_LIKE },
+        "ruby" | "bash" | "zsh" | "shell" | "yaml" | "toml" | "makefile" | "dockerfile"
+        | "graphql" => CommentSyntax { line: &["#"], block: None, ..C_LIKE },
+        "sql" => CommentSyntax { line: &["--"], ..C_LIKE },
+        _ => return None,
+    };
+    Some(syntax)
+}
+
+/// Remove line and block comments from `content` based on `language`.
+///
+/// Handles `//` and `/* */` (C-family), `#` (Python, Ruby, shell, YAML, TOML),
+/// `--` (SQL), and Python docstrings. Languages without known comment
+/// syntax are returned unchanged. A `#` only starts a comment at the start
+/// of a line or after whitespace, so `$#` and `${#var}` are kept.
+pub fn strip_comments_from_content(content: &str, language: &str) -> String {
+    let Some(syntax) = syntax_for(language) else {
+        return content.to_string();
+    };
+
+    let mut out = String::with_capacity(content.len());
+    let mut line_blank = true;
+    let mut i = 0;
+    'scan: while i < content.len() {
+        let rest = &content[i..];
+
+        if syntax.docstrings {
+            if let Some(delim) = ["\"\"\"", "'''"].into_iter().find(|d| rest.starts_with(d)) {
+                let end = rest[3..].find(delim).map(|pos| pos + 6).unwrap_or(rest.len());
+                if line_blank {
+                    trim_trailing_blanks(&mut out);
+                    push_newlines(&mut out, &rest[..end]);
+                } else {
+                    out.push_str(&rest[..end]);
+                }
+                i += end;
+                continue;
+            }
+        }
+
+        if syntax.rust_literals {
+            let after_ident = out.ends_with(|c: char| c.is_alphanumeric() || c == '_');
+            let literal = if after_ident { None } else { rust_literal_end(rest) };
+            if let Some(end) = literal {
+                out.push_str(&rest[..end]);
+                line_blank = false;
+                i += end;
+                continue;
+            }
+        }
+
+        if let Some((open, close)) = syntax.block {
+            if let Some(body) = rest.strip_prefix(open) {
+                let end = body
+                    .find(close)
+                    .map(|pos| pos + open.len() + close.len())
+                    .unwrap_or(rest.len());
+                push_newlines(&mut out, &rest[..end]);
+                i += end;
+                if line_blank || out.ends_with([' ', '\t']) {
+                    // Whitespace before the comment is kept; drop the gap after it.
+                    let gap =
+                        content[i..].len() - content[i..].trim_start_matches([' ', '\t']).len();
+                    i += gap;
+                }
+                continue;
+            }
+        }
+
+        for marker in syntax.line {
+            let preceded_ok =
+                *marker != "#" || out.is_empty() || out.ends_with(char::is_whitespace);
+            if rest.starts_with(marker) && preceded_ok {
+                trim_trailing_blanks(&mut out);
+                i += rest.find('\n').unwrap_or(rest.len());
+                continue 'scan;
+            }
+        }
+
+        let ch = rest.chars().next().unwrap_or_default();
+        if syntax.quotes.contains(&ch) {
+            let end = string_literal_end(rest, ch);
+            out.push_str(&rest[..end]);
+            line_blank = false;
+            i += end;
+            continue;
+        }
+
+        out.push(ch);
+        if ch == '\n' {
+            line_blank = true;
+        } else if !ch.is_whitespace() {
+            line_blank = false;
+        }
+        i += ch.len_utf8();
+    }
+    out
+}
+
+/// Byte length of the string literal at the start of `rest`, including both
+/// quotes. Unterminated literals run to the end of the content.
+fn string_literal_end(rest: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (idx, ch) in rest.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if ch == '\\' {
+            escaped = true;
+        } else if ch == quote {
+            return idx + ch.len_utf8();
+        }
+    }
+    rest.len()
+}
+
+/// Byte length of the Rust raw string (`r"..."`, `br#"..."#`) or char
+/// literal (`'"'`, `'\''`) at the start of `rest`. Lifetimes such as `'a`
+/// and anything else return `None`.
+fn rust_literal_end(rest: &str) -> Option<usize> {
+    let raw = rest.strip_prefix("br").or_else(|| rest.strip_prefix('r'));
+    if let Some(raw) = raw {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let body = raw[hashes..].strip_prefix('"')?;
+        let close = format!("\"{}", "#".repeat(hashes));
+        let prefix = rest.len() - body.len();
+        return Some(body.find(&close).map_or(rest.len(), |pos| prefix + pos + close.len()));
+    }
+    let mut chars = rest.strip_prefix('\'')?.chars();
+    match chars.next()? {
+        '\\' => Some(string_literal_end(rest, '\'')),
+        ch if chars.next() == Some('\'') => Some(ch.len_utf8() + 2),
+        _ => None,
+    }
+}
+
+fn push_newlines(out: &mut String, removed: &str) {
+    out.extend(removed.chars().filter(|&c| c == '\n'));
+}
+
+fn trim_trailing_blanks(out: &mut String) {
+    let trimmed = out.trim_end_matches([' ', '\t']).len();
+    out.truncate(trimmed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_rust_comments_and_keeps_body() {
+        let source = "/// Adds one.\nfn inc(x: u32) -> u32 {\n    /* fast path */ x + 1 // no overflow check\n}\nconst URL: &str = \"http://example.com\";\n";
+        let stripped = strip_comments_from_content(source, "rust");
+        assert_eq!(
+            stripped,
+            "\nfn inc(x: u32) -> u32 {\n    x + 1\n}\nconst URL: &str = \"http://example.com\";\n"
+        );
+        assert_eq!(stripped.lines().count(), source.lines().count());
+    }
+
+    #[test]
+    fn rust_char_literals_do_not_open_strings() {
+        let source = "let q = '\"'; // quote\nlet e = '\\''; let s: &'static str = \"a\"; // tail\nlet x = 1; // gone\n";
+        assert_eq!(
+            strip_comments_from_content(source, "rust"),
+            "let q = '\"';\nlet e = '\\''; let s: &'static str = \"a\";\nlet x = 1;\n"
+        );
+    }
+
+    #[test]
+    fn rust_raw_strings_are_kept_whole() {
+        let source = "let re = r#\"\"// not a comment\"#; // comment\nlet b = br\"//x\"; // gone\n";
+        assert_eq!(
+            strip_comments_from_content(source, "rust"),
+            "let re = r#\"\"// not a comment\"#;\nlet b = br\"//x\";\n"
+        );
+    }
+
+    #[test]
+    fn strips_python_comments_and_docstrings() {
+        let source = "class Greeter:\n    \"\"\"Say hello.\n\n    Politely.\n    \"\"\"\n\n    def greet(self):  # entry point\n        return \"# not a comment\"\n";
+        let stripped = strip_comments_from_content(source, "python");
+        assert_eq!(
+            stripped,
+            "class Greeter:\n\n\n\n\n\n    def greet(self):\n        return \"# not a comment\"\n"
+        );
+    }
+
+    #[test]
+    fn strips_sql_comments() {
+        let source =
+            "-- list users\nSELECT id, name /* display */ FROM users\nWHERE note <> '-- keep';\n";
+        let stripped = strip_comments_from_content(source, "sql");
+        assert_eq!(stripped, "\nSELECT id, name FROM users\nWHERE note <> '-- keep';\n");
+    }
+
+    #[test]
+    fn unknown_language_is_unchanged() {
+        assert_eq!(strip_comments_from_content("// hi\n", "text"), "// hi\n");
+    }
+}
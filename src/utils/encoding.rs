@@ -10,12 +10,18 @@
 use anyhow::{Context, Result};
 use chardetng::EncodingDetector;
 use encoding_rs::{Encoding, UTF_8};
+use memmap2::Mmap;
 use std::fs::File;
 use std::io::Read;
+use std::ops::Deref;
 use std::path::Path;
 
 const DEFAULT_SAMPLE_SIZE: usize = 8192;
 
+/// Files larger than this are read with [`read_file_mmap`] instead of
+/// [`read_file_safe`].
+pub const MMAP_THRESHOLD_BYTES: u64 = 64 * 1024;
+
 /// Detect the encoding of a file.
 ///
 /// Strategy (matching Python implementation):
@@ -208,6 +214,58 @@ fn read_bytes(path: &Path, max_bytes: Option<usize>) -> Result<Vec<u8>> {
     }
 }
 
+/// File text that is either borrowed from a read-only memory map or owned.
+pub enum FileText {
+    /// Memory-mapped file bytes, validated as UTF-8 when mapped.
+    Mapped(Mmap),
+    /// Text read into memory, decoded to UTF-8 when needed.
+    Owned(String),
+}
+
+impl Deref for FileText {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match self {
+            // SAFETY: `read_file_mmap` only builds `Mapped` after
+            // `std::str::from_utf8` accepted the mapped bytes.
+            Self::Mapped(map) => unsafe { std::str::from_utf8_unchecked(map) },
+            Self::Owned(content) => content,
+        }
+    }
+}
+
+/// Read a file through a read-only memory map, avoiding a heap copy of large
+/// files.
+///
+/// Valid UTF-8 without a BOM is returned as [`FileText::Mapped`]. Anything
+/// else, including a failure to map the file, falls back to
+/// [`read_file_safe`] and is returned as [`FileText::Owned`].
+///
+/// # Returns
+/// A tuple `(content, encoding_used)`
+pub fn read_file_mmap(path: &Path) -> Result<(FileText, String)> {
+    if let Some(map) = map_utf8_file(path) {
+        return Ok((FileText::Mapped(map), "utf-8".to_string()));
+    }
+    let (content, encoding) = read_file_safe(path, None, None)?;
+    Ok((FileText::Owned(content), encoding))
+}
+
+fn map_utf8_file(path: &Path) -> Option<Mmap> {
+    let file = File::open(path).ok()?;
+    if file.metadata().ok()?.len() == 0 {
+        return None;
+    }
+    // SAFETY: the map is read-only. A file truncated or rewritten while
+    // mapped is the same hazard every mmap-based reader accepts.
+    let map = unsafe { Mmap::map(&file) }.ok()?;
+    if map.starts_with(&[0xef, 0xbb, 0xbf]) || std::str::from_utf8(&map).is_err() {
+        return None;
+    }
+    Some(map)
+}
+
 /// Read a specific line range from a file without loading it entirely.
 ///
 /// # Arguments
@@ -325,4 +383,34 @@ mod tests {
         assert!(lines[0].contains("Line 2"));
         assert!(lines[1].contains("Line 3"));
     }
+
+    #[test]
+    fn test_read_file_mmap_borrows_plain_utf8() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all("fn main() {}\n// héllo\n".as_bytes()).unwrap();
+        file.flush().unwrap();
+
+        let (content, encoding) = read_file_mmap(file.path()).unwrap();
+        assert!(matches!(content, FileText::Mapped(_)));
+        assert_eq!(&*content, "fn main() {}\n// héllo\n");
+        assert_eq!(encoding, "utf-8");
+    }
+
+    #[test]
+    fn test_read_file_mmap_falls_back_for_bom_and_legacy_encodings() {
+        let mut bom = NamedTempFile::new().unwrap();
+        bom.write_all(&[0xef, 0xbb, 0xbf]).unwrap();
+        bom.write_all(b"Hello").unwrap();
+        bom.flush().unwrap();
+        let mut latin1 = NamedTempFile::new().unwrap();
+        latin1.write_all(b"caf\xe9 cr\xe8me br\xfbl\xe9e\n").unwrap();
+        latin1.flush().unwrap();
+        let empty = NamedTempFile::new().unwrap();
+
+        for path in [bom.path(), latin1.path(), empty.path()] {
+            let (content, encoding) = read_file_mmap(path).unwrap();
+            assert!(matches!(content, FileText::Owned(_)));
+            assert_eq!((content.to_string(), encoding), read_file_safe(path, None, None).unwrap());
+        }
+    }
 }
@@ -3,6 +3,7 @@
 //! Provides content-addressable hashing for chunk identifiers,
 //! ensuring deterministic IDs based on content, path, and line numbers.
 
+use super::normalize_line_endings;
 use sha2::{Digest, Sha256};
 
 /// A buffer large enough for 32 bytes of hex (64 chars).
@@ -26,9 +27,10 @@ fn bytes_to_hex<'a>(bytes: &[u8], buf: &'a mut [u8; HEX_BUF_SIZE]) -> &'a str {
 /// The hash is based on:
 /// - File path
 /// - Start and end line numbers
-/// - First 1000 characters of content
+/// - First 1000 characters of content, with CRLF line endings read as LF
 ///
-/// This produces deterministic IDs that remain consistent across runs.
+/// This produces deterministic IDs that remain consistent across runs and
+/// across checkouts that differ only in line endings.
 ///
 /// # Arguments
 /// * `content` - Chunk content
@@ -41,7 +43,7 @@ fn bytes_to_hex<'a>(bytes: &[u8], buf: &'a mut [u8; HEX_BUF_SIZE]) -> &'a str {
 pub fn stable_hash(content: &str, path: &str, start_line: usize, end_line: usize) -> String {
     // Match Python: hashlib.sha256(f"{path}:{start_line}-{end_line}:{content[:1000]}".encode()).hexdigest()[:16]
     // content[:1000] in Python slices by character, so use char-boundary-safe truncation.
-    let content_prefix: String = content.chars().take(1000).collect();
+    let content_prefix: String = normalize_line_endings(content).chars().take(1000).collect();
     let hash_input = format!("{path}:{start_line}-{end_line}:{content_prefix}");
     let mut hasher = Sha256::new();
     hasher.update(hash_input.as_bytes());
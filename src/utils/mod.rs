@@ -1,16 +1,27 @@
 //! Utility functions
 
 pub mod classify;
+pub mod comments;
 pub mod encoding;
 pub mod hashing;
 pub mod paths;
 pub mod tokens;
 
 pub use classify::{is_likely_generated, is_likely_minified, is_lock_file, is_vendored};
-pub use encoding::{is_binary_file, read_file_safe};
+pub use comments::strip_comments_from_content;
+pub use encoding::{
+    is_binary_file, read_file_mmap, read_file_safe, FileText, MMAP_THRESHOLD_BYTES,
+};
 pub use hashing::stable_hash;
 pub use paths::{normalize_path, redact_url_credentials};
-pub use tokens::estimate_tokens;
+pub use tokens::{count_tokens_tiktoken, estimate_tokens, Tokenizer};
+
+use anyhow::{Context, Result};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::borrow::Cow;
+use std::io::Write;
+use std::path::Path;
 
 /// Format a number with thousands separators (e.g. 1048576 → "1,048,576").
 ///
@@ -28,3 +39,236 @@ pub fn format_with_commas(n: u64) -> String {
     }
     result
 }
+
+/// Normalize whitespace so formatting-only differences don't change a chunk.
+///
+/// Tabs become four spaces, trailing whitespace is stripped from every line,
+/// and leading and trailing blank lines are removed. Kept lines retain their
+/// `\n` terminator; unterminated final lines stay unterminated.
+pub fn normalize_chunk_content(content: &str) -> String {
+    let lines: Vec<String> = content
+        .split_inclusive('\n')
+        .map(|line| {
+            let body = line.strip_suffix('\n').unwrap_or(line);
+            let newline = if body.len() < line.len() { "\n" } else { "" };
+            format!("{}{}", body.replace('\t', "    ").trim_end(), newline)
+        })
+        .collect();
+
+    let is_blank = |line: &String| line.trim().is_empty();
+    let Some(first) = lines.iter().position(|l| !is_blank(l)) else {
+        return String::new();
+    };
+    let last = lines.iter().rposition(|l| !is_blank(l)).unwrap_or(first);
+    lines[first..=last].concat()
+}
+
+/// Convert CRLF line endings to LF, borrowing when there are none.
+pub fn normalize_line_endings(s: &str) -> Cow<'_, str> {
+    if s.contains("\r\n") {
+        Cow::Owned(s.replace("\r\n", "\n"))
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+/// Variables set by specific CI systems (GitHub Actions, GitLab, Jenkins, CircleCI).
+const CI_SYSTEM_VARS: &[&str] = &["GITHUB_ACTIONS", "GITLAB_CI", "JENKINS_URL", "CIRCLECI"];
+
+/// Whether the process appears to run under CI: `CI` is `true`/`1`, or a
+/// CI-system-specific variable is set.
+pub fn detect_ci_environment() -> bool {
+    detect_ci_environment_with(|name| std::env::var(name).ok())
+}
+
+/// Like [`detect_ci_environment`], reading variables through `lookup`.
+pub fn detect_ci_environment_with(lookup: impl Fn(&str) -> Option<String>) -> bool {
+    let ci_flag = lookup("CI")
+        .is_some_and(|value| matches!(value.trim().to_ascii_lowercase().as_str(), "true" | "1"));
+    ci_flag || CI_SYSTEM_VARS.iter().any(|name| lookup(name).is_some_and(|v| !v.is_empty()))
+}
+
+/// Outer extensions that mark a template or generated copy of another file
+/// (`setup.py.in`, `config.yaml.j2`).
+const TEMPLATE_SUFFIXES: &[&str] = &["in", "tmpl", "tpl", "j2", "jinja", "template", "txt"];
+
+/// Inner extensions recognised under a [`TEMPLATE_SUFFIXES`] suffix.
+const TEMPLATE_INNER_EXTENSIONS: &[&str] = &[
+    ".py", ".rs", ".sh", ".js", ".ts", ".rb", ".go", ".java", ".c", ".h", ".cpp", ".php", ".yaml",
+    ".yml", ".toml", ".json", ".html", ".css", ".sql", ".md",
+];
+
+/// Guess the extension a file's content belongs to, for files whose own
+/// extension is missing or not code (`.txt`, templates).
+///
+/// `first_bytes` is the start of the file (512 bytes is plenty). Checked in
+/// order: a template suffix on the path (`setup.py.in` → `.py`), a shebang
+/// (`#!/usr/bin/env python3` → `.py`), `<?php`, a leading YAML `---`, a
+/// Python `# -*- coding` line, then Python (`def`, `import`) or Rust (`fn`,
+/// `//!`, `#![`, `use a::b;`) lines. Plain `//` comments are shared by every
+/// C-family language and are not taken as evidence. Returns `None` for
+/// binary content or when nothing matches.
+pub fn sniff_content_type(path: &Path, first_bytes: &[u8]) -> Option<&'static str> {
+    if let Some(ext) = template_inner_extension(path) {
+        return Some(ext);
+    }
+    if first_bytes.contains(&0) {
+        return None;
+    }
+    let text = String::from_utf8_lossy(first_bytes);
+    let text = text.trim_start_matches('\u{feff}');
+    let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+    let first = lines.next()?;
+
+    if let Some(interpreter) = first.strip_prefix("#!") {
+        return shebang_extension(interpreter);
+    }
+    if first.starts_with("<?php") {
+        return Some(".php");
+    }
+    if first == "---" || first.starts_with("%YAML") {
+        return Some(".yaml");
+    }
+    std::iter::once(first).chain(lines).find_map(|line| {
+        if (line.starts_with('#') && line.contains("-*- coding"))
+            || line.starts_with("def ")
+            || line.starts_with("async def ")
+            || (line.starts_with("import ") && !line.ends_with(';'))
+            || (line.starts_with("from ") && line.contains(" import "))
+        {
+            Some(".py")
+        } else if line.starts_with("fn ")
+            || line.starts_with("pub fn ")
+            || line.starts_with("pub(crate) fn ")
+            || line.starts_with("//!")
+            || line.starts_with("#![")
+            || (line.starts_with("use ") && line.contains("::") && line.ends_with(';'))
+        {
+            Some(".rs")
+        } else {
+            None
+        }
+    })
+}
+
+fn template_inner_extension(path: &Path) -> Option<&'static str> {
+    let outer = path.extension()?.to_str()?.to_ascii_lowercase();
+    if !TEMPLATE_SUFFIXES.contains(&outer.as_str()) {
+        return None;
+    }
+    let inner = Path::new(path.file_stem()?).extension()?.to_str()?.to_ascii_lowercase();
+    TEMPLATE_INNER_EXTENSIONS.iter().copied().find(|ext| ext[1..] == inner)
+}
+
+/// Extension for a shebang interpreter (`/usr/bin/env -S python3 -u`).
+fn shebang_extension(interpreter: &str) -> Option<&'static str> {
+    let mut words = interpreter.split_whitespace();
+    let mut program = words.next()?.rsplit('/').next()?;
+    if program == "env" {
+        program = words.find(|word| !word.starts_with('-'))?;
+    }
+    let name = program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    match name {
+        "python" | "pypy" => Some(".py"),
+        "sh" | "bash" | "dash" | "ksh" => Some(".sh"),
+        "zsh" => Some(".zsh"),
+        "node" | "nodejs" => Some(".js"),
+        "deno" | "bun" | "ts-node" | "tsx" => Some(".ts"),
+        "ruby" => Some(".rb"),
+        "php" => Some(".php"),
+        _ => None,
+    }
+}
+
+/// Write `content` to `path` as a gzip stream (no embedded file name or mtime,
+/// so identical content always yields identical bytes).
+pub fn write_compressed(path: &Path, content: &str) -> Result<()> {
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut encoder = GzEncoder::new(file, Compression::default());
+    encoder.write_all(content.as_bytes())?;
+    encoder.finish().with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_ci_environment_checks_ci_flag_and_system_vars() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+            }
+        };
+        assert!(detect_ci_environment_with(env(&[("CI", "true")])));
+        assert!(detect_ci_environment_with(env(&[("CI", "1")])));
+        assert!(detect_ci_environment_with(env(&[("GITHUB_ACTIONS", "true")])));
+        assert!(detect_ci_environment_with(env(&[("JENKINS_URL", "https://ci.example.com/")])));
+        assert!(!detect_ci_environment_with(env(&[("CI", "false")])));
+        assert!(!detect_ci_environment_with(env(&[("GITLAB_CI", "")])));
+        assert!(!detect_ci_environment_with(env(&[])));
+    }
+
+    #[test]
+    fn sniff_content_type_detects_scripts_and_source() {
+        let sniff =
+            |name: &str, content: &str| sniff_content_type(Path::new(name), content.as_bytes());
+        assert_eq!(sniff("manage", "#!/usr/bin/env python3\nimport sys\n"), Some(".py"));
+        assert_eq!(sniff("run", "#!/usr/bin/env -S python3 -u\n"), Some(".py"));
+        assert_eq!(sniff("deploy", "#!/bin/bash\nset -e\n"), Some(".sh"));
+        assert_eq!(sniff("gen", "# -*- coding: utf-8 -*-\nx = 1\n"), Some(".py"));
+        assert_eq!(sniff("tool", "\n\nfrom os import path\n"), Some(".py"));
+        assert_eq!(
+            sniff("output", "// generated\npub fn answer() -> u32 {\n    42\n}\n"),
+            Some(".rs")
+        );
+        assert_eq!(sniff("lib", "//! Crate docs\n"), Some(".rs"));
+        assert_eq!(sniff("values", "---\nname: app\n"), Some(".yaml"));
+        assert_eq!(sniff("setup.py.in", "anything"), Some(".py"));
+        assert_eq!(sniff("notes.txt", "// just a comment\nplain text\n"), None);
+        assert_eq!(sniff("LICENSE", "MIT License\n"), None);
+        assert_eq!(sniff_content_type(Path::new("blob"), b"fn \0\x01"), None);
+        assert_eq!(sniff("empty", ""), None);
+    }
+
+    #[test]
+    fn normalize_chunk_content_expands_tabs_and_trims() {
+        let content = "\n  \nfn a() {\t\n\tlet x = 1;  \n}\n\n";
+        assert_eq!(normalize_chunk_content(content), "fn a() {\n    let x = 1;\n}\n");
+        assert_eq!(normalize_chunk_content(" \n\t\n"), "");
+        assert_eq!(normalize_chunk_content("x  "), "x");
+    }
+
+    #[test]
+    fn normalize_line_endings_converts_crlf_only() {
+        assert_eq!(normalize_line_endings("a\r\nb\r\n"), "a\nb\n");
+        assert!(matches!(normalize_line_endings("a\nb\r"), Cow::Borrowed("a\nb\r")));
+    }
+
+    #[test]
+    fn stable_hash_ignores_line_ending_style() {
+        let lf = "fn main() {\n    run();\n}\n";
+        let crlf = "fn main() {\r\n    run();\r\n}\r\n";
+        assert_eq!(stable_hash(lf, "src/main.rs", 1, 3), stable_hash(crlf, "src/main.rs", 1, 3));
+        assert_ne!(stable_hash(lf, "src/main.rs", 1, 3), stable_hash(lf, "src/lib.rs", 1, 3));
+    }
+
+    #[test]
+    fn write_compressed_round_trips() {
+        use flate2::read::GzDecoder;
+        use std::io::Read;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pack.md.gz");
+        let content = "# Pack\n\n".to_string() + &"fn repeated() {}\n".repeat(200);
+        write_compressed(&path, &content).unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        assert!(bytes.len() < content.len());
+        let mut decoded = String::new();
+        GzDecoder::new(bytes.as_slice()).read_to_string(&mut decoded).unwrap();
+        assert_eq!(decoded, content);
+    }
+}
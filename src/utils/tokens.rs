@@ -10,3 +10,117 @@
 pub fn estimate_tokens(text: &str) -> usize {
     text.len() / 4
 }
+
+/// Token counter used for chunk estimates and budgets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Tokenizer {
+    /// [`estimate_tokens`].
+    #[default]
+    Heuristic,
+    /// BPE `cl100k_base` (GPT-4 and the `text-embedding-3` models).
+    Cl100k,
+    /// BPE `o200k_base` (GPT-4o-family models).
+    O200k,
+}
+
+impl Tokenizer {
+    /// Tokenizer for the configured `semantic_model`: BPE for names starting
+    /// with `gpt-` or `text-embedding` when the `tiktoken` feature is enabled,
+    /// the heuristic otherwise.
+    pub fn for_model(model: Option<&str>) -> Self {
+        let model = model.unwrap_or_default().trim().to_ascii_lowercase();
+        if cfg!(feature = "tiktoken")
+            && (model.starts_with("gpt-") || model.starts_with("text-embedding"))
+        {
+            Self::bpe_for(&model)
+        } else {
+            Self::Heuristic
+        }
+    }
+
+    fn bpe_for(model: &str) -> Self {
+        if model.starts_with("gpt-4o") || model.starts_with("o1") || model.starts_with("o3") {
+            Self::O200k
+        } else {
+            Self::Cl100k
+        }
+    }
+
+    /// Number of tokens in `text`.
+    pub fn count(self, text: &str) -> usize {
+        match self {
+            Self::Heuristic => estimate_tokens(text),
+            Self::Cl100k | Self::O200k => count_bpe(text, self),
+        }
+    }
+}
+
+/// Count tokens with the BPE tokenizer used by OpenAI models.
+///
+/// `o200k_base` is used for GPT-4o-family models (`gpt-4o*`, `o1*`, `o3*`),
+/// `cl100k_base` for everything else. Falls back to [`estimate_tokens`] if the
+/// tokenizer tables fail to load or the `tiktoken` feature is disabled.
+pub fn count_tokens_tiktoken(text: &str, model: &str) -> usize {
+    Tokenizer::bpe_for(&model.trim().to_ascii_lowercase()).count(text)
+}
+
+#[cfg(feature = "tiktoken")]
+fn count_bpe(text: &str, tokenizer: Tokenizer) -> usize {
+    use once_cell::sync::Lazy;
+    use tiktoken_rs::CoreBPE;
+
+    static CL100K: Lazy<Option<CoreBPE>> = Lazy::new(|| tiktoken_rs::cl100k_base().ok());
+    static O200K: Lazy<Option<CoreBPE>> = Lazy::new(|| tiktoken_rs::o200k_base().ok());
+
+    let bpe = match tokenizer {
+        Tokenizer::O200k => O200K.as_ref(),
+        _ => CL100K.as_ref(),
+    };
+    match bpe {
+        Some(bpe) => bpe.encode_with_special_tokens(text).len(),
+        None => estimate_tokens(text),
+    }
+}
+
+/// Heuristic fallback used when the `tiktoken` feature is disabled.
+#[cfg(not(feature = "tiktoken"))]
+fn count_bpe(text: &str, _tokenizer: Tokenizer) -> usize {
+    estimate_tokens(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_tokens_uses_byte_length() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcdefgh"), 2);
+    }
+
+    #[cfg(feature = "tiktoken")]
+    #[test]
+    fn test_count_tokens_tiktoken_known_snippets() {
+        assert_eq!(count_tokens_tiktoken("hello world", "gpt-4"), 2);
+        assert_eq!(count_tokens_tiktoken("hello world", "gpt-4o"), 2);
+        assert_eq!(count_tokens_tiktoken("", "gpt-4"), 0);
+    }
+
+    #[cfg(not(feature = "tiktoken"))]
+    #[test]
+    fn test_count_tokens_tiktoken_falls_back_to_heuristic() {
+        let text = "fn main() {}\n";
+        assert_eq!(count_tokens_tiktoken(text, "gpt-4"), estimate_tokens(text));
+    }
+
+    #[test]
+    fn test_tokenizer_for_model() {
+        let bpe =
+            |tokenizer| if cfg!(feature = "tiktoken") { tokenizer } else { Tokenizer::Heuristic };
+        assert_eq!(Tokenizer::for_model(None), Tokenizer::Heuristic);
+        assert_eq!(Tokenizer::for_model(Some("nomic-embed-text")), Tokenizer::Heuristic);
+        assert_eq!(Tokenizer::for_model(Some("gpt-4")), bpe(Tokenizer::Cl100k));
+        assert_eq!(Tokenizer::for_model(Some("text-embedding-3-small")), bpe(Tokenizer::Cl100k));
+        assert_eq!(Tokenizer::for_model(Some(" GPT-4o-mini ")), bpe(Tokenizer::O200k));
+    }
+}
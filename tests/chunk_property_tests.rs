@@ -0,0 +1,163 @@
+//! Property tests for chunk boundary invariants across arbitrary file content.
+
+use proptest::prelude::*;
+use repo_context::chunk::code_chunker::chunk_code;
+use repo_context::chunk::line_chunker::chunk_lines;
+use repo_context::chunk::markdown_chunker::chunk_markdown;
+use repo_context::domain::{Chunk, FileInfo};
+use repo_context::utils::stable_hash;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+/// Lines that exercise each chunker's boundary detection.
+const SHAPED_LINES: &[&str] = &[
+    "",
+    "    ",
+    "fn main() {",
+    "pub fn helper(x: u32) -> u32 {",
+    "    x + 1",
+    "}",
+    "/// Docs for the next item.",
+    "#[derive(Debug)]",
+    "struct Point { x: i32 }",
+    "impl Point {",
+    "def run():",
+    "class Service:",
+    "    return value",
+    "function start() {",
+    "# Title",
+    "## Section",
+    "```",
+    "Plain prose with a few words.",
+];
+
+fn file_info(relative_path: &str, language: &str) -> FileInfo {
+    FileInfo {
+        path: PathBuf::from("/tmp/repo").join(relative_path),
+        relative_path: relative_path.to_string(),
+        size_bytes: 0,
+        extension: String::new(),
+        language: language.to_string(),
+        id: "f".to_string(),
+        priority: 0.5,
+        token_estimate: 0,
+        tags: BTreeSet::new(),
+        is_readme: false,
+        is_config: false,
+        is_doc: false,
+        is_env_file: false,
+        is_symlink: false,
+        symlink_target: None,
+        complexity_estimate: 0,
+        priority_explanation: None,
+    }
+}
+
+/// File content built from shaped lines mixed with arbitrary single-line strings.
+fn content() -> impl Strategy<Value = String> {
+    let line = prop_oneof![
+        3 => prop::sample::select(SHAPED_LINES).prop_map(str::to_string),
+        1 => any::<String>().prop_map(|s| s.replace(['\n', '\r'], " ")),
+    ];
+    (prop::collection::vec(line, 0..120), any::<bool>()).prop_map(|(lines, trailing_newline)| {
+        let mut text = lines.join("\n");
+        if trailing_newline && !text.is_empty() {
+            text.push('\n');
+        }
+        text
+    })
+}
+
+type Chunker = fn(&FileInfo, &str, usize, usize) -> Vec<Chunk>;
+
+/// Chunkers under test, with a language that selects the intended path.
+fn chunkers() -> [(&'static str, &'static str, Chunker); 5] {
+    [
+        ("line", "text", chunk_lines),
+        ("code/tree-sitter rust", "rust", chunk_code),
+        ("code/tree-sitter python", "python", chunk_code),
+        ("code/fallback", "ruby", chunk_code),
+        ("markdown", "markdown", chunk_markdown),
+    ]
+}
+
+/// Checks the boundary invariants for `chunks` of `content`.
+///
+/// Whitespace-only sections are dropped by the chunkers, so only lines with
+/// content must be covered.
+fn check_invariants(name: &str, content: &str, chunks: &[Chunk], overlap_tokens: usize) {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let mut covered = vec![false; lines.len()];
+    for chunk in chunks {
+        assert!(chunk.start_line >= 1, "{name}: chunk starts before line 1");
+        assert!(chunk.start_line <= chunk.end_line, "{name}: inverted range");
+        assert!(chunk.end_line <= lines.len(), "{name}: chunk ends past the file");
+        assert!(!chunk.content.is_empty(), "{name}: empty chunk content");
+        assert_eq!(chunk.content, lines[chunk.start_line - 1..chunk.end_line].concat());
+        covered[chunk.start_line - 1..chunk.end_line].iter_mut().for_each(|c| *c = true);
+    }
+    for (idx, line) in lines.iter().enumerate() {
+        assert!(line.trim().is_empty() || covered[idx], "{name}: line {} not covered", idx + 1);
+    }
+    for pair in chunks.windows(2) {
+        let (prev, next) = (&pair[0], &pair[1]);
+        if overlap_tokens == 0 {
+            assert!(next.start_line > prev.end_line, "{name}: chunks overlap without overlap");
+        } else {
+            // Overlap repeats the tail of the previous chunk, never all of it.
+            assert!(next.start_line > prev.start_line, "{name}: chunk starts are not increasing");
+            assert!(next.end_line > prev.end_line, "{name}: chunk adds no new lines");
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    #[test]
+    fn chunks_cover_every_line_in_order(
+        content in content(),
+        max_tokens in prop::sample::select(vec![1usize, 8, 32, 128, 512]),
+        overlap_tenths in 0usize..=5,
+    ) {
+        let overlap_tokens = max_tokens * overlap_tenths / 10;
+        for (name, language, chunker) in chunkers() {
+            let info = file_info("src/sample", language);
+            let chunks = chunker(&info, &content, max_tokens, overlap_tokens);
+            check_invariants(name, &content, &chunks, overlap_tokens);
+        }
+    }
+
+    #[test]
+    fn chunk_ids_are_deterministic(
+        content in content(),
+        max_tokens in prop::sample::select(vec![8usize, 64, 512]),
+    ) {
+        for (name, language, chunker) in chunkers() {
+            let info = file_info("src/sample", language);
+            let first = chunker(&info, &content, max_tokens, 0);
+            let second = chunker(&info, &content, max_tokens, 0);
+            let ids = |chunks: &[Chunk]| chunks.iter().map(|c| c.id.clone()).collect::<Vec<_>>();
+            prop_assert_eq!(ids(&first), ids(&second), "{}", name);
+            for chunk in &first {
+                prop_assert_eq!(
+                    &chunk.id,
+                    &stable_hash(&chunk.content, &chunk.path, chunk.start_line, chunk.end_line)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn stable_hash_is_deterministic(
+        content in any::<String>(),
+        path in "[a-z/]{1,20}\\.rs",
+        start in 1usize..10_000,
+        len in 0usize..500,
+    ) {
+        let id = stable_hash(&content, &path, start, start + len);
+        prop_assert_eq!(id.len(), 16);
+        prop_assert_eq!(&id, &stable_hash(&content, &path, start, start + len));
+        prop_assert_ne!(&id, &stable_hash(&content, &path, start + 1, start + len + 1));
+    }
+}
@@ -59,6 +59,116 @@ fn export_is_deterministic_without_timestamp() {
     assert_eq!(report1, report2);
 }
 
+#[test]
+fn export_compress_writes_gzip_matching_plain_output() {
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    let fixture = TestRepo::new();
+    let out_base = TempDir::new().expect("temp out");
+    let plain_out = out_base.path().join("plain");
+    let gz_out = out_base.path().join("gz");
+
+    run_export(fixture.root(), &plain_out, "both", false);
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("repo-context"));
+    cmd.args([
+        "export",
+        "--path",
+        fixture.root().to_str().expect("repo str"),
+        "--mode",
+        "both",
+        "--output-dir",
+        gz_out.to_str().expect("out str"),
+        "--no-timestamp",
+        "--chunk-tokens",
+        "200",
+        "--chunk-overlap",
+        "20",
+        "--min-chunk-tokens",
+        "80",
+        "--compress",
+    ]);
+    cmd.env("HOME", &gz_out);
+    cmd.assert().success();
+
+    let plain_dir = resolve_output_dir(&plain_out, fixture.root());
+    let gz_dir = resolve_output_dir(&gz_out, fixture.root());
+    for artifact in ["context_pack.md", "chunks.jsonl"] {
+        let name = output_file_name(fixture.root(), artifact);
+        assert!(!gz_dir.join(&name).exists(), "{name} should only be written compressed");
+        let plain = fs::read_to_string(plain_dir.join(&name)).expect("read plain");
+        let mut decoded = String::new();
+        GzDecoder::new(fs::File::open(gz_dir.join(format!("{name}.gz"))).expect("open gz"))
+            .read_to_string(&mut decoded)
+            .expect("decompress");
+        assert_eq!(decoded, plain);
+    }
+
+    let report: Value = serde_json::from_str(
+        &fs::read_to_string(gz_dir.join(output_file_name(fixture.root(), "report.json")))
+            .expect("report stays uncompressed"),
+    )
+    .expect("parse report");
+    let compressed = report["compressed_outputs"].as_array().expect("compressed_outputs");
+    assert_eq!(compressed.len(), 2);
+    for entry in compressed {
+        assert!(entry["path"].as_str().expect("path").ends_with(".gz"));
+        assert!(entry["compressed_bytes"].as_u64().expect("compressed") > 0);
+        assert!(entry["original_bytes"].as_u64().expect("original") > 0);
+    }
+}
+
+#[test]
+fn export_dedup_files_keeps_one_identical_copy() {
+    let temp = TempDir::new().expect("temp dir");
+    let root = temp.path();
+    let shared = "pub fn shared_helper(x: u32) -> u32 {\n    x * 2\n}\n";
+    for rel in ["src/helper.rs", "third_party/a/helper.rs", "gen/helper_copy.rs"] {
+        let path = root.join(rel);
+        fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+        fs::write(path, shared).expect("write copy");
+    }
+    fs::write(root.join("src/main.rs"), "fn main() {}\n").expect("write main");
+
+    let out = TempDir::new().expect("out dir");
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("repo-context"));
+    cmd.args([
+        "export",
+        "--path",
+        root.to_str().expect("root"),
+        "--mode",
+        "rag",
+        "--output-dir",
+        out.path().to_str().expect("out"),
+        "--no-timestamp",
+        "--dedup-files",
+    ]);
+    cmd.env("HOME", out.path());
+    cmd.assert().success();
+
+    let repo_name = root.file_name().and_then(|n| n.to_str()).unwrap_or("repo");
+    let actual = out.path().join(repo_name);
+    let jsonl =
+        fs::read_to_string(actual.join(format!("{}_chunks.jsonl", repo_name))).expect("read jsonl");
+    let paths: std::collections::BTreeSet<String> = jsonl
+        .lines()
+        .skip(1)
+        .map(|line| {
+            let chunk: Value = serde_json::from_str(line).expect("chunk json");
+            chunk["path"].as_str().expect("path").to_string()
+        })
+        .collect();
+    let copies = paths.iter().filter(|p| p.contains("helper")).count();
+    assert_eq!(copies, 1, "expected one helper copy, got {paths:?}");
+    assert!(paths.contains("src/main.rs"));
+
+    let report: Value = serde_json::from_str(
+        &fs::read_to_string(actual.join(format!("{}_report.json", repo_name))).expect("report"),
+    )
+    .expect("parse report");
+    assert_eq!(report["stats"]["files_deduplicated"], 2);
+}
+
 #[test]
 fn export_applies_redaction_by_default() {
     let fixture = TestRepo::new();
@@ -395,6 +505,63 @@ impl TestRepo {
 
 // ── HIGH-VALUE REGRESSION TESTS ──────────────────────────────────────────────
 
+#[test]
+fn export_fully_redacts_env_files() {
+    let temp = TempDir::new().expect("temp dir");
+    let root = temp.path();
+    fs::write(root.join("README.md"), "# App\n").expect("write readme");
+    for name in [".env", ".env.local", ".env.production"] {
+        fs::write(root.join(name), "DATABASE_URL=postgres://admin:hunter2@db/app\n")
+            .expect("write env file");
+    }
+
+    // (extra flags, env files expected in the export)
+    let runs: [(&[&str], usize); 3] =
+        [(&[], 0), (&["--include-env-files"], 3), (&["--include-env-files", "--no-redact"], 3)];
+    for (extra_args, expected) in runs {
+        let out = TempDir::new().expect("out dir");
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("repo-context"));
+        cmd.args([
+            "export",
+            "--path",
+            root.to_str().expect("root"),
+            "--mode",
+            "rag",
+            "--output-dir",
+            out.path().to_str().expect("out"),
+            "--no-timestamp",
+        ]);
+        cmd.args(extra_args);
+        cmd.env("HOME", out.path());
+        let assert = cmd.assert().success();
+        if expected > 0 {
+            assert.stderr(predicates::str::contains(format!("{expected} .env file(s) detected")));
+        }
+
+        let repo_name = root.file_name().and_then(|n| n.to_str()).unwrap_or("repo");
+        let actual = out.path().join(repo_name);
+        let jsonl = fs::read_to_string(actual.join(format!("{}_chunks.jsonl", repo_name)))
+            .expect("read jsonl");
+        assert!(!jsonl.contains("hunter2"), "env file values must never be exported");
+        let mut env_chunks = 0;
+        for line in jsonl.lines().skip(1) {
+            let chunk: Value = serde_json::from_str(line).expect("chunk json");
+            if chunk["path"].as_str().is_some_and(|p| p.starts_with(".env")) {
+                assert_eq!(chunk["content"], "# [.env file redacted]\n");
+                env_chunks += 1;
+            }
+        }
+        assert_eq!(env_chunks, expected, "{extra_args:?}");
+
+        let report: Value = serde_json::from_str(
+            &fs::read_to_string(actual.join(format!("{}_report.json", repo_name))).expect("report"),
+        )
+        .expect("parse report");
+        let redacted = report["stats"]["env_files_redacted"].as_u64().unwrap_or(0);
+        assert_eq!(redacted, expected as u64, "{extra_args:?}");
+    }
+}
+
 #[test]
 fn export_redacts_readme_secret_from_context_pack_and_jsonl() {
     let temp = TempDir::new().expect("temp dir");
@@ -593,8 +760,13 @@ fn coalesced_chunks_have_valid_metadata_after_enrichment() {
     let jsonl =
         fs::read_to_string(actual.join(format!("{}_chunks.jsonl", repo_name))).expect("read jsonl");
 
-    // Each line should have self-consistent metadata.
-    for line in jsonl.lines() {
+    let mut lines = jsonl.lines();
+    let header: Value = serde_json::from_str(lines.next().expect("header line")).expect("header");
+    assert_eq!(header["type"], "header");
+    assert_eq!(header["chunk_count"].as_u64(), Some(jsonl.lines().count() as u64 - 1));
+
+    // Each chunk line should have self-consistent metadata.
+    for line in lines {
         let v: Value = serde_json::from_str(line).expect("valid json");
         let chunk_index = v["chunk_index"].as_u64().unwrap();
         let chunks_in_file = v["chunks_in_file"].as_u64().unwrap();
@@ -0,0 +1,17 @@
+# Restock notifications for subscribed customers.
+module Inventory
+  class Notifier
+    def initialize(mailer)
+      @mailer = mailer
+      @subscribers = Hash.new { |hash, sku| hash[sku] = [] }
+    end
+
+    def subscribe(sku, email)
+      @subscribers[sku] << email unless @subscribers[sku].include?(email)
+    end
+
+    def restocked(sku)
+      @subscribers.delete(sku)&.each { |email| @mailer.deliver(email, "#{sku} is back in stock") }
+    end
+  end
+end
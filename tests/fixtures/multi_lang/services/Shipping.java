@@ -0,0 +1,22 @@
+package inventory.services;
+
+/** Shipping quotes by weight and destination zone. */
+public class Shipping {
+    private final double baseRate;
+
+    public Shipping(double baseRate) {
+        this.baseRate = baseRate;
+    }
+
+    /** Quote for a parcel of {@code weightKg} to {@code zone} (1-5). */
+    public double quote(double weightKg, int zone) {
+        if (zone < 1 || zone > 5) {
+            throw new IllegalArgumentException("zone must be 1-5");
+        }
+        return baseRate + weightKg * zoneMultiplier(zone);
+    }
+
+    private double zoneMultiplier(int zone) {
+        return 1.0 + (zone - 1) * 0.25;
+    }
+}
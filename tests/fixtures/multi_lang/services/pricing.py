@@ -0,0 +1,18 @@
+"""Price rules for catalog items."""
+
+
+class PriceBook:
+    """Base prices with optional percentage discounts."""
+
+    def __init__(self, prices):
+        self.prices = dict(prices)
+        self.discounts = {}
+
+    def discount(self, sku, percent):
+        if not 0 <= percent <= 100:
+            raise ValueError("percent must be between 0 and 100")
+        self.discounts[sku] = percent
+
+    def price(self, sku):
+        base = self.prices[sku]
+        return round(base * (100 - self.discounts.get(sku, 0)) / 100, 2)
@@ -0,0 +1,32 @@
+// Package warehouse reserves stock in remote warehouses.
+package warehouse
+
+import "errors"
+
+// ErrInsufficientStock is returned when a reservation cannot be met.
+var ErrInsufficientStock = errors.New("insufficient stock")
+
+// Reserver reserves units of a SKU.
+type Reserver interface {
+	Reserve(sku string, units int) error
+}
+
+// Warehouse holds stock for one site.
+type Warehouse struct {
+	Site  string
+	stock map[string]int
+}
+
+// NewWarehouse returns an empty warehouse for site.
+func NewWarehouse(site string) *Warehouse {
+	return &Warehouse{Site: site, stock: map[string]int{}}
+}
+
+// Reserve takes units of sku out of stock.
+func (w *Warehouse) Reserve(sku string, units int) error {
+	if w.stock[sku] < units {
+		return ErrInsufficientStock
+	}
+	w.stock[sku] -= units
+	return nil
+}
@@ -0,0 +1,37 @@
+//! Stock levels per SKU.
+
+use std::collections::HashMap;
+
+/// Units on hand for each SKU.
+#[derive(Debug, Default)]
+pub struct Stock {
+    levels: HashMap<String, u32>,
+}
+
+impl Stock {
+    /// Adds `units` of `sku`.
+    pub fn receive(&mut self, sku: &str, units: u32) {
+        *self.levels.entry(sku.to_string()).or_insert(0) += units;
+    }
+
+    /// Removes up to `units` of `sku`, returning how many were taken.
+    pub fn pick(&mut self, sku: &str, units: u32) -> u32 {
+        let level = self.levels.entry(sku.to_string()).or_insert(0);
+        let taken = units.min(*level);
+        *level -= taken;
+        taken
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Stock;
+
+    #[test]
+    fn pick_never_exceeds_stock() {
+        let mut stock = Stock::default();
+        stock.receive("A-1", 3);
+        assert_eq!(stock.pick("A-1", 5), 3);
+        assert_eq!(stock.pick("A-1", 1), 0);
+    }
+}
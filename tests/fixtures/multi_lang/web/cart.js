@@ -0,0 +1,16 @@
+// Shopping cart helpers for the storefront.
+
+export function addItem(cart, sku, quantity = 1) {
+  const items = { ...cart.items };
+  items[sku] = (items[sku] ?? 0) + quantity;
+  return { ...cart, items };
+}
+
+export function removeItem(cart, sku) {
+  const { [sku]: _removed, ...items } = cart.items;
+  return { ...cart, items };
+}
+
+export function itemCount(cart) {
+  return Object.values(cart.items).reduce((sum, quantity) => sum + quantity, 0);
+}
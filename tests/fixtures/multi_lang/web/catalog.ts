@@ -0,0 +1,24 @@
+// Catalog lookups for the storefront.
+
+export interface Product {
+  sku: string;
+  name: string;
+  price: number;
+}
+
+export class Catalog {
+  private readonly products = new Map<string, Product>();
+
+  add(product: Product): void {
+    this.products.set(product.sku, product);
+  }
+
+  find(sku: string): Product | undefined {
+    return this.products.get(sku);
+  }
+
+  search(term: string): Product[] {
+    const needle = term.toLowerCase();
+    return [...this.products.values()].filter((p) => p.name.toLowerCase().includes(needle));
+  }
+}